@@ -40,7 +40,7 @@ esac
 print_green "Building plugins..."
 
 # Array of plugins to build
-PLUGINS=("plugin_a" "plugin_b" "plugin_c")
+PLUGINS=("plugin_a" "plugin_b" "plugin_c" "plugin_e" "plugin_http" "plugin_schema")
 BUILD_ERRORS=0
 
 # Build each plugin and track errors
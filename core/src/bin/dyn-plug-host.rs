@@ -0,0 +1,22 @@
+//! Helper process that runs plugin executions for subprocess isolation
+//!
+//! Usage: `dyn-plug-host <plugin library>`. Reads one `SubprocessRequest` per line of
+//! JSON from stdin and writes one `SubprocessResponse` per line to stdout until stdin closes.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let Some(library) = std::env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("usage: dyn-plug-host <plugin library>");
+        return ExitCode::from(2);
+    };
+
+    match dyn_plug_core::subprocess::serve(&library, std::io::stdin().lock(), std::io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("dyn-plug-host: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::config::CircuitBreakerConfig;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// State of a single plugin's circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Executions run normally
+    Closed,
+    /// Executions fail fast until the cooldown elapses
+    Open,
+    /// The cooldown elapsed; the next execution probes whether the plugin recovered
+    HalfOpen,
+}
+
+#[derive(Default)]
+struct PluginCircuit {
+    /// Failure times within the current window, oldest first
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+    probe_in_flight: bool,
+}
+
+impl PluginCircuit {
+    fn state(&self, cooldown: Duration) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// Per-plugin circuit breaker that stops executing plugins which keep failing
+///
+/// After `failure_threshold` failures within `window_ms` a plugin's circuit opens
+/// and executions are refused for `cooldown_ms`. The first execution after the
+/// cooldown is let through as a probe: success closes the circuit, failure
+/// reopens it for another cooldown.
+#[derive(Default)]
+pub struct CircuitBreaker {
+    circuits: Mutex<HashMap<String, PluginCircuit>>,
+}
+
+impl CircuitBreaker {
+    /// Create a breaker with every circuit closed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check whether a plugin may execute now, claiming the probe when half-open
+    pub fn allow(&self, plugin: &str, config: &CircuitBreakerConfig) -> bool {
+        if config.failure_threshold == 0 {
+            return true;
+        }
+
+        let mut circuits = self.circuits.lock().unwrap();
+        let Some(circuit) = circuits.get_mut(plugin) else {
+            return true;
+        };
+        match circuit.state(Duration::from_millis(config.cooldown_ms)) {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen if circuit.probe_in_flight => false,
+            CircuitState::HalfOpen => {
+                info!("Circuit for plugin '{}' is half-open, probing recovery (category: circuit_half_open)", plugin);
+                circuit.probe_in_flight = true;
+                true
+            }
+        }
+    }
+
+    /// Record the outcome of an execution that `allow` let through
+    pub fn record(&self, plugin: &str, success: bool, config: &CircuitBreakerConfig) {
+        if config.failure_threshold == 0 {
+            return;
+        }
+
+        let mut circuits = self.circuits.lock().unwrap();
+        if success {
+            if let Some(circuit) = circuits.remove(plugin) {
+                if circuit.opened_at.is_some() {
+                    info!("Circuit for plugin '{}' closed after a successful probe (category: circuit_closed)", plugin);
+                }
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        let circuit = circuits.entry(plugin.to_string()).or_default();
+        if circuit.opened_at.is_some() {
+            warn!("Probe for plugin '{}' failed, reopening circuit (category: circuit_open)", plugin);
+            circuit.opened_at = Some(now);
+            circuit.probe_in_flight = false;
+            return;
+        }
+
+        let window = Duration::from_millis(config.window_ms);
+        circuit.failures.push_back(now);
+        while circuit.failures.front().is_some_and(|&at| now.duration_since(at) > window) {
+            circuit.failures.pop_front();
+        }
+        if circuit.failures.len() >= config.failure_threshold as usize {
+            warn!("Plugin '{}' failed {} times within {:?}, opening circuit for {}ms (category: circuit_open)",
+                  plugin, circuit.failures.len(), window, config.cooldown_ms);
+            circuit.failures.clear();
+            circuit.opened_at = Some(now);
+        }
+    }
+
+    /// Give back a probe claimed by `allow` when the plugin did not end up running
+    pub fn release(&self, plugin: &str) {
+        if let Some(circuit) = self.circuits.lock().unwrap().get_mut(plugin) {
+            circuit.probe_in_flight = false;
+        }
+    }
+
+    /// Current state of a plugin's circuit
+    pub fn state(&self, plugin: &str, config: &CircuitBreakerConfig) -> CircuitState {
+        let circuits = self.circuits.lock().unwrap();
+        circuits
+            .get(plugin)
+            .map_or(CircuitState::Closed, |circuit| circuit.state(Duration::from_millis(config.cooldown_ms)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(failure_threshold: u32, cooldown_ms: u64) -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold,
+            window_ms: 60_000,
+            cooldown_ms,
+        }
+    }
+
+    #[test]
+    fn test_opens_after_threshold_and_probes_once() {
+        let breaker = CircuitBreaker::new();
+        let config = config(2, 50);
+
+        breaker.record("p", false, &config);
+        assert_eq!(breaker.state("p", &config), CircuitState::Closed);
+        breaker.record("p", false, &config);
+        assert_eq!(breaker.state("p", &config), CircuitState::Open);
+        assert!(!breaker.allow("p", &config));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(breaker.state("p", &config), CircuitState::HalfOpen);
+        assert!(breaker.allow("p", &config));
+        // Only one probe at a time
+        assert!(!breaker.allow("p", &config));
+
+        breaker.record("p", false, &config);
+        assert_eq!(breaker.state("p", &config), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_zero_threshold_disables_breaker() {
+        let breaker = CircuitBreaker::new();
+        let config = config(0, 50);
+
+        for _ in 0..10 {
+            breaker.record("p", false, &config);
+        }
+        assert!(breaker.allow("p", &config));
+        assert_eq!(breaker.state("p", &config), CircuitState::Closed);
+    }
+}
@@ -2,20 +2,142 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::fs;
+use std::io::Write;
+use fs2::FileExt;
 use anyhow::{Context, Result};
 use log::{info, warn, error};
 
+/// Schema version of newly written config files
+///
+/// Files with an older `config_version` are upgraded in memory by `ConfigManager::migrate`
+/// when loaded, and only written at the new version when the configuration is next saved.
+pub const CONFIG_VERSION: u32 = 1;
+
 /// Main configuration structure for the plugin system
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Schema version the file was written for; files without one are version 0
+    #[serde(default)]
+    pub config_version: u32,
     /// Directory where plugins are stored
     pub plugins_dir: PathBuf,
+    /// Directory where plugins persist their key-value state
+    #[serde(default = "default_state_dir")]
+    pub state_dir: PathBuf,
     /// Logging level for the system
     pub log_level: String,
     /// Server configuration for HTTP API
     pub server: ServerConfig,
     /// Per-plugin configuration settings
     pub plugins: HashMap<String, PluginConfig>,
+    /// Expected SHA-256 digests (hex) of plugin libraries, keyed by file name
+    #[serde(default)]
+    pub expected_digests: HashMap<String, String>,
+    /// Whether to scan subdirectories of the plugins directory
+    #[serde(default)]
+    pub recursive_plugin_scan: bool,
+    /// Maximum subdirectory depth to descend into when scanning recursively
+    #[serde(default = "default_max_scan_depth")]
+    pub max_scan_depth: usize,
+    /// Maximum time in milliseconds a plugin's `on_load` hook may take
+    #[serde(default = "default_plugin_load_timeout_ms")]
+    pub plugin_load_timeout_ms: u64,
+    /// Number of recent executions kept in the execution history
+    #[serde(default = "default_history_capacity")]
+    pub history_capacity: usize,
+    /// Refuse to save over a config file whose YAML anchors and aliases a rewrite would expand
+    #[serde(default)]
+    pub preserve_config_comments: bool,
+    /// Thresholds for failing fast on plugins that keep failing
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// Helper binary for subprocess isolation, defaults to `dyn-plug-host` next to the running executable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isolation_host: Option<PathBuf>,
+    /// Also load plugins installed in the system plugins directory, such as by a package manager
+    ///
+    /// A plugin in `plugins_dir` takes precedence over a system plugin with the same name.
+    #[serde(default)]
+    pub use_system_plugins: bool,
+    /// System plugins directory, defaults to `/usr/lib/dyn-plug/plugins` (`%PROGRAMDATA%\dyn-plug\plugins` on Windows)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_plugins_dir: Option<PathBuf>,
+    /// Plugin libraries loaded in addition to those found in `plugins_dir`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preload: Vec<PluginSpec>,
+    /// Record an approximate resident memory delta for each execution (Linux only)
+    #[serde(default)]
+    pub track_memory: bool,
+    /// Fail executions whose output does not match the plugin's declared `output_schema`
+    ///
+    /// Meant for development and testing, as every output is parsed and checked.
+    #[serde(default)]
+    pub strict_output_validation: bool,
+    /// Wrap each execution in a `tracing` span for subscribers such as OpenTelemetry exporters
+    #[serde(default)]
+    pub tracing_enabled: bool,
+    /// Report a SHA-256 hash of each successful execution's output
+    #[serde(default)]
+    pub hash_outputs: bool,
+    /// Testing aid that fails executions with synthetic transient errors; never enable in production
+    ///
+    /// Left out of generated configuration files; off unless set explicitly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fault_injection: Option<FaultInjectionConfig>,
+    /// What to do with a plugin whose `validate_config` rejects its settings at load
+    #[serde(default)]
+    pub invalid_plugin_config: InvalidConfigPolicy,
+}
+
+/// Handling of plugins whose settings fail their own validation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InvalidConfigPolicy {
+    /// Keep the plugin disabled until its settings are fixed
+    #[default]
+    Disable,
+    /// Log a warning and enable the plugin as configured
+    Warn,
+}
+
+/// Synthetic failures injected before plugins run, for exercising retries and circuit breakers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultInjectionConfig {
+    /// Chance from 0.0 to 1.0 that each execution attempt fails with a temporary failure
+    pub probability: f64,
+}
+
+/// A plugin library loaded from an explicit path
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginSpec {
+    /// Path to the library file
+    pub path: PathBuf,
+}
+
+fn default_state_dir() -> PathBuf {
+    PathBuf::from("target/state")
+}
+
+/// Conventional location for plugins installed system-wide
+fn default_system_plugins_dir() -> PathBuf {
+    if cfg!(windows) {
+        let program_data = std::env::var_os("PROGRAMDATA").map_or_else(|| PathBuf::from(r"C:\ProgramData"), PathBuf::from);
+        program_data.join("dyn-plug").join("plugins")
+    } else {
+        PathBuf::from("/usr/lib/dyn-plug/plugins")
+    }
+}
+
+fn default_max_scan_depth() -> usize {
+    3
+}
+
+fn default_plugin_load_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_history_capacity() -> usize {
+    1000
 }
 
 /// Server configuration for HTTP API
@@ -27,6 +149,110 @@ pub struct ServerConfig {
     pub port: u16,
     /// Whether the server is enabled
     pub enabled: bool,
+    /// Report `/health` as unhealthy (503) when any plugin failed to load
+    #[serde(default)]
+    pub fail_health_on_load_errors: bool,
+    /// API key required by administrative endpoints such as `/metrics/reset`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Reject execute requests without `Content-Type: application/json` (415)
+    #[serde(default)]
+    pub strict_content_type: bool,
+    /// Reject enabling, disabling and reconfiguring plugins over the API (403)
+    ///
+    /// Also covers per-request settings overrides, resetting metrics, and custom
+    /// routes called with methods other than GET and HEAD.
+    #[serde(default)]
+    pub read_only: bool,
+    /// Seconds an `Idempotency-Key` response is kept for replay
+    #[serde(default = "default_idempotency_ttl_secs")]
+    pub idempotency_ttl_secs: u64,
+    /// Worker threads for the server runtime and HTTP workers, defaults to one per CPU core
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker_threads: Option<usize>,
+    /// Truncate plugin output in JSON execute responses to this many bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_response_output_bytes: Option<usize>,
+    /// Executions the server handles at once before rejecting more with `429 Too Many Requests`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_pending_executions: Option<usize>,
+    /// Seconds to let in-flight requests finish on shutdown before forcing the server to stop
+    #[serde(default = "default_graceful_shutdown_secs")]
+    pub graceful_shutdown_secs: u64,
+    /// Format of the access log record emitted for each request
+    #[serde(default)]
+    pub access_log_format: AccessLogFormat,
+    /// Shape of JSON response bodies
+    #[serde(default)]
+    pub response_style: ResponseStyle,
+}
+
+/// Format of HTTP access log records
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessLogFormat {
+    /// Space-separated `key=value` pairs
+    #[default]
+    Text,
+    /// One JSON object per request
+    Json,
+    /// No access logs
+    Off,
+}
+
+/// Shape of JSON response bodies sent by the API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseStyle {
+    /// `{"success": ..., "data": ...}` or `{"success": false, "error": ...}`
+    #[default]
+    Wrapped,
+    /// The data itself, or `{"error": ...}` on failure
+    Bare,
+}
+
+fn default_idempotency_ttl_secs() -> u64 {
+    300
+}
+
+fn default_graceful_shutdown_secs() -> u64 {
+    30
+}
+
+/// Per-plugin circuit breaker thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures within the window that open a plugin's circuit, 0 (the default) to disable
+    #[serde(default = "default_circuit_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Window in milliseconds in which failures are counted
+    #[serde(default = "default_circuit_window_ms")]
+    pub window_ms: u64,
+    /// Milliseconds an open circuit refuses executions before probing recovery
+    #[serde(default = "default_circuit_cooldown_ms")]
+    pub cooldown_ms: u64,
+}
+
+fn default_circuit_failure_threshold() -> u32 {
+    0
+}
+
+fn default_circuit_window_ms() -> u64 {
+    60_000
+}
+
+fn default_circuit_cooldown_ms() -> u64 {
+    30_000
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_circuit_failure_threshold(),
+            window_ms: default_circuit_window_ms(),
+            cooldown_ms: default_circuit_cooldown_ms(),
+        }
+    }
 }
 
 /// Configuration for individual plugins
@@ -36,15 +262,76 @@ pub struct PluginConfig {
     pub enabled: bool,
     /// Plugin-specific settings as key-value pairs
     pub settings: HashMap<String, serde_json::Value>,
+    /// Feature flags exposed to the plugin through its execution context
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub feature_flags: HashMap<String, bool>,
+    /// Where the plugin's executions run
+    #[serde(default, skip_serializing_if = "Isolation::is_in_process")]
+    pub isolation: Isolation,
+    /// Idle helper processes kept ready for subprocess isolation; 0 starts one per execution
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub pool_size: usize,
+    /// Operations the host lets the plugin run; all operations when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_operations: Option<Vec<String>>,
+    /// Settings a caller may override for a single execution; none when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overridable_settings: Vec<String>,
+    /// Timeout in milliseconds for executions using default options, instead of the global default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// When the plugin was last enabled or disabled, as an RFC 3339 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_state_change: Option<String>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+/// Where a plugin's executions run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Isolation {
+    /// Call into the plugin library loaded in the host process
+    #[default]
+    InProcess,
+    /// Run each execution in a separate helper process, killed when it times out
+    Subprocess,
+}
+
+impl Isolation {
+    fn is_in_process(&self) -> bool {
+        *self == Isolation::InProcess
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            config_version: CONFIG_VERSION,
             plugins_dir: PathBuf::from("target/plugins"),
+            state_dir: default_state_dir(),
             log_level: "info".to_string(),
             server: ServerConfig::default(),
             plugins: HashMap::new(),
+            expected_digests: HashMap::new(),
+            recursive_plugin_scan: false,
+            max_scan_depth: default_max_scan_depth(),
+            plugin_load_timeout_ms: default_plugin_load_timeout_ms(),
+            history_capacity: default_history_capacity(),
+            preserve_config_comments: false,
+            circuit_breaker: CircuitBreakerConfig::default(),
+            isolation_host: None,
+            preload: Vec::new(),
+            track_memory: false,
+            strict_output_validation: false,
+            tracing_enabled: false,
+            hash_outputs: false,
+            fault_injection: None,
+            invalid_plugin_config: InvalidConfigPolicy::default(),
+            use_system_plugins: false,
+            system_plugins_dir: None,
         }
     }
 }
@@ -55,6 +342,17 @@ impl Default for ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 8080,
             enabled: true,
+            fail_health_on_load_errors: false,
+            api_key: None,
+            strict_content_type: false,
+            read_only: false,
+            idempotency_ttl_secs: default_idempotency_ttl_secs(),
+            worker_threads: None,
+            max_response_output_bytes: None,
+            max_pending_executions: None,
+            graceful_shutdown_secs: default_graceful_shutdown_secs(),
+            access_log_format: AccessLogFormat::Text,
+            response_style: ResponseStyle::Wrapped,
         }
     }
 }
@@ -64,28 +362,110 @@ impl Default for PluginConfig {
         Self {
             enabled: true,
             settings: HashMap::new(),
+            feature_flags: HashMap::new(),
+            isolation: Isolation::InProcess,
+            pool_size: 0,
+            allowed_operations: None,
+            overridable_settings: Vec::new(),
+            timeout_ms: None,
+            last_state_change: None,
         }
     }
 }
 
+/// Environment variables that override values from the configuration file
+pub const ENV_OVERRIDES: &[&str] = &[
+    "DYN_PLUG_PLUGINS_DIR",
+    "DYN_PLUG_STATE_DIR",
+    "DYN_PLUG_LOG_LEVEL",
+    "DYN_PLUG_SERVER_HOST",
+    "DYN_PLUG_SERVER_PORT",
+];
+
 /// Configuration manager handles loading, saving, and validating configuration
+///
+/// The in-memory configuration reflects environment overrides; overridden
+/// fields keep their file values when the configuration is saved.
 pub struct ConfigManager {
     config: Config,
+    file_config: Config,
+    env_overrides: Vec<&'static str>,
     config_path: PathBuf,
+    /// Whether the file on disk uses YAML anchors or aliases, which saving expands
+    uses_yaml_aliases: bool,
 }
 
 impl ConfigManager {
     /// Create a new configuration manager with the specified config file path
     pub fn new<P: AsRef<Path>>(config_path: P) -> Result<Self> {
         let config_path = config_path.as_ref().to_path_buf();
-        let config = Self::load_or_create_default(&config_path)?;
+        let file_config = Self::load_or_create_default(&config_path)?;
+        let (config, env_overrides) = Self::resolve(&file_config, |var| std::env::var(var).ok())?;
+        let uses_yaml_aliases = Self::file_uses_yaml_aliases(&config_path);
         
         Ok(Self {
             config,
+            file_config,
+            env_overrides,
             config_path,
+            uses_yaml_aliases,
         })
     }
 
+    /// Apply environment overrides to a file configuration and re-validate it
+    ///
+    /// Returns the effective configuration and the overrides that were applied.
+    pub fn resolve<F>(file_config: &Config, lookup: F) -> Result<(Config, Vec<&'static str>)>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut config = file_config.clone();
+        let mut applied = Vec::new();
+
+        for &var in ENV_OVERRIDES {
+            let Some(value) = lookup(var) else { continue };
+            match var {
+                "DYN_PLUG_PLUGINS_DIR" => config.plugins_dir = PathBuf::from(&value),
+                "DYN_PLUG_STATE_DIR" => config.state_dir = PathBuf::from(&value),
+                "DYN_PLUG_LOG_LEVEL" => config.log_level = value.clone(),
+                "DYN_PLUG_SERVER_HOST" => config.server.host = value.clone(),
+                "DYN_PLUG_SERVER_PORT" => match value.parse() {
+                    Ok(port) => config.server.port = port,
+                    Err(_) => {
+                        warn!("Ignoring invalid {} value '{}'", var, value);
+                        continue;
+                    }
+                },
+                _ => continue,
+            }
+            info!("Configuration override from environment: {}={}", var, value);
+            applied.push(var);
+        }
+
+        Ok((Self::validate_and_fix_config(config)?, applied))
+    }
+
+    /// The configuration as it is saved to the file, with environment overrides undone
+    pub fn without_env_overrides(&self) -> Config {
+        let mut config = self.config.clone();
+        for &var in &self.env_overrides {
+            match var {
+                "DYN_PLUG_PLUGINS_DIR" => config.plugins_dir = self.file_config.plugins_dir.clone(),
+                "DYN_PLUG_STATE_DIR" => config.state_dir = self.file_config.state_dir.clone(),
+                "DYN_PLUG_LOG_LEVEL" => config.log_level = self.file_config.log_level.clone(),
+                "DYN_PLUG_SERVER_HOST" => config.server.host = self.file_config.server.host.clone(),
+                "DYN_PLUG_SERVER_PORT" => config.server.port = self.file_config.server.port,
+                _ => {}
+            }
+        }
+        config
+    }
+
+    /// Environment overrides applied to the in-memory configuration
+    pub fn env_overrides(&self) -> &[&'static str] {
+        &self.env_overrides
+    }
+
     /// Create a configuration manager with default config file location
     pub fn with_default_path() -> Result<Self> {
         let config_path = Self::default_config_path()?;
@@ -131,7 +511,37 @@ impl ConfigManager {
                 Config::default()
             });
 
-        Self::validate_and_fix_config(config)
+        let mut config = Self::validate_and_fix_config(config)?;
+        // Loading never rewrites the file; the migrated version is written on the next save
+        if let Some(&from_version) = Self::migrate(&mut config).first() {
+            info!("Migrated configuration {} from version {} to {}", config_path.display(), from_version, CONFIG_VERSION);
+        }
+        Ok(config)
+    }
+
+    /// Upgrade a configuration written for an older schema to `CONFIG_VERSION`
+    ///
+    /// Returns each version migrated from, oldest first; empty when the configuration
+    /// is already current. Each step only fills in what the next schema expects.
+    pub fn migrate(config: &mut Config) -> Vec<u32> {
+        if config.config_version > CONFIG_VERSION {
+            warn!("Configuration version {} is newer than supported version {}", config.config_version, CONFIG_VERSION);
+        }
+
+        let mut migrated = Vec::new();
+        while config.config_version < CONFIG_VERSION {
+            // Version 1 only introduced `config_version`, so files without one need no changes
+            migrated.push(config.config_version);
+            config.config_version += 1;
+        }
+        migrated
+    }
+
+    /// Check whether the config file defines YAML anchors or uses aliases
+    fn file_uses_yaml_aliases(config_path: &Path) -> bool {
+        fs::read_to_string(config_path)
+            .map(|content| contains_yaml_aliases(&content))
+            .unwrap_or(false)
     }
 
     /// Backup invalid configuration file
@@ -144,32 +554,70 @@ impl ConfigManager {
     }
 
     /// Validate configuration and apply defaults for invalid values
-    fn validate_and_fix_config(mut config: Config) -> Result<Config> {
+    fn validate_and_fix_config(config: Config) -> Result<Config> {
+        let (config, fixes) = Self::fix_config(config);
+        for fix in fixes {
+            warn!("{}", fix);
+        }
+        Ok(config)
+    }
+
+    /// Validate a configuration without fixing it, failing with every problem found
+    pub fn validate(config: Config) -> Result<Config> {
+        let (config, fixes) = Self::fix_config(config);
+        if !fixes.is_empty() {
+            anyhow::bail!("Invalid configuration: {}", fixes.join("; "));
+        }
+        Ok(config)
+    }
+
+    /// Replace invalid values with defaults, describing each replacement
+    fn fix_config(mut config: Config) -> (Config, Vec<String>) {
+        let mut fixes = Vec::new();
+
         // Validate and fix plugins directory
         if config.plugins_dir.as_os_str().is_empty() {
-            warn!("Empty plugins directory, using default");
+            fixes.push("Empty plugins directory, using default".to_string());
             config.plugins_dir = PathBuf::from("target/plugins");
         }
 
+        // Validate and fix state directory
+        if config.state_dir.as_os_str().is_empty() {
+            fixes.push("Empty state directory, using default".to_string());
+            config.state_dir = default_state_dir();
+        }
+
         // Validate and fix log level
         let valid_log_levels = ["error", "warn", "info", "debug", "trace"];
         if !valid_log_levels.contains(&config.log_level.as_str()) {
-            warn!("Invalid log level '{}', using 'info'", config.log_level);
+            fixes.push(format!("Invalid log level '{}', using 'info'", config.log_level));
             config.log_level = "info".to_string();
         }
 
         // Validate and fix server configuration
         if config.server.host.is_empty() {
-            warn!("Empty server host, using default");
+            fixes.push("Empty server host, using default".to_string());
             config.server.host = "127.0.0.1".to_string();
         }
 
         if config.server.port == 0 {
-            warn!("Invalid server port {}, using default 8080", config.server.port);
+            fixes.push(format!("Invalid server port {}, using default 8080", config.server.port));
             config.server.port = 8080;
         }
+        
+        if config.server.worker_threads == Some(0) {
+            fixes.push("Invalid server worker_threads 0, using one per CPU core".to_string());
+            config.server.worker_threads = None;
+        }
 
-        Ok(config)
+        if let Some(fault_injection) = &mut config.fault_injection {
+            if !(0.0..=1.0).contains(&fault_injection.probability) {
+                fixes.push(format!("Invalid fault_injection probability {}, using 0", fault_injection.probability));
+                fault_injection.probability = 0.0;
+            }
+        }
+
+        (config, fixes)
     }
 
     /// Save configuration to YAML file
@@ -183,13 +631,41 @@ impl ConfigManager {
         let yaml_content = serde_yaml::to_string(config)
             .context("Failed to serialize configuration to YAML")?;
 
-        fs::write(config_path, yaml_content)
+        // Serialize writers on a sidecar lock file; the config file itself is replaced by the rename
+        let lock_path = Self::lock_path(config_path);
+        let lock_file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open config lock file: {}", lock_path.display()))?;
+        lock_file.lock_exclusive()
+            .with_context(|| format!("Failed to lock config file: {}", lock_path.display()))?;
+
+        // Write a temp file in the same directory and rename it over the config so readers
+        // never see a partially written file
+        let dir = config_path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let mut temp_file = tempfile::NamedTempFile::new_in(dir)
+            .with_context(|| format!("Failed to create temp file in: {}", dir.display()))?;
+        temp_file.write_all(yaml_content.as_bytes())
+            .and_then(|_| temp_file.as_file().sync_all())
             .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
+        temp_file.persist(config_path)
+            .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
+
+        let _ = FileExt::unlock(&lock_file);
 
         info!("Configuration saved to: {}", config_path.display());
         Ok(())
     }
 
+    /// Path of the advisory lock file guarding writes to `config_path`
+    fn lock_path(config_path: &Path) -> PathBuf {
+        let mut name = config_path.file_name().unwrap_or_default().to_os_string();
+        name.push(".lock");
+        config_path.with_file_name(name)
+    }
+
     /// Get a reference to the current configuration
     pub fn config(&self) -> &Config {
         &self.config
@@ -201,27 +677,89 @@ impl ConfigManager {
     }
 
     /// Save the current configuration to file
-    pub fn save(&self) -> Result<()> {
-        Self::save_to_file(&self.config, &self.config_path)
+    ///
+    /// YAML anchors and aliases in the file are expanded by the rewrite; this is
+    /// logged as a warning, or refused when `preserve_config_comments` is set.
+    pub fn save(&mut self) -> Result<()> {
+        if self.uses_yaml_aliases {
+            if self.config.preserve_config_comments {
+                anyhow::bail!(
+                    "Refusing to save {}: it uses YAML anchors or aliases that would be expanded (preserve_config_comments is set)",
+                    self.config_path.display()
+                );
+            }
+            warn!(
+                "Config file {} uses YAML anchors or aliases; saving will expand them and rewrite the file",
+                self.config_path.display()
+            );
+        }
+        Self::save_to_file(&self.without_env_overrides(), &self.config_path)?;
+        self.uses_yaml_aliases = false;
+        Ok(())
+    }
+
+    /// Replace the file configuration, persist it and re-apply environment overrides
+    ///
+    /// The configuration is rejected without being saved when `validate` finds problems.
+    pub fn replace(&mut self, config: Config) -> Result<()> {
+        let mut file_config = Self::validate(config)?;
+        Self::migrate(&mut file_config);
+        Self::save_to_file(&file_config, &self.config_path)?;
+        let (config, env_overrides) = Self::resolve(&file_config, |var| std::env::var(var).ok())?;
+        self.file_config = file_config;
+        self.config = config;
+        self.env_overrides = env_overrides;
+        self.uses_yaml_aliases = false;
+        Ok(())
+    }
+
+    /// Whether the loaded config file uses YAML anchors or aliases
+    pub fn uses_yaml_aliases(&self) -> bool {
+        self.uses_yaml_aliases
     }
 
     /// Reload configuration from file
     pub fn reload(&mut self) -> Result<()> {
-        self.config = Self::load_or_create_default(&self.config_path)?;
+        self.file_config = Self::load_or_create_default(&self.config_path)?;
+        let (config, env_overrides) = Self::resolve(&self.file_config, |var| std::env::var(var).ok())?;
+        self.config = config;
+        self.env_overrides = env_overrides;
+        self.uses_yaml_aliases = Self::file_uses_yaml_aliases(&self.config_path);
         Ok(())
     }
 
+    /// Re-read one plugin's settings from the config file, leaving the rest of the configuration as is
+    ///
+    /// Returns the plugin's settings as now in effect. A missing or unparsable file is
+    /// an error that leaves the settings in effect untouched.
+    pub fn reload_plugin_settings(&mut self, plugin_name: &str) -> Result<HashMap<String, serde_json::Value>> {
+        let content = fs::read_to_string(&self.config_path)
+            .with_context(|| format!("Failed to read config file: {}", self.config_path.display()))?;
+        let file_config: Config = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file: {}", self.config_path.display()))?;
+        let settings = file_config.plugins
+            .get(plugin_name)
+            .map(|p| p.settings.clone())
+            .unwrap_or_default();
+        for config in [&mut self.file_config, &mut self.config] {
+            config.plugins.entry(plugin_name.to_string()).or_default().settings = settings.clone();
+        }
+        info!("Plugin '{}' settings reloaded from {}", plugin_name, self.config_path.display());
+        Ok(settings)
+    }
+
     /// Get plugin configuration, creating default if it doesn't exist
     pub fn get_plugin_config(&mut self, plugin_name: &str) -> &mut PluginConfig {
         self.config.plugins
             .entry(plugin_name.to_string())
-            .or_insert_with(PluginConfig::default)
+            .or_default()
     }
 
     /// Enable a plugin and persist the change
     pub fn enable_plugin(&mut self, plugin_name: &str) -> Result<()> {
         let plugin_config = self.get_plugin_config(plugin_name);
         plugin_config.enabled = true;
+        plugin_config.last_state_change = Some(chrono::Utc::now().to_rfc3339());
         self.save()?;
         info!("Plugin '{}' enabled", plugin_name);
         Ok(())
@@ -231,6 +769,7 @@ impl ConfigManager {
     pub fn disable_plugin(&mut self, plugin_name: &str) -> Result<()> {
         let plugin_config = self.get_plugin_config(plugin_name);
         plugin_config.enabled = false;
+        plugin_config.last_state_change = Some(chrono::Utc::now().to_rfc3339());
         self.save()?;
         info!("Plugin '{}' disabled", plugin_name);
         Ok(())
@@ -244,6 +783,13 @@ impl ConfigManager {
             .unwrap_or(true) // Default to enabled if not configured
     }
 
+    /// When a plugin was last enabled or disabled, if ever
+    pub fn last_state_change(&self, plugin_name: &str) -> Option<String> {
+        self.config.plugins
+            .get(plugin_name)
+            .and_then(|config| config.last_state_change.clone())
+    }
+
     /// Set plugin setting and persist the change
     pub fn set_plugin_setting(&mut self, plugin_name: &str, key: &str, value: serde_json::Value) -> Result<()> {
         let plugin_config = self.get_plugin_config(plugin_name);
@@ -253,6 +799,15 @@ impl ConfigManager {
         Ok(())
     }
 
+    /// Set a plugin feature flag and save the configuration
+    pub fn set_feature_flag(&mut self, plugin_name: &str, flag: &str, enabled: bool) -> Result<()> {
+        let plugin_config = self.get_plugin_config(plugin_name);
+        plugin_config.feature_flags.insert(flag.to_string(), enabled);
+        self.save()?;
+        info!("Plugin '{}' feature flag '{}' set to {}", plugin_name, flag, enabled);
+        Ok(())
+    }
+
     /// Get plugin setting
     pub fn get_plugin_setting(&self, plugin_name: &str, key: &str) -> Option<&serde_json::Value> {
         self.config.plugins
@@ -261,6 +816,60 @@ impl ConfigManager {
             .get(key)
     }
 
+    /// Get the feature flags configured for a plugin
+    pub fn feature_flags(&self, plugin_name: &str) -> HashMap<String, bool> {
+        self.config.plugins
+            .get(plugin_name)
+            .map(|p| p.feature_flags.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get where a plugin's executions run, in process unless configured otherwise
+    pub fn isolation(&self, plugin_name: &str) -> Isolation {
+        self.config.plugins
+            .get(plugin_name)
+            .map(|p| p.isolation)
+            .unwrap_or_default()
+    }
+
+    /// Get the number of warm helper processes kept for a subprocess-isolated plugin
+    pub fn pool_size(&self, plugin_name: &str) -> usize {
+        self.config.plugins
+            .get(plugin_name)
+            .map_or(0, |p| p.pool_size)
+    }
+
+    /// Get the operations a plugin is restricted to, `None` when it is unrestricted
+    pub fn allowed_operations(&self, plugin_name: &str) -> Option<Vec<String>> {
+        self.config.plugins
+            .get(plugin_name)
+            .and_then(|p| p.allowed_operations.clone())
+    }
+
+    /// Get the settings callers may override for a single execution of the plugin
+    pub fn overridable_settings(&self, plugin_name: &str) -> Vec<String> {
+        self.config.plugins
+            .get(plugin_name)
+            .map(|p| p.overridable_settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Get the plugin's own default execution timeout, if configured
+    pub fn plugin_timeout(&self, plugin_name: &str) -> Option<std::time::Duration> {
+        self.config.plugins
+            .get(plugin_name)
+            .and_then(|p| p.timeout_ms)
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// Get the configured settings for a plugin, empty when it has none
+    pub fn plugin_settings(&self, plugin_name: &str) -> HashMap<String, serde_json::Value> {
+        self.config.plugins
+            .get(plugin_name)
+            .map(|p| p.settings.clone())
+            .unwrap_or_default()
+    }
+
     /// Update server configuration and persist the change
     pub fn update_server_config(&mut self, host: Option<String>, port: Option<u16>, enabled: Option<bool>) -> Result<()> {
         if let Some(host) = host {
@@ -282,6 +891,14 @@ impl ConfigManager {
         &self.config.plugins_dir
     }
 
+    /// System plugins directory to scan, or `None` unless `use_system_plugins` is set
+    pub fn system_plugins_dir(&self) -> Option<PathBuf> {
+        if !self.config.use_system_plugins {
+            return None;
+        }
+        Some(self.config.system_plugins_dir.clone().unwrap_or_else(default_system_plugins_dir))
+    }
+
     /// Update plugins directory and persist the change
     pub fn set_plugins_dir<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
         self.config.plugins_dir = path.as_ref().to_path_buf();
@@ -291,6 +908,34 @@ impl ConfigManager {
     }
 }
 
+/// Lexically check raw YAML for anchors (`&name`) or aliases (`*name`)
+///
+/// Tokens inside quotes or comments are ignored; this only needs to be good
+/// enough to warn before a save expands them.
+fn contains_yaml_aliases(content: &str) -> bool {
+    content.lines().any(|line| {
+        let mut in_single = false;
+        let mut in_double = false;
+        let mut previous = ' ';
+        for (i, c) in line.char_indices() {
+            match c {
+                '\'' if !in_double => in_single = !in_single,
+                '"' if !in_single => in_double = !in_double,
+                '#' if !in_single && !in_double && previous.is_whitespace() => return false,
+                '&' | '*' if !in_single && !in_double && (previous.is_whitespace() || previous == '[' || previous == ',') => {
+                    let next = line[i + c.len_utf8()..].chars().next();
+                    if next.is_some_and(|n| n.is_alphanumeric() || n == '_' || n == '-') {
+                        return true;
+                    }
+                }
+                _ => {}
+            }
+            previous = c;
+        }
+        false
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,11 +945,15 @@ mod tests {
     fn test_default_config() {
         let config = Config::default();
         assert_eq!(config.plugins_dir, PathBuf::from("target/plugins"));
+        assert_eq!(config.state_dir, PathBuf::from("target/state"));
         assert_eq!(config.log_level, "info");
         assert_eq!(config.server.host, "127.0.0.1");
         assert_eq!(config.server.port, 8080);
         assert!(config.server.enabled);
         assert!(config.plugins.is_empty());
+        assert!(config.expected_digests.is_empty());
+        assert!(!config.recursive_plugin_scan);
+        assert_eq!(config.max_scan_depth, 3);
     }
 
     #[test]
@@ -353,6 +1002,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_config_validation() {
         let mut config = Config::default();
         config.log_level = "invalid".to_string();
@@ -365,6 +1015,60 @@ mod tests {
         assert_eq!(fixed_config.server.host, "127.0.0.1");
     }
 
+    #[test]
+    fn test_contains_yaml_aliases() {
+        assert!(contains_yaml_aliases("base: &base\n  enabled: true\nplugins:\n  a: *base\n"));
+        assert!(contains_yaml_aliases("plugin_a:\n  <<: *defaults\n"));
+        assert!(!contains_yaml_aliases("host: \"a&b\"\nlog_level: info # &not-an-anchor\n"));
+        assert!(!contains_yaml_aliases("pattern: '*glob'\nport: 8080\n"));
+    }
+
+    #[test]
+    fn test_preserve_config_comments_refuses_save_over_aliases() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        let content = "plugins_dir: plugins\nlog_level: info\npreserve_config_comments: true\nserver: &server\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\nplugins: {}\n";
+        fs::write(&config_path, content).unwrap();
+
+        let mut manager = ConfigManager::new(&config_path).unwrap();
+        assert!(manager.uses_yaml_aliases());
+        assert!(manager.save().is_err());
+        assert!(manager.uses_yaml_aliases());
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), content);
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let file_config = Config::default();
+        let (config, applied) = ConfigManager::resolve(&file_config, |var| match var {
+            "DYN_PLUG_PLUGINS_DIR" => Some("/opt/plugins".to_string()),
+            "DYN_PLUG_SERVER_PORT" => Some("not-a-port".to_string()),
+            _ => None,
+        }).unwrap();
+        
+        assert_eq!(config.plugins_dir, PathBuf::from("/opt/plugins"));
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(applied, vec!["DYN_PLUG_PLUGINS_DIR"]);
+        assert_eq!(file_config.plugins_dir, PathBuf::from("target/plugins"));
+    }
+
+    #[test]
+    fn test_without_env_overrides_restores_file_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manager = ConfigManager::new(temp_dir.path().join("config.yaml")).unwrap();
+        let (config, env_overrides) = ConfigManager::resolve(&manager.file_config, |var| {
+            (var == "DYN_PLUG_SERVER_PORT").then(|| "9191".to_string())
+        }).unwrap();
+        manager.config = config;
+        manager.env_overrides = env_overrides;
+        manager.config.log_level = "debug".to_string();
+
+        assert_eq!(manager.config().server.port, 9191);
+        let saved = manager.without_env_overrides();
+        assert_eq!(saved.server.port, 8080);
+        assert_eq!(saved.log_level, "debug");
+    }
+
     #[test]
     fn test_config_persistence() {
         let temp_dir = TempDir::new().unwrap();
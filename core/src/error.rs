@@ -63,6 +63,18 @@ pub enum PluginError {
     /// Temporary failure that may be retried
     #[error("Temporary failure: {message}")]
     TemporaryFailure { message: String },
+    
+    /// Plugin input failed validation
+    #[error("Invalid input: {message}")]
+    InvalidInput { message: String },
+    
+    /// Plugin execution failed with a structured `PluginExecutionError`
+    #[error("Plugin execution failed [{code}]: {message}")]
+    PluginFailure { code: String, message: String, retryable: bool },
+    
+    /// The loaded plugin version does not satisfy a requested version constraint
+    #[error("Plugin {name} version {found} does not satisfy {required}")]
+    VersionMismatch { name: String, required: String, found: String },
 }
 
 impl PluginError {
@@ -118,6 +130,13 @@ impl PluginError {
         }
     }
     
+    /// Create a new InvalidInput error
+    pub fn invalid_input<S: Into<String>>(message: S) -> Self {
+        Self::InvalidInput {
+            message: message.into(),
+        }
+    }
+    
     /// Check if this error is transient and worth retrying
     pub fn is_transient(&self) -> bool {
         match self {
@@ -138,10 +157,12 @@ impl PluginError {
                 message.contains("busy") ||
                 message.contains("in use")
             }
+            PluginError::LoadingFailed { source } => is_transient_loading_error(source),
             PluginError::NetworkError { .. } => true,
             PluginError::TimeoutError { .. } => true,
             PluginError::ResourceExhausted { .. } => true,
             PluginError::TemporaryFailure { .. } => true,
+            PluginError::PluginFailure { retryable, .. } => *retryable,
             _ => false,
         }
     }
@@ -185,6 +206,15 @@ impl PluginError {
             PluginError::TemporaryFailure { message } => {
                 format!("Temporary failure: {}. Please try again in a moment.", message)
             }
+            PluginError::InvalidInput { message } => {
+                format!("Invalid input: {}. Check plugin input format and try again.", message)
+            }
+            PluginError::PluginFailure { code, message, .. } => {
+                format!("Plugin execution failed [{}]: {}.", code, message)
+            }
+            PluginError::VersionMismatch { name, required, found } => {
+                format!("Plugin '{}' is loaded at version {}, which does not satisfy '{}'.", name, found, required)
+            }
         }
     }
     
@@ -203,7 +233,70 @@ impl PluginError {
             PluginError::TimeoutError { .. } => "timeout_error",
             PluginError::ResourceExhausted { .. } => "resource_exhausted",
             PluginError::TemporaryFailure { .. } => "temporary_failure",
+            PluginError::InvalidInput { .. } => "invalid_input",
+            PluginError::PluginFailure { .. } => "execution_failed",
+            PluginError::VersionMismatch { .. } => "version_mismatch",
+        }
+    }
+    
+    /// Plugin-defined error code, for failures reported as a `PluginExecutionError`
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            PluginError::PluginFailure { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+}
+
+/// Structured error a plugin can return from `execute` instead of a plain message
+///
+/// The host downcasts plugin errors to this type to honor `retryable` when
+/// deciding whether to retry, and reports `code` to callers.
+#[derive(Error, Debug, Clone)]
+#[error("{message}")]
+pub struct PluginExecutionError {
+    pub code: String,
+    pub message: String,
+    pub retryable: bool,
+}
+
+impl PluginExecutionError {
+    /// Create a non-retryable error with a plugin-defined code
+    pub fn new<C: Into<String>, M: Into<String>>(code: C, message: M) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            retryable: false,
+        }
+    }
+    
+    /// Mark the error as worth retrying
+    pub fn retryable(mut self) -> Self {
+        self.retryable = true;
+        self
+    }
+}
+
+/// Library loader failures caused by the system rather than the library itself
+///
+/// A library that is still being written or is briefly locked can load on a later
+/// attempt; an invalid format or a missing symbol never will.
+const TRANSIENT_LOAD_ERRORS: &[&str] = &[
+    "Text file busy",
+    "Resource temporarily unavailable",
+    "Interrupted system call",
+    "Cannot allocate memory",
+    "being used by another process",
+];
+
+/// Check whether a library loading error is worth retrying
+fn is_transient_loading_error(error: &libloading::Error) -> bool {
+    match error {
+        libloading::Error::DlOpen { .. } | libloading::Error::LoadLibraryExW { .. } => {
+            let message = error.to_string();
+            TRANSIENT_LOAD_ERRORS.iter().any(|m| message.contains(m))
         }
+        _ => false,
     }
 }
 
@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A single recorded plugin execution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExecutionRecord {
+    pub plugin: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub executed_at: DateTime<Utc>,
+    /// Size of the input as the caller sent it, before input middleware
+    #[serde(default)]
+    pub input_bytes: u64,
+    /// Output size of a successful execution, 0 for failures
+    #[serde(default)]
+    pub output_bytes: u64,
+}
+
+struct HistoryState {
+    records: VecDeque<ExecutionRecord>,
+    /// Number of lines in the backing file, used to decide when to compact it
+    file_lines: usize,
+}
+
+/// Bounded history of recent plugin executions
+///
+/// Records are kept in memory up to `capacity` and, when a backing file is
+/// configured, appended to it as JSON lines so short-lived CLI invocations
+/// and long-running servers share one history.
+pub struct ExecutionHistory {
+    state: Mutex<HistoryState>,
+    capacity: usize,
+    path: Option<PathBuf>,
+}
+
+impl ExecutionHistory {
+    /// Create an in-memory history holding at most `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(HistoryState {
+                records: VecDeque::new(),
+                file_lines: 0,
+            }),
+            capacity,
+            path: None,
+        }
+    }
+
+    /// Create a history backed by a JSON lines file, loading its most recent records
+    ///
+    /// Unreadable lines are skipped so a partially written file never blocks startup.
+    pub fn open<P: AsRef<Path>>(path: P, capacity: usize) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut records = VecDeque::new();
+        let mut file_lines = 0;
+
+        if let Ok(content) = fs::read_to_string(&path) {
+            for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                file_lines += 1;
+                match serde_json::from_str::<ExecutionRecord>(line) {
+                    Ok(record) => {
+                        records.push_back(record);
+                        if records.len() > capacity {
+                            records.pop_front();
+                        }
+                    }
+                    Err(e) => warn!("Skipping unreadable execution history entry in {:?}: {}", path, e),
+                }
+            }
+            debug!("Loaded {} execution history records from {:?}", records.len(), path);
+        }
+
+        Self {
+            state: Mutex::new(HistoryState { records, file_lines }),
+            capacity,
+            path: Some(path),
+        }
+    }
+
+    /// Record an execution, evicting the oldest record when full
+    pub fn record(&self, record: ExecutionRecord) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(path) = &self.path {
+            if let Err(e) = Self::append_to_file(path, &record) {
+                warn!("Failed to persist execution history to {:?}: {}", path, e);
+            } else {
+                state.file_lines += 1;
+            }
+        }
+
+        state.records.push_back(record);
+        while state.records.len() > self.capacity {
+            state.records.pop_front();
+        }
+
+        // Keep the backing file from growing without bound
+        if let Some(path) = &self.path {
+            if state.file_lines > self.capacity.saturating_mul(2).max(1) {
+                match Self::rewrite_file(path, &state.records) {
+                    Ok(()) => state.file_lines = state.records.len(),
+                    Err(e) => warn!("Failed to compact execution history {:?}: {}", path, e),
+                }
+            }
+        }
+    }
+
+    /// Get recorded executions, oldest first
+    ///
+    /// When `since` is given only executions strictly after that instant are
+    /// returned, so a client can poll with the last timestamp it has seen
+    /// without receiving duplicates. `limit` keeps the most recent entries.
+    pub fn recent(&self, since: Option<DateTime<Utc>>, limit: Option<usize>) -> Vec<ExecutionRecord> {
+        let state = self.state.lock().unwrap();
+        let matching: Vec<ExecutionRecord> = state.records
+            .iter()
+            .filter(|record| since.is_none_or(|since| record.executed_at > since))
+            .cloned()
+            .collect();
+
+        match limit {
+            Some(limit) if matching.len() > limit => matching[matching.len() - limit..].to_vec(),
+            _ => matching,
+        }
+    }
+
+    /// Number of records currently held
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().records.len()
+    }
+
+    /// Whether no executions have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn append_to_file(path: &Path, record: &ExecutionRecord) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)
+    }
+
+    fn rewrite_file(path: &Path, records: &VecDeque<ExecutionRecord>) -> std::io::Result<()> {
+        let mut content = String::new();
+        for record in records {
+            content.push_str(&serde_json::to_string(record)?);
+            content.push('\n');
+        }
+        let tmp_path = path.with_extension("jsonl.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn record_at(plugin: &str, secs: i64) -> ExecutionRecord {
+        ExecutionRecord {
+            plugin: plugin.to_string(),
+            success: true,
+            duration_ms: 1,
+            executed_at: Utc.timestamp_opt(secs, 0).unwrap(),
+            input_bytes: 0,
+            output_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn test_since_filter_is_exclusive() {
+        let history = ExecutionHistory::new(10);
+        history.record(record_at("a", 100));
+        history.record(record_at("b", 200));
+        history.record(record_at("c", 300));
+
+        let since = Utc.timestamp_opt(200, 0).unwrap();
+        let recent = history.recent(Some(since), None);
+        assert_eq!(recent, vec![record_at("c", 300)]);
+
+        let since = Utc.timestamp_opt(199, 0).unwrap();
+        assert_eq!(history.recent(Some(since), None).len(), 2);
+        assert_eq!(history.recent(None, None).len(), 3);
+        assert_eq!(history.recent(None, Some(1)), vec![record_at("c", 300)]);
+    }
+
+    #[test]
+    fn test_capacity_evicts_oldest() {
+        let history = ExecutionHistory::new(2);
+        history.record(record_at("a", 1));
+        history.record(record_at("b", 2));
+        history.record(record_at("c", 3));
+
+        let plugins: Vec<String> = history.recent(None, None).into_iter().map(|r| r.plugin).collect();
+        assert_eq!(plugins, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_file_backed_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("history.jsonl");
+
+        let history = ExecutionHistory::open(&path, 3);
+        for secs in 1..=10 {
+            history.record(record_at("a", secs));
+        }
+
+        // Compaction keeps the file bounded while preserving the newest records
+        let lines = fs::read_to_string(&path).unwrap().lines().count();
+        assert!(lines <= 6);
+
+        let reopened = ExecutionHistory::open(&path, 3);
+        let times: Vec<i64> = reopened.recent(None, None).iter().map(|r| r.executed_at.timestamp()).collect();
+        assert_eq!(times, vec![8, 9, 10]);
+    }
+}
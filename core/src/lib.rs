@@ -1,14 +1,31 @@
 pub mod plugin;
 pub mod error;
+pub mod circuit_breaker;
 pub mod registry;
 pub mod config;
 pub mod manager;
+pub mod history;
+pub mod metrics;
+pub mod middleware;
+pub mod panic_hook;
+pub mod plugin_prelude;
+pub mod queue;
+pub mod schema;
+pub mod storage;
+pub mod subprocess;
 
-pub use plugin::Plugin;
-pub use error::{PluginError, PluginResult};
-pub use registry::{PluginRegistry, PluginInfo};
-pub use config::{Config, ConfigManager, PluginConfig, ServerConfig};
+pub use plugin::{ExecStatus, ExecutionContext, Plugin, RouteHandler, RouteSpec};
+pub use error::{PluginError, PluginExecutionError, PluginResult};
+pub use registry::{plugin_log_target, PluginRegistry, RegistryListener, HOST_FEATURES, PluginInfo, PluginLoadFailure};
+pub use config::{AccessLogFormat, CircuitBreakerConfig, Config, ConfigManager, CONFIG_VERSION, FaultInjectionConfig, InvalidConfigPolicy, Isolation, PluginConfig, PluginSpec, ResponseStyle, ServerConfig};
+pub use circuit_breaker::{CircuitBreaker, CircuitState};
+pub use storage::KvStore;
+pub use subprocess::{SubprocessExecutor, SubprocessRequest, SubprocessResponse, WarmPool};
+pub use history::{ExecutionHistory, ExecutionRecord};
+pub use middleware::{InputMiddleware, OutputMiddleware};
+pub use metrics::{MetricsCollector, MetricsSnapshot, PluginMetrics};
 pub use manager::{PluginManager, PluginStatus, ExecutionResult, ExecutionOptions};
+pub use queue::{ExecutionPriority, ExecutionQueue, QueueSlot};
 
 // Re-export commonly used types
 pub use anyhow::Result;
\ No newline at end of file
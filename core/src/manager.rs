@@ -1,9 +1,15 @@
 use crate::{
-    Config, ConfigManager, PluginError, PluginRegistry, PluginResult,
+    CircuitBreaker, CircuitState, Config, ConfigManager, ExecStatus, ExecutionContext, ExecutionHistory, ExecutionPriority, ExecutionRecord, InputMiddleware, InvalidConfigPolicy, Isolation,
+    KvStore, MetricsCollector, OutputMiddleware, PluginError, PluginRegistry, PluginResult, RegistryListener, SubprocessExecutor,
+    SubprocessRequest, WarmPool, plugin_log_target,
 };
-use log::{debug, error, info, warn};
+use chrono::{DateTime, Utc};
+use log::{debug, error, info, log, warn, Level};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// Execution result with timing information
@@ -12,7 +18,41 @@ pub struct ExecutionResult {
     pub plugin: String,
     pub output: String,
     pub duration_ms: u64,
+    /// Time spent queued before execution started, 0 when not queued
+    #[serde(default)]
+    pub queue_ms: u64,
+    /// Time spent in the plugin call itself
+    #[serde(default)]
+    pub exec_ms: u64,
     pub success: bool,
+    /// Category of the error that failed the execution, e.g. `timeout_error`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_category: Option<String>,
+    /// Plugin-defined code of the error that failed the execution, if it reported one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    /// Change in the process's resident memory across the plugin call, in KiB
+    ///
+    /// A coarse, process-wide estimate that includes other threads' allocations,
+    /// only recorded when `track_memory` is configured and the platform supports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approx_memory_kb: Option<i64>,
+    /// Status the plugin reported for successful output, e.g. `rejected`
+    #[serde(default, skip_serializing_if = "ExecStatus::is_ok")]
+    pub status: ExecStatus,
+    /// Hex SHA-256 of successful output, only computed when `hash_outputs` is configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_hash: Option<String>,
+}
+
+/// Outcome of `run_execution`, with the timings reported in an `ExecutionResult`
+struct ExecutionRun<T> {
+    result: PluginResult<T>,
+    duration_ms: u64,
+    queue_ms: u64,
+    exec_ms: u64,
+    approx_memory_kb: Option<i64>,
+    status: ExecStatus,
 }
 
 /// Plugin status information combining registry and configuration data
@@ -25,6 +65,12 @@ pub struct PluginStatus {
     pub loaded: bool,
     pub path: std::path::PathBuf,
     pub config_enabled: bool,
+    /// When the plugin was last enabled or disabled, as an RFC 3339 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_state_change: Option<String>,
+    /// Why the plugin's own validation rejected its settings at load, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_error: Option<String>,
 }
 
 /// Options for plugin execution with error recovery
@@ -35,7 +81,20 @@ pub struct ExecutionOptions {
     /// Delay between retry attempts
     pub retry_delay: Duration,
     /// Timeout for plugin execution (None for no timeout)
+    ///
+    /// In-process calls cannot be interrupted, so a call that succeeds after the timeout
+    /// has passed, including the 30 second default, still fails with `timeout_error`.
     pub timeout: Option<Duration>,
+    /// When the caller queued the execution, used to report time spent waiting
+    pub enqueued_at: Option<Instant>,
+    /// Settings layered over the plugin's configured settings for this execution only
+    ///
+    /// Each key must be listed in the plugin's `overridable_settings`.
+    pub settings_override: HashMap<String, serde_json::Value>,
+    /// Id of the request that triggered the execution, recorded on its tracing span
+    pub request_id: Option<String>,
+    /// Order in which the execution is admitted by an `ExecutionQueue`
+    pub priority: ExecutionPriority,
 }
 
 impl Default for ExecutionOptions {
@@ -44,6 +103,10 @@ impl Default for ExecutionOptions {
             max_retries: 2,
             retry_delay: Duration::from_millis(100),
             timeout: Some(Duration::from_secs(30)),
+            enqueued_at: None,
+            settings_override: HashMap::new(),
+            request_id: None,
+            priority: ExecutionPriority::Normal,
         }
     }
 }
@@ -55,6 +118,10 @@ impl ExecutionOptions {
             max_retries: 1,
             retry_delay: Duration::from_millis(0),
             timeout: Some(Duration::from_secs(30)),
+            enqueued_at: None,
+            settings_override: HashMap::new(),
+            request_id: None,
+            priority: ExecutionPriority::Normal,
         }
     }
     
@@ -64,23 +131,94 @@ impl ExecutionOptions {
             max_retries: 5,
             retry_delay: Duration::from_millis(200),
             timeout: Some(Duration::from_secs(60)),
+            enqueued_at: None,
+            settings_override: HashMap::new(),
+            request_id: None,
+            priority: ExecutionPriority::Normal,
         }
     }
     
+    /// Record when the execution was queued so the result reports its queue time
+    pub fn with_enqueued_at(mut self, enqueued_at: Instant) -> Self {
+        self.enqueued_at = Some(enqueued_at);
+        self
+    }
+    
+    /// Override plugin settings for this execution without changing the configuration
+    pub fn with_settings_override(mut self, settings: HashMap<String, serde_json::Value>) -> Self {
+        self.settings_override = settings;
+        self
+    }
+    
+    /// Bound the execution by `timeout` instead of the default
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    
+    /// Set the priority used when the execution waits in an `ExecutionQueue`
+    pub fn with_priority(mut self, priority: ExecutionPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+    
+    /// Tag the execution with the id of the request that triggered it
+    pub fn with_request_id<S: Into<String>>(mut self, request_id: S) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+    
     /// Create execution options with no timeout
     pub fn no_timeout() -> Self {
         Self {
             max_retries: 2,
             retry_delay: Duration::from_millis(100),
             timeout: None,
+            enqueued_at: None,
+            settings_override: HashMap::new(),
+            request_id: None,
+            priority: ExecutionPriority::Normal,
         }
     }
 }
 
+/// Hex-encoded SHA-256 digest of `output`
+fn sha256_hex(output: &str) -> String {
+    Sha256::digest(output.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Resident memory of this process in KiB, or `None` where it cannot be read
+#[cfg(target_os = "linux")]
+fn resident_memory_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
+
+/// Resident memory of this process in KiB, or `None` where it cannot be read
+#[cfg(not(target_os = "linux"))]
+fn resident_memory_kb() -> Option<u64> {
+    None
+}
+
 /// High-level plugin manager that integrates registry and configuration
 pub struct PluginManager {
     registry: PluginRegistry,
     config_manager: ConfigManager,
+    kv_stores: Mutex<HashMap<String, Arc<KvStore>>>,
+    warm_pools: Mutex<HashMap<String, Arc<WarmPool>>>,
+    history: ExecutionHistory,
+    metrics: MetricsCollector,
+    circuit_breaker: CircuitBreaker,
+    input_middleware: Vec<Box<dyn InputMiddleware>>,
+    output_middleware: Vec<Box<dyn OutputMiddleware>>,
+    /// Why each plugin's `validate_config` rejected its settings at load
+    config_errors: HashMap<String, String>,
 }
 
 impl PluginManager {
@@ -92,11 +230,31 @@ impl PluginManager {
             .map_err(|e| PluginError::config_error(format!("Failed to load configuration: {}", e)))?;
         
         let plugins_dir = config_manager.config().plugins_dir.clone();
-        let registry = PluginRegistry::new(&plugins_dir);
+        let registry = PluginRegistry::new(&plugins_dir)
+            .with_expected_digests(config_manager.config().expected_digests.clone())
+            .with_recursive_scan(
+                config_manager.config().recursive_plugin_scan,
+                config_manager.config().max_scan_depth,
+            )
+            .with_load_timeout(Duration::from_millis(config_manager.config().plugin_load_timeout_ms))
+            .with_system_plugins_dir(config_manager.system_plugins_dir());
+        
+        let history = ExecutionHistory::open(
+            config_manager.config().state_dir.join("history.jsonl"),
+            config_manager.config().history_capacity,
+        );
         
         let mut manager = Self {
             registry,
             config_manager,
+            kv_stores: Mutex::new(HashMap::new()),
+            warm_pools: Mutex::new(HashMap::new()),
+            history,
+            metrics: MetricsCollector::new(),
+            circuit_breaker: CircuitBreaker::new(),
+            input_middleware: Vec::new(),
+            output_middleware: Vec::new(),
+            config_errors: HashMap::new(),
         };
         
         // Load plugins from the configured directory
@@ -114,11 +272,31 @@ impl PluginManager {
             .map_err(|e| PluginError::config_error(format!("Failed to load configuration: {}", e)))?;
         
         let plugins_dir = config_manager.config().plugins_dir.clone();
-        let registry = PluginRegistry::new(&plugins_dir);
+        let registry = PluginRegistry::new(&plugins_dir)
+            .with_expected_digests(config_manager.config().expected_digests.clone())
+            .with_recursive_scan(
+                config_manager.config().recursive_plugin_scan,
+                config_manager.config().max_scan_depth,
+            )
+            .with_load_timeout(Duration::from_millis(config_manager.config().plugin_load_timeout_ms))
+            .with_system_plugins_dir(config_manager.system_plugins_dir());
+        
+        let history = ExecutionHistory::open(
+            config_manager.config().state_dir.join("history.jsonl"),
+            config_manager.config().history_capacity,
+        );
         
         let mut manager = Self {
             registry,
             config_manager,
+            kv_stores: Mutex::new(HashMap::new()),
+            warm_pools: Mutex::new(HashMap::new()),
+            history,
+            metrics: MetricsCollector::new(),
+            circuit_breaker: CircuitBreaker::new(),
+            input_middleware: Vec::new(),
+            output_middleware: Vec::new(),
+            config_errors: HashMap::new(),
         };
         
         // Load plugins from the configured directory
@@ -132,11 +310,16 @@ impl PluginManager {
     pub fn load_plugins(&mut self) -> PluginResult<Vec<String>> {
         info!("Loading plugins from directory: {:?}", self.config_manager.plugins_dir());
         
-        let loaded_plugins = self.registry.scan_and_load()?;
+        let mut loaded_plugins = self.registry.scan_and_load()?;
+        let preload: Vec<_> = self.config_manager.config().preload.iter().map(|spec| spec.path.clone()).collect();
+        if !preload.is_empty() {
+            info!("Preloading {} plugins listed in config", preload.len());
+            loaded_plugins.extend(self.registry.load_from_paths(&preload));
+        }
         
         // Sync plugin states with configuration
         for plugin_name in &loaded_plugins {
-            let config_enabled = self.config_manager.is_plugin_enabled(plugin_name);
+            let config_enabled = self.check_plugin_config(plugin_name) && self.config_manager.is_plugin_enabled(plugin_name);
             if config_enabled {
                 if let Err(e) = self.registry.enable_plugin(plugin_name) {
                     warn!("Failed to enable plugin '{}' from config: {}", plugin_name, e);
@@ -152,6 +335,64 @@ impl PluginManager {
         Ok(loaded_plugins)
     }
 
+    /// Register an in-process plugin instance and sync its state with configuration
+    pub fn register_plugin(&mut self, plugin: Box<dyn crate::Plugin>) -> PluginResult<String> {
+        let plugin_name = self.registry.register_plugin(plugin)?;
+        
+        if !self.check_plugin_config(&plugin_name) || !self.config_manager.is_plugin_enabled(&plugin_name) {
+            self.registry.disable_plugin(&plugin_name)?;
+        }
+        
+        Ok(plugin_name)
+    }
+
+    /// Run a plugin's `validate_config` over its current settings
+    ///
+    /// Returns false if the plugin must stay disabled under `invalid_plugin_config`.
+    fn check_plugin_config(&mut self, name: &str) -> bool {
+        let settings = self.config_manager.plugin_settings(name);
+        let reason = match self.registry.validate_plugin_config(name, &settings) {
+            Ok(Ok(())) => {
+                self.config_errors.remove(name);
+                return true;
+            }
+            Ok(Err(reason)) => reason,
+            Err(e) => e.to_string(),
+        };
+        
+        let keep_enabled = self.config_manager.config().invalid_plugin_config == InvalidConfigPolicy::Warn;
+        warn!("Plugin '{}' rejected its settings: {}{} (category: config_error)",
+              name, reason, if keep_enabled { "" } else { "; keeping it disabled" });
+        self.config_errors.insert(name.to_string(), reason);
+        keep_enabled
+    }
+
+    /// Re-validate a plugin's settings and enable or disable it in the registry to match
+    ///
+    /// The plugin runs only if its settings pass validation (or the policy is `warn`)
+    /// and the configuration enables it. The persisted enabled flag is left untouched.
+    fn apply_plugin_config(&mut self, name: &str) {
+        let should_enable = self.check_plugin_config(name) && self.config_manager.is_plugin_enabled(name);
+        let enabled = match self.registry.get_plugin_info(name) {
+            Some(info) => info.enabled,
+            None => return,
+        };
+        if should_enable == enabled {
+            return;
+        }
+        
+        let result = if should_enable {
+            self.registry.enable_plugin(name)
+        } else {
+            self.warm_pools.lock().unwrap().remove(name);
+            self.registry.disable_plugin(name)
+        };
+        if let Err(e) = result {
+            warn!("Failed to {} plugin '{}' after a settings change: {} (category: config_error)",
+                  if should_enable { "enable" } else { "disable" }, name, e);
+        }
+    }
+
     /// List all plugins with their status information
     pub fn list_plugins(&self) -> Vec<PluginStatus> {
         debug!("Listing all plugins");
@@ -161,6 +402,7 @@ impl PluginManager {
         
         for info in plugin_infos {
             let config_enabled = self.config_manager.is_plugin_enabled(&info.name);
+            let last_state_change = self.config_manager.last_state_change(&info.name);
             let status = PluginStatus {
                 name: info.name.clone(),
                 version: info.version,
@@ -169,6 +411,8 @@ impl PluginManager {
                 loaded: info.loaded,
                 path: info.path,
                 config_enabled,
+                last_state_change,
+                config_error: self.config_errors.get(&info.name).cloned(),
             };
             statuses.push(status);
         }
@@ -177,6 +421,43 @@ impl PluginManager {
         statuses
     }
 
+    /// Get example inputs for a specific plugin
+    pub fn get_plugin_examples(&self, name: &str) -> Option<Vec<(String, String)>> {
+        self.registry.get_plugin_examples(name)
+    }
+
+    /// Operations declared by each plugin, keyed by plugin name
+    ///
+    /// Plugins declaring no operations are left out.
+    pub fn list_operations(&self) -> BTreeMap<String, Vec<String>> {
+        self.registry
+            .list_plugins()
+            .into_iter()
+            .filter_map(|info| {
+                let operations = self.registry.get_plugin_capabilities(&info.name)?;
+                (!operations.is_empty()).then_some((info.name, operations))
+            })
+            .collect()
+    }
+
+    /// Get the input schema published by a plugin
+    ///
+    /// Returns `Ok(None)` if the plugin exists but publishes no schema.
+    pub fn get_plugin_schema(&self, name: &str) -> PluginResult<Option<serde_json::Value>> {
+        self.registry
+            .get_plugin_schema(name)
+            .ok_or_else(|| PluginError::NotFound { name: name.to_string() })
+    }
+
+    /// Get the output schema published by a plugin
+    ///
+    /// Returns `Ok(None)` if the plugin exists but publishes no schema.
+    pub fn get_plugin_output_schema(&self, name: &str) -> PluginResult<Option<serde_json::Value>> {
+        self.registry
+            .get_plugin_output_schema(name)
+            .ok_or_else(|| PluginError::NotFound { name: name.to_string() })
+    }
+
     /// Get status information for a specific plugin
     pub fn get_plugin_status(&self, name: &str) -> Option<PluginStatus> {
         debug!("Getting status for plugin: {}", name);
@@ -192,6 +473,8 @@ impl PluginManager {
             loaded: info.loaded,
             path: info.path,
             config_enabled,
+            last_state_change: self.config_manager.last_state_change(name),
+            config_error: self.config_errors.get(name).cloned(),
         })
     }
 
@@ -207,6 +490,13 @@ impl PluginManager {
             });
         }
         
+        // Refuse plugins whose settings fail validation under the `disable` policy
+        if !self.check_plugin_config(name) {
+            let reason = self.config_errors.get(name).cloned().unwrap_or_default();
+            error!("Cannot enable plugin '{}': invalid settings (category: config_error)", name);
+            return Err(PluginError::config_error(format!("Plugin '{}' rejected its settings: {}", name, reason)));
+        }
+        
         // Enable in registry
         self.registry.enable_plugin(name)?;
         
@@ -230,8 +520,9 @@ impl PluginManager {
             });
         }
         
-        // Disable in registry
+        // Disable in registry and stop any warm helper processes
         self.registry.disable_plugin(name)?;
+        self.warm_pools.lock().unwrap().remove(name);
         
         // Disable in configuration and persist
         self.config_manager.disable_plugin(name)
@@ -241,9 +532,81 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Flip a plugin between enabled and disabled, persist it, and return the resulting status
+    pub fn toggle_plugin(&mut self, name: &str) -> PluginResult<PluginStatus> {
+        let status = self.get_plugin_status(name).ok_or_else(|| {
+            error!("Cannot toggle plugin '{}': not found", name);
+            PluginError::NotFound {
+                name: name.to_string(),
+            }
+        })?;
+        
+        if status.enabled && status.config_enabled {
+            self.disable_plugin(name)?;
+        } else {
+            self.enable_plugin(name)?;
+        }
+        
+        self.get_plugin_status(name).ok_or_else(|| PluginError::NotFound {
+            name: name.to_string(),
+        })
+    }
+
     /// Execute a plugin with comprehensive error handling and result formatting
+    ///
+    /// Uses the plugin's configured `timeout_ms` in place of the default timeout when set.
     pub fn execute_plugin(&self, name: &str, input: &str) -> PluginResult<ExecutionResult> {
-        self.execute_plugin_with_options(name, input, ExecutionOptions::default())
+        self.execute_plugin_with_options(name, input, self.execution_options(name))
+    }
+
+    /// Default execution options for a plugin, using its configured `timeout_ms` in place of the default timeout
+    pub fn execution_options(&self, name: &str) -> ExecutionOptions {
+        let mut options = ExecutionOptions::default();
+        if let Some(timeout) = self.config_manager.plugin_timeout(name) {
+            options.timeout = Some(timeout);
+        }
+        options
+    }
+    
+    /// Check that the loaded version of a plugin satisfies a semver requirement such as `^0.1`
+    pub fn check_plugin_version(&self, name: &str, version_req: &str) -> PluginResult<()> {
+        let required = semver::VersionReq::parse(version_req).map_err(|e| {
+            PluginError::invalid_input(format!("Invalid version requirement '{}': {}", version_req, e))
+        })?;
+        let info = self.registry.get_plugin_info(name).ok_or_else(|| PluginError::NotFound {
+            name: name.to_string(),
+        })?;
+        
+        // Versions that are not valid semver never satisfy a constraint
+        let satisfied = semver::Version::parse(&info.version).is_ok_and(|version| required.matches(&version));
+        if !satisfied {
+            warn!("Plugin '{}' version {} does not satisfy '{}' (category: version_mismatch)", name, info.version, version_req);
+            return Err(PluginError::VersionMismatch {
+                name: name.to_string(),
+                required: version_req.to_string(),
+                found: info.version,
+            });
+        }
+        Ok(())
+    }
+    
+    /// Execute a plugin only if its loaded version satisfies `version_req`
+    ///
+    /// The registry holds a single version of each plugin, so this checks that
+    /// version rather than choosing among several.
+    pub fn execute_plugin_versioned(&self, name: &str, version_req: &str, input: &str) -> PluginResult<ExecutionResult> {
+        self.check_plugin_version(name, version_req)?;
+        self.execute_plugin(name, input)
+    }
+    
+    /// Call straight into a plugin once, for fuzzing and other tests
+    ///
+    /// Skips middleware, timeouts, retries, fault injection, the circuit breaker,
+    /// metrics and history, and runs with an empty context, so repeated calls with
+    /// the same input behave the same. Plugin panics are still returned as errors.
+    /// Production callers should use `execute_plugin`.
+    pub fn execute_plugin_isolated(&self, name: &str, input: &str) -> PluginResult<String> {
+        self.registry.execute_plugin_with_retry(name, input, 1, Duration::ZERO)
     }
     
     /// Execute a plugin with configurable execution options
@@ -251,7 +614,74 @@ impl PluginManager {
         info!("Executing plugin '{}' with input length: {} (timeout: {:?}, retries: {})", 
               name, input.len(), options.timeout, options.max_retries);
         
+        let run = self.run_execution(
+            name,
+            input,
+            &options,
+            |input, ctx| {
+                let output = if self.config_manager.isolation(name) == Isolation::Subprocess {
+                    self.execute_in_subprocess(name, input, ctx, options.timeout)
+                } else if let Some(timeout) = options.timeout {
+                    self.execute_plugin_with_timeout(name, input, ctx, timeout, options.max_retries)
+                } else {
+                    self.registry.execute_plugin_with_context(name, input, ctx, options.max_retries, options.retry_delay)
+                };
+                output.and_then(|output| self.check_output_schema(name, output))
+            },
+            |output| self.apply_output_middleware(name, output),
+            |output| output.len() as u64,
+        )?;
+        
+        match run.result {
+            Ok(output) => Ok(ExecutionResult {
+                plugin: name.to_string(),
+                output_hash: self.output_hash(&output),
+                output,
+                duration_ms: run.duration_ms,
+                queue_ms: run.queue_ms,
+                exec_ms: run.exec_ms,
+                success: true,
+                error_category: None,
+                error_code: None,
+                approx_memory_kb: run.approx_memory_kb,
+                status: run.status,
+            }),
+            // Return the error result instead of propagating the error
+            // This allows callers to get timing information even for failed executions
+            Err(e) => Ok(ExecutionResult {
+                plugin: name.to_string(),
+                output: e.user_friendly_message(),
+                duration_ms: run.duration_ms,
+                queue_ms: run.queue_ms,
+                exec_ms: run.exec_ms,
+                success: false,
+                error_category: Some(e.category().to_string()),
+                error_code: e.code().map(str::to_string),
+                approx_memory_kb: run.approx_memory_kb,
+                status: ExecStatus::Ok,
+                output_hash: None,
+            }),
+        }
+    }
+    
+    /// Run one execution with the bookkeeping shared by every single-input path
+    ///
+    /// Checks that the plugin can run, passes the input through the input middleware,
+    /// then hands it to `call` with the execution context and the output to `finish`.
+    /// The circuit breaker, metrics, history and tracing span record the outcome.
+    /// Only errors raised before the plugin is reached are returned as `Err`.
+    fn run_execution<T>(
+        &self,
+        name: &str,
+        input: &str,
+        options: &ExecutionOptions,
+        call: impl FnOnce(&str, &ExecutionContext) -> PluginResult<T>,
+        finish: impl FnOnce(T) -> PluginResult<T>,
+        output_bytes: impl Fn(&T) -> u64,
+    ) -> PluginResult<ExecutionRun<T>> {
         let start_time = Instant::now();
+        let span = self.execution_span(name, options.request_id.as_deref());
+        let _entered = span.enter();
         
         // Check if plugin exists first
         if !self.registry.has_plugin(name) {
@@ -271,55 +701,402 @@ impl PluginManager {
             }
         }
         
-        // Execute the plugin with timeout and retry logic
-        let result = if let Some(timeout) = options.timeout {
-            self.execute_plugin_with_timeout(name, input, timeout, options.max_retries)
-        } else {
-            self.registry.execute_plugin_with_retry(name, input, options.max_retries, options.retry_delay)
-        };
+        self.check_settings_override(name, &options.settings_override)?;
         
-        let duration = start_time.elapsed();
+        let breaker_config = &self.config_manager.config().circuit_breaker;
+        if !self.circuit_breaker.allow(name, breaker_config) {
+            warn!("Plugin '{}' circuit is open, failing fast (category: circuit_open)", name);
+            return Err(PluginError::resource_exhausted("circuit_open"));
+        }
         
-        match result {
-            Ok(output) => {
-                let execution_result = ExecutionResult {
-                    plugin: name.to_string(),
-                    output,
-                    duration_ms: duration.as_millis() as u64,
-                    success: true,
-                };
-                
-                info!(
-                    "Plugin '{}' executed successfully in {}ms, output length: {} (category: execution_success)",
-                    name,
-                    execution_result.duration_ms,
-                    execution_result.output.len()
-                );
-                
-                Ok(execution_result)
+        // Run the input middleware chain, execute the plugin, then run the output
+        // middleware chain on successful output
+        let queue_ms = options.enqueued_at
+            .map_or(0, |enqueued_at| start_time.saturating_duration_since(enqueued_at).as_millis() as u64);
+        let mut exec_ms = 0;
+        let mut approx_memory_kb = None;
+        let mut status = ExecStatus::Ok;
+        let mut plugin_outcome = None;
+        let result = self.prepare_input(name, input).and_then(|input| {
+            let ctx = self.execution_context(name, &options.settings_override)?;
+            let memory_before = self.config_manager.config().track_memory.then(resident_memory_kb).flatten();
+            let exec_start = Instant::now();
+            let output = call(&input, &ctx);
+            exec_ms = exec_start.elapsed().as_millis() as u64;
+            approx_memory_kb = memory_before
+                .and_then(|before| resident_memory_kb().map(|after| after as i64 - before as i64));
+            status = ctx.status();
+            plugin_outcome = Some(output.is_ok());
+            output
+        }).and_then(finish);
+        
+        // Only the plugin's own failures count towards opening its circuit
+        match plugin_outcome {
+            Some(success) => self.circuit_breaker.record(name, success, breaker_config),
+            None => self.circuit_breaker.release(name),
+        }
+        
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+        let input_bytes = input.len() as u64;
+        let output_bytes = result.as_ref().map_or(0, output_bytes);
+        span.record("duration_ms", duration_ms);
+        span.record("success", result.is_ok());
+        
+        self.metrics.record(name, result.is_ok(), duration_ms);
+        self.metrics.record_sizes(name, input_bytes, output_bytes);
+        self.history.record(ExecutionRecord {
+            plugin: name.to_string(),
+            success: result.is_ok(),
+            duration_ms,
+            executed_at: Utc::now(),
+            input_bytes,
+            output_bytes,
+        });
+        
+        match &result {
+            Ok(_) => log!(
+                target: &plugin_log_target(name),
+                Level::Info,
+                "Plugin '{}' executed successfully in {}ms, output length: {} (category: execution_success)",
+                name, duration_ms, output_bytes
+            ),
+            Err(e) => log!(
+                target: &plugin_log_target(name),
+                Level::Error,
+                "Plugin '{}' execution failed after {}ms: {} (category: {})",
+                name, duration_ms, e, e.category()
+            ),
+        }
+        
+        Ok(ExecutionRun { result, duration_ms, queue_ms, exec_ms, approx_memory_kb, status })
+    }
+    
+    /// Execute a plugin over several inputs, using the plugin's `batch_execute`
+    ///
+    /// Returns one result per input in order. Inputs rejected by the input middleware
+    /// fail individually without reaching the plugin. The plugin handles its inputs in
+    /// one call, so each result's `duration_ms` and `exec_ms` are totals for the whole
+    /// batch. Metrics and history get one entry per input, each charged an equal share
+    /// of the batch duration.
+    pub fn execute_plugin_batch(&self, name: &str, inputs: &[String]) -> PluginResult<Vec<ExecutionResult>> {
+        info!("Executing plugin '{}' over a batch of {} inputs", name, inputs.len());
+
+        let start_time = Instant::now();
+
+        if !self.registry.has_plugin(name) {
+            error!("Plugin '{}' not found for execution", name);
+            return Err(PluginError::NotFound {
+                name: name.to_string(),
+            });
+        }
+
+        if let Some(status) = self.get_plugin_status(name) {
+            if !status.enabled || !status.config_enabled {
+                warn!("Attempted to execute disabled plugin '{}'", name);
+                return Err(PluginError::PluginDisabled {
+                    name: name.to_string(),
+                });
             }
-            Err(e) => {
-                let execution_result = ExecutionResult {
+        }
+
+        let breaker_config = &self.config_manager.config().circuit_breaker;
+        if !self.circuit_breaker.allow(name, breaker_config) {
+            warn!("Plugin '{}' circuit is open, failing fast (category: circuit_open)", name);
+            return Err(PluginError::resource_exhausted("circuit_open"));
+        }
+
+        let prepared: Vec<PluginResult<String>> = inputs
+            .iter()
+            .map(|input| self.prepare_input(name, input))
+            .collect();
+        let plugin_inputs: Vec<String> = prepared.iter().filter_map(|input| input.as_ref().ok().cloned()).collect();
+
+        let exec_start = Instant::now();
+        let batch = if plugin_inputs.is_empty() {
+            Ok(Vec::new())
+        } else if self.config_manager.isolation(name) == Isolation::Subprocess {
+            // Isolated plugins are never called in process, so each input gets its own helper
+            let timeout = self.execution_options(name).timeout;
+            self.execution_context(name, &HashMap::new()).map(|ctx| {
+                plugin_inputs
+                    .iter()
+                    .map(|input| {
+                        self.execute_in_subprocess(name, input, &ctx, timeout)
+                            .map_err(|e| e.user_friendly_message())
+                    })
+                    .collect()
+            })
+        } else {
+            self.registry.execute_plugin_batch(name, &plugin_inputs)
+        };
+        let exec_ms = exec_start.elapsed().as_millis() as u64;
+
+        if plugin_inputs.is_empty() {
+            self.circuit_breaker.release(name);
+        } else {
+            self.circuit_breaker.record(name, batch.is_ok(), breaker_config);
+        }
+        let mut plugin_outputs = batch?.into_iter();
+
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+        let share_ms = duration_ms / inputs.len().max(1) as u64;
+        let results = inputs
+            .iter()
+            .zip(prepared)
+            .map(|(input, prepared)| {
+                let result = prepared.and_then(|_| {
+                    // One plugin output was produced for every input that passed the middleware
+                    plugin_outputs
+                        .next()
+                        .expect("batch results match plugin inputs")
+                        .map_err(|message| PluginError::ExecutionFailed { message })
+                        .and_then(|output| self.check_output_schema(name, output))
+                        .and_then(|output| self.apply_output_middleware(name, output))
+                });
+
+                let input_bytes = input.len() as u64;
+                let output_bytes = result.as_ref().map_or(0, |output| output.len() as u64);
+                self.metrics.record(name, result.is_ok(), share_ms);
+                self.metrics.record_sizes(name, input_bytes, output_bytes);
+                self.history.record(ExecutionRecord {
                     plugin: name.to_string(),
-                    output: e.user_friendly_message(),
-                    duration_ms: duration.as_millis() as u64,
-                    success: false,
+                    success: result.is_ok(),
+                    duration_ms: share_ms,
+                    executed_at: Utc::now(),
+                    input_bytes,
+                    output_bytes,
+                });
+
+                match result {
+                    Ok(output) => ExecutionResult {
+                        plugin: name.to_string(),
+                        output_hash: self.output_hash(&output),
+                        output,
+                        duration_ms,
+                        queue_ms: 0,
+                        exec_ms,
+                        success: true,
+                        error_category: None,
+                        error_code: None,
+                        approx_memory_kb: None,
+                        status: ExecStatus::Ok,
+                    },
+                    Err(e) => ExecutionResult {
+                        plugin: name.to_string(),
+                        output: e.user_friendly_message(),
+                        duration_ms,
+                        queue_ms: 0,
+                        exec_ms,
+                        success: false,
+                        error_category: Some(e.category().to_string()),
+                        error_code: e.code().map(str::to_string),
+                        approx_memory_kb: None,
+                        status: ExecStatus::Ok,
+                        output_hash: None,
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+
+        log!(
+            target: &plugin_log_target(name),
+            Level::Info,
+            "Plugin '{}' executed a batch of {} inputs in {}ms, {} failed (category: batch_execution)",
+            name, results.len(), duration_ms, results.iter().filter(|result| !result.success).count()
+        );
+
+        Ok(results)
+    }
+
+    /// Execute a plugin once through its `execute_multi`, returning its named outputs
+    ///
+    /// Uses the plugin's configured `timeout_ms` in place of the default timeout when set.
+    pub fn execute_plugin_multi(&self, name: &str, input: &str) -> PluginResult<HashMap<String, String>> {
+        self.execute_plugin_multi_with_options(name, input, self.execution_options(name))
+    }
+
+    /// Execute a plugin once through its `execute_multi` with configurable execution options
+    ///
+    /// The input middleware runs on the input, and the output schema check and output
+    /// middleware on each output. Plugins isolated in a subprocess return their single
+    /// output under `"result"`. `execute_multi` takes no execution context, so in the
+    /// host process the plugin is called once and settings overrides only reach isolated
+    /// plugins. Unlike `execute_plugin_with_options`, a failed execution is returned as an `Err`.
+    pub fn execute_plugin_multi_with_options(&self, name: &str, input: &str, options: ExecutionOptions) -> PluginResult<HashMap<String, String>> {
+        info!("Executing plugin '{}' for named outputs with input length: {} (timeout: {:?})", name, input.len(), options.timeout);
+
+        self.run_execution(
+            name,
+            input,
+            &options,
+            |input, ctx| {
+                let outputs = if self.config_manager.isolation(name) == Isolation::Subprocess {
+                    self.execute_in_subprocess(name, input, ctx, options.timeout)
+                        .map(|output| HashMap::from([("result".to_string(), output)]))?
+                } else {
+                    let start_time = Instant::now();
+                    let outputs = self.registry.execute_plugin_multi(name, input)?;
+                    // The call cannot be interrupted, but outputs arriving after the deadline are discarded
+                    if let Some(timeout) = options.timeout.filter(|&timeout| start_time.elapsed() > timeout) {
+                        warn!("Plugin '{}' execution overran its timeout of {:?}", name, timeout);
+                        return Err(PluginError::timeout_error(format!("Plugin '{}' execution", name)));
+                    }
+                    outputs
                 };
-                
-                error!(
-                    "Plugin '{}' execution failed after {}ms: {} (category: {})",
-                    name, execution_result.duration_ms, e, e.category()
-                );
-                
-                // Return the error result instead of propagating the error
-                // This allows callers to get timing information even for failed executions
-                Ok(execution_result)
+                outputs
+                    .into_iter()
+                    .map(|(key, output)| Ok((key, self.check_output_schema(name, output)?)))
+                    .collect()
+            },
+            |outputs: HashMap<String, String>| {
+                outputs
+                    .into_iter()
+                    .map(|(key, output)| Ok((key, self.apply_output_middleware(name, output)?)))
+                    .collect()
+            },
+            |outputs| outputs.values().map(|output| output.len() as u64).sum(),
+        )?
+        .result
+    }
+    
+    /// Call a custom route declared by a plugin's `routes`
+    ///
+    /// Returns `Ok(None)` when the plugin declares no route for `method` and `path`.
+    /// Calls are recorded in metrics and history like executions, but routes always run
+    /// in the host process, whatever the plugin's isolation, and skip middleware,
+    /// timeouts and the circuit breaker.
+    pub fn execute_plugin_route(&self, name: &str, method: &str, path: &str, input: &str) -> PluginResult<Option<String>> {
+        if let Some(status) = self.get_plugin_status(name) {
+            if !status.enabled || !status.config_enabled {
+                warn!("Attempted to call a route of disabled plugin '{}'", name);
+                return Err(PluginError::PluginDisabled {
+                    name: name.to_string(),
+                });
+            }
+        }
+
+        let start_time = Instant::now();
+        let result = self.registry.execute_plugin_route(name, method, path, input);
+        let output = match &result {
+            Ok(None) | Err(PluginError::NotFound { .. }) => return result,
+            Ok(Some(output)) => Some(output),
+            Err(_) => None,
+        };
+
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+        let input_bytes = input.len() as u64;
+        let output_bytes = output.map_or(0, |output| output.len() as u64);
+        self.metrics.record(name, result.is_ok(), duration_ms);
+        self.metrics.record_sizes(name, input_bytes, output_bytes);
+        self.history.record(ExecutionRecord {
+            plugin: name.to_string(),
+            success: result.is_ok(),
+            duration_ms,
+            executed_at: Utc::now(),
+            input_bytes,
+            output_bytes,
+        });
+        if result.is_ok() {
+            debug!("Plugin '{}' route {} {} returned {} bytes in {}ms", name, method, path, output_bytes, duration_ms);
+        }
+        result
+    }
+
+    /// Execute a plugin in a helper process, killing the process if it outlives `timeout`
+    fn execute_in_subprocess(&self, name: &str, input: &str, ctx: &ExecutionContext, timeout: Option<Duration>) -> PluginResult<String> {
+        let info = self.registry.get_plugin_info(name).ok_or_else(|| PluginError::NotFound {
+            name: name.to_string(),
+        })?;
+        if info.path.as_os_str().is_empty() {
+            return Err(PluginError::config_error(format!(
+                "Plugin '{}' is not loaded from a library and cannot run in a subprocess", name
+            )));
+        }
+
+        // The helper builds its own context, so faults are injected here, once per call
+        if ctx.inject_fault() {
+            warn!("Injected a transient fault into plugin '{}' before starting its subprocess (category: fault_injection)", name);
+            return Err(PluginError::temporary_failure("injected fault"));
+        }
+
+        let executor = SubprocessExecutor::with_host_override(self.config_manager.config().isolation_host.as_deref());
+        let request = SubprocessRequest {
+            input: input.to_string(),
+            settings: ctx.settings().clone(),
+            settings_override: ctx.settings_override().clone(),
+            feature_flags: ctx.feature_flags().clone(),
+        };
+        debug!("Executing plugin '{}' in a subprocess (category: subprocess_execution)", name);
+        let pool_size = self.config_manager.pool_size(name);
+        let (output, status) = if pool_size > 0 {
+            self.warm_pool(name, executor, &info.path, pool_size)?.execute(&request, timeout)?
+        } else {
+            executor.execute(&info.path, &request, timeout)?
+        };
+        ctx.set_status(status);
+        Ok(output)
+    }
+    
+    /// Hash of `output` when `hash_outputs` is on, so clients can spot changed output cheaply
+    fn output_hash(&self, output: &str) -> Option<String> {
+        self.config_manager.config().hash_outputs.then(|| sha256_hex(output))
+    }
+    
+    /// Check output against the plugin's `output_schema` when `strict_output_validation` is on
+    ///
+    /// Output that is not JSON or breaks the schema fails with `ExecutionFailed`.
+    fn check_output_schema(&self, name: &str, output: String) -> PluginResult<String> {
+        if !self.config_manager.config().strict_output_validation {
+            return Ok(output);
+        }
+        let Some(schema) = self.registry.get_plugin_output_schema(name).flatten() else {
+            return Ok(output);
+        };
+
+        let violation = match serde_json::from_str::<serde_json::Value>(&output) {
+            Ok(value) => crate::schema::validate(&value, &schema).err(),
+            Err(e) => Some(format!("output is not JSON: {}", e)),
+        };
+        match violation {
+            None => Ok(output),
+            Some(violation) => {
+                warn!("Plugin '{}' output violates its output schema: {} (category: schema_violation)", name, violation);
+                Err(PluginError::execution_failed(format!("Output of plugin '{}' violates its output schema: {}", name, violation)))
             }
         }
     }
+
+    /// Tracing span covering one execution, disabled unless `tracing_enabled` is configured
+    ///
+    /// `duration_ms` and `success` are recorded once the execution finishes.
+    fn execution_span(&self, name: &str, request_id: Option<&str>) -> tracing::Span {
+        if !self.config_manager.config().tracing_enabled {
+            return tracing::Span::none();
+        }
+        tracing::info_span!(
+            "plugin_execution",
+            plugin = name,
+            request_id = request_id,
+            duration_ms = tracing::field::Empty,
+            success = tracing::field::Empty,
+        )
+    }
+    
+    /// Get the warm pool for a plugin, starting its helper processes on first use
+    fn warm_pool(&self, name: &str, executor: SubprocessExecutor, library: &Path, size: usize) -> PluginResult<Arc<WarmPool>> {
+        let mut warm_pools = self.warm_pools.lock().unwrap();
+        if let Some(pool) = warm_pools.get(name) {
+            return Ok(pool.clone());
+        }
+        
+        info!("Starting {} warm plugin hosts for '{}' (category: warm_pool)", size, name);
+        let pool = Arc::new(WarmPool::new(executor, library, size)?);
+        warm_pools.insert(name.to_string(), pool.clone());
+        Ok(pool)
+    }
     
     /// Execute a plugin with timeout (simplified implementation)
-    fn execute_plugin_with_timeout(&self, name: &str, input: &str, timeout: std::time::Duration, max_retries: u32) -> PluginResult<String> {
+    fn execute_plugin_with_timeout(&self, name: &str, input: &str, ctx: &ExecutionContext, timeout: std::time::Duration, max_retries: u32) -> PluginResult<String> {
         // For now, we'll use a simple timeout approach without threading
         // This could be enhanced later with async execution or proper thread management
         let start_time = Instant::now();
@@ -331,7 +1108,12 @@ impl PluginManager {
                 return Err(PluginError::timeout_error(format!("Plugin '{}' execution", name)));
             }
             
-            match self.registry.execute_plugin(name, input) {
+            match self.registry.execute_plugin_with_context(name, input, ctx, 2, Duration::from_millis(100)) {
+                // The call cannot be interrupted, but output arriving after the deadline is discarded
+                Ok(_) if start_time.elapsed() > timeout => {
+                    warn!("Plugin '{}' execution overran its timeout of {:?} (attempt {})", name, timeout, attempt);
+                    return Err(PluginError::timeout_error(format!("Plugin '{}' execution", name)));
+                }
                 Ok(result) => return Ok(result),
                 Err(e) if attempt < max_retries && e.is_transient() => {
                     warn!("Transient error on attempt {}: {}. Retrying...", attempt, e);
@@ -345,6 +1127,113 @@ impl PluginManager {
         Err(PluginError::execution_failed("Maximum retries exceeded"))
     }
 
+    /// Current state of a plugin's circuit breaker
+    pub fn circuit_state(&self, name: &str) -> CircuitState {
+        self.circuit_breaker.state(name, &self.config_manager.config().circuit_breaker)
+    }
+
+    /// Register a listener for plugin load, enable, disable, and unload events
+    pub fn subscribe_registry_events(&self, listener: Box<dyn RegistryListener>) {
+        self.registry.subscribe(listener);
+    }
+
+    /// Add an input middleware, run after those already registered
+    pub fn with_input_middleware<M: InputMiddleware + 'static>(mut self, middleware: M) -> Self {
+        self.input_middleware.push(Box::new(middleware));
+        self
+    }
+
+    /// Pass input through the input middleware chain in registration order
+    fn apply_input_middleware(&self, name: &str, input: &str) -> PluginResult<String> {
+        self.input_middleware
+            .iter()
+            .try_fold(input.to_string(), |input, middleware| middleware.process(name, input))
+    }
+
+    /// Run the input middleware, then reject operations outside the plugin's `allowed_operations`
+    fn prepare_input(&self, name: &str, input: &str) -> PluginResult<String> {
+        let input = self.apply_input_middleware(name, input)?;
+        if let Some(allowed) = self.config_manager.allowed_operations(name) {
+            if let Err(e) = crate::plugin_prelude::check_operation_allowed(&input, &allowed) {
+                warn!("Rejected input for plugin '{}': {} (category: operation_not_allowed)", name, e);
+                return Err(e);
+            }
+        }
+        Ok(input)
+    }
+
+    /// Add an output middleware, run after those already registered
+    pub fn with_output_middleware<M: OutputMiddleware + 'static>(mut self, middleware: M) -> Self {
+        self.output_middleware.push(Box::new(middleware));
+        self
+    }
+
+    /// Pass successful output through the output middleware chain in registration order
+    fn apply_output_middleware(&self, name: &str, output: String) -> PluginResult<String> {
+        self.output_middleware
+            .iter()
+            .try_fold(output, |output, middleware| middleware.process(name, output))
+    }
+
+    /// Get the execution history
+    pub fn history(&self) -> &ExecutionHistory {
+        &self.history
+    }
+
+    /// Get the execution metrics collector
+    pub fn metrics(&self) -> &MetricsCollector {
+        &self.metrics
+    }
+
+    /// Get recent executions, optionally only those strictly after `since`
+    pub fn recent_executions(&self, since: Option<DateTime<Utc>>, limit: Option<usize>) -> Vec<ExecutionRecord> {
+        self.history.recent(since, limit)
+    }
+
+    /// Reject overrides of settings the plugin's configuration does not list in `overridable_settings`
+    fn check_settings_override(&self, name: &str, settings_override: &HashMap<String, serde_json::Value>) -> PluginResult<()> {
+        if settings_override.is_empty() {
+            return Ok(());
+        }
+        let overridable = self.config_manager.overridable_settings(name);
+        let mut refused: Vec<&str> = settings_override
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !overridable.iter().any(|allowed| allowed == key))
+            .collect();
+        if refused.is_empty() {
+            return Ok(());
+        }
+        refused.sort_unstable();
+        warn!("Rejected settings override for plugin '{}': {} (category: invalid_input)", name, refused.join(", "));
+        Err(PluginError::invalid_input(format!(
+            "settings not overridable for plugin '{}': {}", name, refused.join(", ")
+        )))
+    }
+
+    /// Build the execution context handed to a plugin, including its persistent storage
+    fn execution_context(&self, name: &str, settings_override: &HashMap<String, serde_json::Value>) -> PluginResult<ExecutionContext> {
+        let mut kv_stores = self.kv_stores.lock().unwrap();
+        
+        let kv = match kv_stores.get(name) {
+            Some(kv) => kv.clone(),
+            None => {
+                let path = self.config_manager.config().state_dir.join(format!("{}.json", name));
+                let kv = Arc::new(KvStore::open(path)?);
+                kv_stores.insert(name.to_string(), kv.clone());
+                kv
+            }
+        };
+        
+        Ok(ExecutionContext::new(name)
+            .with_kv(kv)
+            .with_feature_flags(self.config_manager.feature_flags(name))
+            .with_allowed_operations(self.config_manager.allowed_operations(name))
+            .with_settings(self.config_manager.plugin_settings(name))
+            .with_settings_override(settings_override.clone())
+            .with_fault_probability(self.config_manager.config().fault_injection.as_ref().map_or(0.0, |f| f.probability)))
+    }
+
     /// Execute a plugin and return only the output (for backward compatibility)
     pub fn execute_plugin_simple(&self, name: &str, input: &str) -> PluginResult<String> {
         let result = self.execute_plugin(name, input)?;
@@ -367,6 +1256,11 @@ impl PluginManager {
         self.registry.plugin_count()
     }
 
+    /// Get the plugin libraries that failed to load during the last scan
+    pub fn load_failures(&self) -> Vec<crate::PluginLoadFailure> {
+        self.registry.load_failures()
+    }
+
     /// Get configuration for a specific plugin
     pub fn get_plugin_config(&mut self, name: &str) -> &mut crate::PluginConfig {
         self.config_manager.get_plugin_config(name)
@@ -384,6 +1278,18 @@ impl PluginManager {
         self.config_manager
             .set_plugin_setting(plugin_name, key, value)
             .map_err(|e| PluginError::config_error(format!("Failed to set plugin setting: {}", e)))?;
+        self.apply_plugin_config(plugin_name);
+        
+        Ok(())
+    }
+
+    /// Set a plugin feature flag, taking effect on the next execution
+    pub fn set_feature_flag(&mut self, plugin_name: &str, flag: &str, enabled: bool) -> PluginResult<()> {
+        info!("Setting plugin '{}' feature flag '{}' = {}", plugin_name, flag, enabled);
+        
+        self.config_manager
+            .set_feature_flag(plugin_name, flag, enabled)
+            .map_err(|e| PluginError::config_error(format!("Failed to set feature flag: {}", e)))?;
         
         Ok(())
     }
@@ -398,32 +1304,92 @@ impl PluginManager {
         self.config_manager.config()
     }
 
+    /// The configuration as saved to the file, without environment overrides
+    pub fn file_config(&self) -> Config {
+        self.config_manager.without_env_overrides()
+    }
+
     /// Reload configuration and sync plugin states
     pub fn reload_config(&mut self) -> PluginResult<()> {
         info!("Reloading configuration");
         
+        let previous_settings = self.plugin_settings_snapshot();
         self.config_manager.reload()
             .map_err(|e| PluginError::config_error(format!("Failed to reload configuration: {}", e)))?;
+        self.sync_with_config(previous_settings);
+        
+        info!("Configuration reloaded successfully");
+        Ok(())
+    }
+    
+    /// Re-read a plugin's settings from the config file and notify the plugin if they changed
+    ///
+    /// Other plugins and the rest of the configuration are left untouched, and no
+    /// libraries are rescanned. Returns the plugin's settings as now in effect.
+    pub fn reload_plugin_settings(&mut self, name: &str) -> PluginResult<HashMap<String, serde_json::Value>> {
+        if !self.registry.has_plugin(name) {
+            return Err(PluginError::NotFound { name: name.to_string() });
+        }
+        
+        let previous = self.config_manager.plugin_settings(name);
+        let settings = self.config_manager.reload_plugin_settings(name)
+            .map_err(|e| PluginError::config_error(format!("Failed to reload settings of plugin '{}': {}", name, e)))?;
+        if settings != previous {
+            info!("Settings changed for plugin '{}', notifying it", name);
+            self.registry.notify_config_changed(name, &settings)?;
+        }
+        self.apply_plugin_config(name);
+        Ok(settings)
+    }
+    
+    /// Replace the configuration file with `config` and sync plugin states with it
+    ///
+    /// Invalid configuration is rejected with a `ConfigError` and nothing is changed.
+    pub fn replace_config(&mut self, config: Config) -> PluginResult<()> {
+        info!("Replacing configuration");
+        
+        let previous_settings = self.plugin_settings_snapshot();
+        self.config_manager.replace(config)
+            .map_err(|e| PluginError::config_error(format!("{:#}", e)))?;
+        self.sync_with_config(previous_settings);
         
-        // Sync plugin states with the reloaded configuration
+        info!("Configuration replaced successfully");
+        Ok(())
+    }
+    
+    /// Each configured plugin's settings, for detecting changes across a config update
+    fn plugin_settings_snapshot(&self) -> HashMap<String, HashMap<String, serde_json::Value>> {
+        self.config().plugins
+            .iter()
+            .map(|(name, plugin_config)| (name.clone(), plugin_config.settings.clone()))
+            .collect()
+    }
+    
+    /// Bring plugin states, caches and settings in line with a changed configuration
+    fn sync_with_config(&mut self, previous_settings: HashMap<String, HashMap<String, serde_json::Value>>) {
+        // Reopen plugin state lazily in case the state directory changed
+        self.kv_stores.lock().unwrap().clear();
+        // Restart warm pools lazily in case their size or host changed
+        self.warm_pools.lock().unwrap().clear();
+        
+        // Sync plugin states with the reloaded configuration and re-validate settings
         let plugin_infos = self.registry.list_plugins();
         for info in plugin_infos {
-            let config_enabled = self.config_manager.is_plugin_enabled(&info.name);
-            if config_enabled != info.enabled {
-                if config_enabled {
-                    if let Err(e) = self.registry.enable_plugin(&info.name) {
-                        warn!("Failed to enable plugin '{}' after config reload: {}", info.name, e);
-                    }
-                } else {
-                    if let Err(e) = self.registry.disable_plugin(&info.name) {
-                        warn!("Failed to disable plugin '{}' after config reload: {}", info.name, e);
-                    }
+            self.apply_plugin_config(&info.name);
+            
+            // Let plugins pick up changed settings without being reloaded
+            let settings = self.config().plugins
+                .get(&info.name)
+                .map(|plugin_config| plugin_config.settings.clone())
+                .unwrap_or_default();
+            let previous = previous_settings.get(&info.name).cloned().unwrap_or_default();
+            if settings != previous {
+                info!("Settings changed for plugin '{}', notifying it", info.name);
+                if let Err(e) = self.registry.notify_config_changed(&info.name, &settings) {
+                    warn!("Failed to notify plugin '{}' of changed settings: {}", info.name, e);
                 }
             }
         }
-        
-        info!("Configuration reloaded successfully");
-        Ok(())
     }
 
     /// Get plugins directory path
@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Execution counters for a single plugin
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginMetrics {
+    pub executions: u64,
+    pub failures: u64,
+    pub total_duration_ms: u64,
+    pub max_duration_ms: u64,
+    /// Input bytes of every execution, as the caller sent them
+    #[serde(default)]
+    pub total_input_bytes: u64,
+    #[serde(default)]
+    pub max_input_bytes: u64,
+    /// Output bytes of successful executions; failures count as no output
+    #[serde(default)]
+    pub total_output_bytes: u64,
+    #[serde(default)]
+    pub max_output_bytes: u64,
+}
+
+impl PluginMetrics {
+    /// Mean input size per execution
+    pub fn avg_input_bytes(&self) -> f64 {
+        average(self.total_input_bytes, self.executions)
+    }
+
+    /// Mean output size per execution
+    pub fn avg_output_bytes(&self) -> f64 {
+        average(self.total_output_bytes, self.executions)
+    }
+}
+
+fn average(total: u64, count: u64) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        total as f64 / count as f64
+    }
+}
+
+/// Accessor for one per-plugin counter
+type MetricValue = fn(&PluginMetrics) -> u64;
+
+/// Point-in-time copy of all execution metrics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSnapshot {
+    /// When the counters were last reset (or the collector created)
+    pub since: DateTime<Utc>,
+    pub executions_total: u64,
+    pub failures_total: u64,
+    pub plugins: BTreeMap<String, PluginMetrics>,
+}
+
+impl MetricsSnapshot {
+    fn empty() -> Self {
+        Self {
+            since: Utc::now(),
+            executions_total: 0,
+            failures_total: 0,
+            plugins: BTreeMap::new(),
+        }
+    }
+
+    /// Render the snapshot in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let per_plugin: [(&str, &str, MetricValue); 5] = [
+            ("dyn_plug_plugin_executions_total", "Plugin executions", |m| m.executions),
+            ("dyn_plug_plugin_failures_total", "Failed plugin executions", |m| m.failures),
+            ("dyn_plug_plugin_duration_ms_total", "Total plugin execution time in milliseconds", |m| m.total_duration_ms),
+            ("dyn_plug_plugin_input_bytes_total", "Total plugin input size in bytes", |m| m.total_input_bytes),
+            ("dyn_plug_plugin_output_bytes_total", "Total plugin output size in bytes", |m| m.total_output_bytes),
+        ];
+
+        let _ = writeln!(out, "# HELP dyn_plug_executions_total Plugin executions across all plugins");
+        let _ = writeln!(out, "# TYPE dyn_plug_executions_total counter");
+        let _ = writeln!(out, "dyn_plug_executions_total {}", self.executions_total);
+        let _ = writeln!(out, "# HELP dyn_plug_failures_total Failed plugin executions across all plugins");
+        let _ = writeln!(out, "# TYPE dyn_plug_failures_total counter");
+        let _ = writeln!(out, "dyn_plug_failures_total {}", self.failures_total);
+
+        for (name, help, value) in per_plugin {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            for (plugin, metrics) in &self.plugins {
+                let _ = writeln!(out, "{}{{plugin=\"{}\"}} {}", name, plugin, value(metrics));
+            }
+        }
+
+        out
+    }
+}
+
+/// Thread-safe collector of plugin execution metrics
+pub struct MetricsCollector {
+    state: Mutex<MetricsSnapshot>,
+}
+
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricsCollector {
+    /// Create a collector with all counters at zero
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(MetricsSnapshot::empty()),
+        }
+    }
+
+    /// Record one plugin execution
+    pub fn record(&self, plugin: &str, success: bool, duration_ms: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.executions_total += 1;
+        if !success {
+            state.failures_total += 1;
+        }
+
+        let metrics = state.plugins.entry(plugin.to_string()).or_default();
+        metrics.executions += 1;
+        if !success {
+            metrics.failures += 1;
+        }
+        metrics.total_duration_ms += duration_ms;
+        metrics.max_duration_ms = metrics.max_duration_ms.max(duration_ms);
+    }
+
+    /// Record the input and output sizes of one plugin execution
+    pub fn record_sizes(&self, plugin: &str, input_bytes: u64, output_bytes: u64) {
+        let mut state = self.state.lock().unwrap();
+        let metrics = state.plugins.entry(plugin.to_string()).or_default();
+        metrics.total_input_bytes += input_bytes;
+        metrics.max_input_bytes = metrics.max_input_bytes.max(input_bytes);
+        metrics.total_output_bytes += output_bytes;
+        metrics.max_output_bytes = metrics.max_output_bytes.max(output_bytes);
+    }
+
+    /// Copy the current counters
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Zero all counters, returning the values they held
+    pub fn reset(&self) -> MetricsSnapshot {
+        let mut state = self.state.lock().unwrap();
+        std::mem::replace(&mut *state, MetricsSnapshot::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_snapshot() {
+        let collector = MetricsCollector::new();
+        collector.record("a", true, 5);
+        collector.record("a", false, 9);
+        collector.record("b", true, 1);
+
+        let snapshot = collector.snapshot();
+        assert_eq!(snapshot.executions_total, 3);
+        assert_eq!(snapshot.failures_total, 1);
+        assert_eq!(snapshot.plugins["a"], PluginMetrics {
+            executions: 2,
+            failures: 1,
+            total_duration_ms: 14,
+            max_duration_ms: 9,
+            ..PluginMetrics::default()
+        });
+
+        let text = snapshot.to_prometheus();
+        assert!(text.contains("dyn_plug_executions_total 3"));
+        assert!(text.contains("dyn_plug_plugin_failures_total{plugin=\"a\"} 1"));
+    }
+
+    #[test]
+    fn test_record_sizes() {
+        let collector = MetricsCollector::new();
+        collector.record("a", true, 1);
+        collector.record_sizes("a", 10, 40);
+        collector.record("a", false, 1);
+        collector.record_sizes("a", 30, 0);
+
+        let metrics = &collector.snapshot().plugins["a"];
+        assert_eq!(metrics.avg_input_bytes(), 20.0);
+        assert_eq!(metrics.max_input_bytes, 30);
+        assert_eq!(metrics.avg_output_bytes(), 20.0);
+        assert_eq!(metrics.max_output_bytes, 40);
+        assert_eq!(PluginMetrics::default().avg_input_bytes(), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let collector = MetricsCollector::new();
+        collector.record("a", true, 5);
+
+        let previous = collector.reset();
+        assert_eq!(previous.executions_total, 1);
+
+        let snapshot = collector.snapshot();
+        assert_eq!(snapshot.executions_total, 0);
+        assert!(snapshot.plugins.is_empty());
+        assert!(snapshot.since >= previous.since);
+    }
+}
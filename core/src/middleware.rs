@@ -0,0 +1,19 @@
+use crate::PluginError;
+
+/// Transforms plugin input before it reaches the plugin
+///
+/// Middleware registered on a `PluginManager` runs in registration order for
+/// every execution; an error fails the execution without running the plugin.
+pub trait InputMiddleware: Send + Sync {
+    /// Process the input destined for the plugin called `name`
+    fn process(&self, name: &str, input: String) -> Result<String, PluginError>;
+}
+
+/// Transforms successful plugin output before it is returned
+///
+/// Middleware registered on a `PluginManager` runs in registration order; an
+/// error turns the execution into a failed `ExecutionResult`.
+pub trait OutputMiddleware: Send + Sync {
+    /// Process the output produced by the plugin called `name`
+    fn process(&self, name: &str, output: String) -> Result<String, PluginError>;
+}
@@ -0,0 +1,86 @@
+//! Panic reporting for plugin executions
+//!
+//! The registry catches plugin panics and turns them into errors, but the
+//! default hook prints only a bare message to stderr first. [`install`]
+//! replaces it for plugin code with a log record naming the plugin, plus a
+//! backtrace when debug logging is enabled.
+//!
+//! In-process executions run on the caller's thread, which is attributed to the
+//! plugin only while the plugin is being called. `dyn-plug-host` runs isolated
+//! executions on threads named after the plugin, see [`exec_thread_name`].
+
+use log::{debug, error, log_enabled, Level};
+use std::cell::RefCell;
+use std::sync::Once;
+
+/// Prefix of the names of the `dyn-plug-host` threads that run isolated executions
+pub const EXEC_THREAD_PREFIX: &str = "dynplug-exec-";
+
+thread_local! {
+    /// Plugin whose code is running on this thread, if any
+    static CURRENT_PLUGIN: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static INSTALL: Once = Once::new();
+
+/// Name for a `dyn-plug-host` thread dedicated to executing `plugin`
+pub fn exec_thread_name(plugin: &str) -> String {
+    format!("{}{}", EXEC_THREAD_PREFIX, plugin)
+}
+
+/// Marks the current thread as running a plugin until dropped
+pub(crate) struct PluginScope {
+    previous: Option<String>,
+}
+
+impl Drop for PluginScope {
+    fn drop(&mut self) {
+        CURRENT_PLUGIN.with(|current| *current.borrow_mut() = self.previous.take());
+    }
+}
+
+/// Attribute panics on the current thread to `plugin` while the returned scope lives
+pub(crate) fn enter(plugin: &str) -> PluginScope {
+    let previous = CURRENT_PLUGIN.with(|current| current.borrow_mut().replace(plugin.to_string()));
+    PluginScope { previous }
+}
+
+/// The plugin running on the current thread, from an active scope or the thread name
+fn current_plugin() -> Option<String> {
+    CURRENT_PLUGIN
+        .with(|current| current.borrow().clone())
+        .or_else(|| {
+            std::thread::current()
+                .name()
+                .and_then(|name| name.strip_prefix(EXEC_THREAD_PREFIX))
+                .map(str::to_string)
+        })
+}
+
+/// Install the panic hook; later calls do nothing
+///
+/// Panics outside plugin code are passed to the previously installed hook.
+pub fn install() {
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let Some(plugin) = current_plugin() else {
+                previous(info);
+                return;
+            };
+
+            let message = info
+                .payload()
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| info.payload().downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "non-string panic payload".to_string());
+            let location = info.location().map(|location| location.to_string()).unwrap_or_default();
+            let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+            error!("Plugin '{}' panicked on thread '{}' at {}: {} (category: plugin_panic)", plugin, thread, location, message);
+            if log_enabled!(Level::Debug) {
+                debug!("Backtrace of plugin '{}' panic:\n{}", plugin, std::backtrace::Backtrace::force_capture());
+            }
+        }));
+    });
+}
@@ -1,4 +1,199 @@
+use crate::KvStore;
+use std::collections::HashMap;
 use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+/// Outcome a plugin reports alongside successful output
+///
+/// `Rejected` marks input the plugin handled but judged negatively, such as a
+/// validator reporting invalid data; it is not an execution error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecStatus {
+    #[default]
+    Ok,
+    Rejected,
+    Warning,
+}
+
+impl ExecStatus {
+    /// Whether this is the default `Ok` status
+    pub fn is_ok(&self) -> bool {
+        *self == ExecStatus::Ok
+    }
+}
+
+/// Host-provided context passed to a plugin for a single execution
+///
+/// The context gives plugins access to host services such as persistent
+/// key-value storage without changing the basic `execute` signature.
+#[derive(Clone, Default)]
+pub struct ExecutionContext {
+    plugin_name: String,
+    kv: Option<Arc<KvStore>>,
+    feature_flags: HashMap<String, bool>,
+    settings: HashMap<String, serde_json::Value>,
+    settings_override: HashMap<String, serde_json::Value>,
+    allowed_operations: Option<Vec<String>>,
+    status: Arc<Mutex<ExecStatus>>,
+    fault_probability: f64,
+}
+
+impl ExecutionContext {
+    /// Create an empty context for the named plugin
+    pub fn new<S: Into<String>>(plugin_name: S) -> Self {
+        Self {
+            plugin_name: plugin_name.into(),
+            kv: None,
+            feature_flags: HashMap::new(),
+            settings: HashMap::new(),
+            settings_override: HashMap::new(),
+            allowed_operations: None,
+            status: Arc::default(),
+            fault_probability: 0.0,
+        }
+    }
+
+    /// Attach the plugin's persistent key-value store
+    pub fn with_kv(mut self, kv: Arc<KvStore>) -> Self {
+        self.kv = Some(kv);
+        self
+    }
+
+    /// Attach the plugin's configured feature flags
+    pub fn with_feature_flags(mut self, feature_flags: HashMap<String, bool>) -> Self {
+        self.feature_flags = feature_flags;
+        self
+    }
+
+    /// Attach the plugin's configured settings
+    pub fn with_settings(mut self, settings: HashMap<String, serde_json::Value>) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Attach settings the caller overrode for this execution only
+    pub fn with_settings_override(mut self, settings_override: HashMap<String, serde_json::Value>) -> Self {
+        self.settings_override = settings_override;
+        self
+    }
+
+    /// Restrict the operations the plugin may run, as configured by the operator
+    pub fn with_allowed_operations(mut self, allowed_operations: Option<Vec<String>>) -> Self {
+        self.allowed_operations = allowed_operations;
+        self
+    }
+
+    /// Fail each execution attempt with a temporary failure at this rate, for fault injection
+    pub(crate) fn with_fault_probability(mut self, probability: f64) -> Self {
+        self.fault_probability = probability;
+        self
+    }
+
+    /// Whether fault injection fails the attempt about to be made
+    pub(crate) fn inject_fault(&self) -> bool {
+        self.fault_probability > 0.0 && rand::random_bool(self.fault_probability)
+    }
+
+    /// Operations the plugin may run, or `None` when it is unrestricted
+    ///
+    /// The host rejects other operations before the plugin is called.
+    pub fn allowed_operations(&self) -> Option<&[String]> {
+        self.allowed_operations.as_deref()
+    }
+
+    /// A plugin setting, including any override for this execution
+    pub fn setting(&self, key: &str) -> Option<&serde_json::Value> {
+        self.settings_override.get(key).or_else(|| self.settings.get(key))
+    }
+
+    /// A plugin setting as the operator configured it, ignoring overrides
+    ///
+    /// Use this for settings that guard what the plugin may do, which callers must not change.
+    pub fn configured_setting(&self, key: &str) -> Option<&serde_json::Value> {
+        self.settings.get(key)
+    }
+
+    /// Whether a feature flag is on; flags that are not configured are off
+    pub fn feature_enabled(&self, flag: &str) -> bool {
+        self.feature_flags.get(flag).copied().unwrap_or(false)
+    }
+
+    /// All configured settings, without overrides
+    pub(crate) fn settings(&self) -> &HashMap<String, serde_json::Value> {
+        &self.settings
+    }
+
+    /// Settings overridden for this execution
+    pub(crate) fn settings_override(&self) -> &HashMap<String, serde_json::Value> {
+        &self.settings_override
+    }
+
+    /// All configured feature flags
+    pub(crate) fn feature_flags(&self) -> &HashMap<String, bool> {
+        &self.feature_flags
+    }
+
+    /// Name of the plugin being executed
+    pub fn plugin_name(&self) -> &str {
+        &self.plugin_name
+    }
+
+    /// Report the status of this execution's output, shared with clones of the context
+    pub fn set_status(&self, status: ExecStatus) {
+        *self.status.lock().unwrap() = status;
+    }
+
+    /// Status reported for this execution, `Ok` unless the plugin set one
+    pub fn status(&self) -> ExecStatus {
+        *self.status.lock().unwrap()
+    }
+
+    /// Persistent key-value storage for this plugin, if the host provides one
+    pub fn kv(&self) -> Option<&KvStore> {
+        self.kv.as_deref()
+    }
+}
+
+/// Handler for a plugin's custom HTTP route, called with the request input
+pub type RouteHandler = Arc<dyn Fn(&str) -> Result<String, Box<dyn Error>> + Send + Sync>;
+
+/// An HTTP endpoint a plugin serves under `/api/v1/plugins/{name}/custom/{path}`
+#[derive(Clone)]
+pub struct RouteSpec {
+    /// HTTP method in upper case, e.g. `GET`
+    pub method: String,
+    /// Sub-path below `custom/`, without leading or trailing slashes
+    pub path: String,
+    pub handler: RouteHandler,
+}
+
+impl RouteSpec {
+    /// Declare a route; the method is upper-cased and slashes around the path are trimmed
+    pub fn new<M, P, F>(method: M, path: P, handler: F) -> Self
+    where
+        M: AsRef<str>,
+        P: AsRef<str>,
+        F: Fn(&str) -> Result<String, Box<dyn Error>> + Send + Sync + 'static,
+    {
+        Self {
+            method: method.as_ref().to_ascii_uppercase(),
+            path: path.as_ref().trim_matches('/').to_string(),
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Whether this route serves `method` requests for `path`
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        self.method.eq_ignore_ascii_case(method) && self.path == path.trim_matches('/')
+    }
+}
+
+impl std::fmt::Debug for RouteSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouteSpec").field("method", &self.method).field("path", &self.path).finish()
+    }
+}
 
 /// The core trait that all plugins must implement
 ///
@@ -14,6 +209,82 @@ pub trait Plugin: Send + Sync {
     /// Returns a description of what the plugin does
     fn description(&self) -> &str;
 
+    /// Returns example inputs as `(description, example_input)` pairs
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    /// Returns a JSON Schema describing accepted input, if the plugin publishes one
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Returns a JSON Schema describing produced output, if the plugin publishes one
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Operations the plugin supports, listed for discovery
+    ///
+    /// The default reads the `operation` enum from `input_schema`, so plugins that
+    /// publish one need not repeat it.
+    fn capabilities(&self) -> Vec<String> {
+        self.input_schema()
+            .and_then(|schema| schema.pointer("/properties/operation/enum").and_then(|ops| ops.as_array().cloned()))
+            .map(|ops| ops.iter().filter_map(|op| op.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Whether running `operation` twice has the same effect as running it once
+    ///
+    /// The host retries transient failures only for idempotent operations, so an
+    /// operation with side effects is never applied twice by a retry.
+    fn is_idempotent(&self, _operation: &str) -> bool {
+        true
+    }
+
+    /// Custom HTTP routes the API server serves for this plugin
+    ///
+    /// Each handler receives the request body, or the query string for requests
+    /// without a body, and its output becomes the response. The routes are read on
+    /// every request, so they may change while the plugin is loaded.
+    fn routes(&self) -> Vec<RouteSpec> {
+        vec![]
+    }
+
+    /// Host features the plugin depends on, see `HOST_FEATURES`
+    ///
+    /// A plugin requiring a feature the host does not provide is refused at load time.
+    fn required_host_features(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Called once when the plugin is registered, before it can be executed
+    ///
+    /// The host bounds this hook with the configured load timeout; a plugin whose
+    /// `on_load` fails or does not return in time is not registered.
+    fn on_load(&self) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    /// Check the plugin's settings as a whole, called once when the plugin is loaded
+    ///
+    /// Depending on `invalid_plugin_config`, a plugin whose settings are rejected is
+    /// kept disabled or loaded with a warning.
+    fn validate_config(&self, _settings: &HashMap<String, serde_json::Value>) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called when a configuration reload changes this plugin's settings
+    fn on_config_changed(&self, _settings: &HashMap<String, serde_json::Value>) {}
+
+    /// Called when the plugin is disabled, so stateful plugins can flush or reset
+    ///
+    /// Errors are logged by the host; the plugin is disabled regardless.
+    fn reset(&self) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
     /// Executes the plugin with the given input and returns the result
     ///
     /// # Arguments
@@ -23,6 +294,46 @@ pub trait Plugin: Send + Sync {
     /// * `Ok(String)` - The processed output
     /// * `Err(Box<dyn Error>)` - An error if execution fails
     fn execute(&self, input: &str) -> Result<String, Box<dyn Error>>;
+
+    /// Executes the plugin and reports the status of its output
+    ///
+    /// The default implementation delegates to `execute` and reports `ExecStatus::Ok`.
+    /// Plugins that distinguish a negative result from an error override this.
+    fn execute_status(&self, input: &str) -> Result<(String, ExecStatus), Box<dyn Error>> {
+        self.execute(input).map(|output| (output, ExecStatus::Ok))
+    }
+
+    /// Executes the plugin with access to host-provided services
+    ///
+    /// The default implementation delegates to `execute_status` and records its status
+    /// on the context. Plugins that need host services such as persistent storage
+    /// override this and report a status with `ExecutionContext::set_status`.
+    fn execute_with_context(&self, input: &str, ctx: &ExecutionContext) -> Result<String, Box<dyn Error>> {
+        let (output, status) = self.execute_status(input)?;
+        ctx.set_status(status);
+        Ok(output)
+    }
+
+    /// Executes the plugin once per input, returning one result per input in order
+    ///
+    /// The default implementation calls `execute` for each input. Plugins that can
+    /// process many inputs more cheaply together override this. An `Err` fails the
+    /// whole batch; per-input failures are reported as `Err` entries.
+    fn batch_execute(&self, inputs: &[String]) -> Result<Vec<Result<String, String>>, Box<dyn Error>> {
+        Ok(inputs
+            .iter()
+            .map(|input| self.execute(input).map_err(|e| e.to_string()))
+            .collect())
+    }
+
+    /// Executes the plugin and returns several named outputs
+    ///
+    /// The default implementation returns the output of `execute` under the
+    /// `"result"` key. Plugins that produce more than one value, such as several
+    /// digests of the same input, override this.
+    fn execute_multi(&self, input: &str) -> Result<HashMap<String, String>, Box<dyn Error>> {
+        Ok(HashMap::from([("result".to_string(), self.execute(input)?)]))
+    }
 }
 
 /// Macro to simplify plugin registration
@@ -0,0 +1,201 @@
+//! Shared input parsing helpers for plugin implementations
+//!
+//! Plugins in this workspace accept a JSON object carrying an `operation`
+//! field plus operation-specific fields. These helpers parse that envelope
+//! and extract fields so every plugin reports validation failures with the
+//! same wording, e.g. `missing required field 'text'`.
+
+use crate::PluginError;
+use serde_json::Value;
+
+/// Parse plugin input as a JSON object and extract its `operation` field
+///
+/// # Returns
+/// * `Ok((operation, value))` - The operation name and the full parsed input
+/// * `Err(PluginError::InvalidInput)` - If the input is not a JSON object with a string `operation`
+pub fn parse_operation(input: &str) -> Result<(String, Value), PluginError> {
+    let parsed: Value = serde_json::from_str(input)
+        .map_err(|e| PluginError::invalid_input(format!("invalid JSON input: {}", e)))?;
+
+    if !parsed.is_object() {
+        return Err(PluginError::invalid_input("input must be a JSON object"));
+    }
+
+    let operation = require_str(&parsed, "operation")?.to_string();
+    Ok((operation, parsed))
+}
+
+/// Parse plugin input like [`parse_operation`], guiding plain-text input towards `example`
+///
+/// Input that looks like a bare word, sentence or number rather than an attempt
+/// at JSON fails with `input must be JSON, e.g. <example>` instead of a parser error.
+pub fn parse_operation_with_example(input: &str, example: &str) -> Result<(String, Value), PluginError> {
+    parse_operation(input).map_err(|e| {
+        if looks_like_plain_text(input) {
+            PluginError::invalid_input(format!("input must be JSON, e.g. {}", example))
+        } else {
+            e
+        }
+    })
+}
+
+/// Check that the operation named by `input` is one of `allowed`
+///
+/// Input without a readable `operation` field is rejected, since the operation
+/// it would run cannot be checked.
+pub fn check_operation_allowed(input: &str, allowed: &[String]) -> Result<(), PluginError> {
+    let (operation, _) = parse_operation(input)?;
+    if allowed.contains(&operation) {
+        return Ok(());
+    }
+    Err(PluginError::invalid_input(format!(
+        "operation '{}' is not allowed; allowed operations: {}",
+        operation,
+        allowed.join(", ")
+    )))
+}
+
+/// Check whether input looks like plain text or a bare number rather than JSON
+pub fn looks_like_plain_text(input: &str) -> bool {
+    let trimmed = input.trim();
+    !trimmed.is_empty() && !trimmed.starts_with(['{', '[', '"'])
+}
+
+/// Get a required string field
+pub fn require_str<'a>(value: &'a Value, field: &str) -> Result<&'a str, PluginError> {
+    require_field(value, field)?
+        .as_str()
+        .ok_or_else(|| wrong_type(field, "a string"))
+}
+
+/// Get a required array field
+pub fn require_array<'a>(value: &'a Value, field: &str) -> Result<&'a Vec<Value>, PluginError> {
+    require_field(value, field)?
+        .as_array()
+        .ok_or_else(|| wrong_type(field, "an array"))
+}
+
+/// Get a required object field
+pub fn require_object<'a>(
+    value: &'a Value,
+    field: &str,
+) -> Result<&'a serde_json::Map<String, Value>, PluginError> {
+    require_field(value, field)?
+        .as_object()
+        .ok_or_else(|| wrong_type(field, "an object"))
+}
+
+/// Get a required numeric field as `f64`
+pub fn require_f64(value: &Value, field: &str) -> Result<f64, PluginError> {
+    require_field(value, field)?
+        .as_f64()
+        .ok_or_else(|| wrong_type(field, "a number"))
+}
+
+/// Get a required array field whose elements must all be numbers
+pub fn require_f64_array(value: &Value, field: &str) -> Result<Vec<f64>, PluginError> {
+    require_array(value, field)?
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            item.as_f64()
+                .ok_or_else(|| wrong_type(&format!("{}[{}]", field, index), "a number"))
+        })
+        .collect()
+}
+
+/// Get an optional field, treating an explicit `null` as absent
+pub fn optional_field<'a>(value: &'a Value, field: &str) -> Option<&'a Value> {
+    value.get(field).filter(|v| !v.is_null())
+}
+
+fn require_field<'a>(value: &'a Value, field: &str) -> Result<&'a Value, PluginError> {
+    optional_field(value, field)
+        .ok_or_else(|| PluginError::invalid_input(format!("missing required field '{}'", field)))
+}
+
+fn wrong_type(field: &str, expected: &str) -> PluginError {
+    PluginError::invalid_input(format!("field '{}' must be {}", field, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(error: PluginError) -> String {
+        match error {
+            PluginError::InvalidInput { message } => message,
+            other => panic!("expected InvalidInput, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_operation() {
+        let (operation, parsed) = parse_operation(r#"{"operation": "uppercase", "text": "hi"}"#).unwrap();
+        assert_eq!(operation, "uppercase");
+        assert_eq!(parsed["text"], "hi");
+    }
+
+    #[test]
+    fn test_parse_operation_errors() {
+        let error = parse_operation("not json").unwrap_err();
+        assert!(message(error).starts_with("invalid JSON input"));
+
+        let error = parse_operation("[1, 2]").unwrap_err();
+        assert_eq!(message(error), "input must be a JSON object");
+
+        let error = parse_operation(r#"{"text": "hi"}"#).unwrap_err();
+        assert_eq!(message(error), "missing required field 'operation'");
+
+        let error = parse_operation(r#"{"operation": 5}"#).unwrap_err();
+        assert_eq!(message(error), "field 'operation' must be a string");
+    }
+
+    #[test]
+    fn test_plain_text_guidance() {
+        let example = r#"{"operation":"uppercase","text":"hello"}"#;
+        for input in ["hello", "hello world", "42", "true"] {
+            let error = parse_operation_with_example(input, example).unwrap_err();
+            assert_eq!(error.to_string(), format!("Invalid input: input must be JSON, e.g. {}", example));
+        }
+
+        // Malformed JSON keeps the parser error
+        let error = parse_operation_with_example("{\"operation\":", example).unwrap_err();
+        assert!(error.to_string().contains("invalid JSON input"));
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let value = json!({"operation": "add", "nothing": null});
+
+        assert_eq!(message(require_str(&value, "text").unwrap_err()), "missing required field 'text'");
+        assert_eq!(message(require_array(&value, "numbers").unwrap_err()), "missing required field 'numbers'");
+        assert_eq!(message(require_object(&value, "vars").unwrap_err()), "missing required field 'vars'");
+        assert_eq!(message(require_f64(&value, "base").unwrap_err()), "missing required field 'base'");
+        assert_eq!(message(require_str(&value, "nothing").unwrap_err()), "missing required field 'nothing'");
+    }
+
+    #[test]
+    fn test_mistyped_fields() {
+        let value = json!({"text": 1, "numbers": "1,2", "vars": [], "base": "16", "mixed": [1, "two"]});
+
+        assert_eq!(message(require_str(&value, "text").unwrap_err()), "field 'text' must be a string");
+        assert_eq!(message(require_array(&value, "numbers").unwrap_err()), "field 'numbers' must be an array");
+        assert_eq!(message(require_object(&value, "vars").unwrap_err()), "field 'vars' must be an object");
+        assert_eq!(message(require_f64(&value, "base").unwrap_err()), "field 'base' must be a number");
+        assert_eq!(message(require_f64_array(&value, "mixed").unwrap_err()), "field 'mixed[1]' must be a number");
+    }
+
+    #[test]
+    fn test_present_fields() {
+        let value = json!({"text": "hi", "numbers": [1, 2.5], "vars": {"a": 1}, "base": 16});
+
+        assert_eq!(require_str(&value, "text").unwrap(), "hi");
+        assert_eq!(require_array(&value, "numbers").unwrap().len(), 2);
+        assert_eq!(require_object(&value, "vars").unwrap().len(), 1);
+        assert_eq!(require_f64(&value, "base").unwrap(), 16.0);
+        assert_eq!(require_f64_array(&value, "numbers").unwrap(), vec![1.0, 2.5]);
+        assert!(optional_field(&value, "missing").is_none());
+    }
+}
@@ -0,0 +1,183 @@
+//! Priority-ordered admission of executions to a fixed number of slots
+//!
+//! Callers wait in [`ExecutionQueue::acquire`] until a slot is free. A freed slot
+//! goes to the highest-priority waiter, and among equal priorities to the one that
+//! has waited longest.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::str::FromStr;
+use std::sync::{Arc, Condvar, Mutex};
+
+/// How urgently an execution should be scheduled relative to others
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl FromStr for ExecutionPriority {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "normal" => Ok(Self::Normal),
+            "high" => Ok(Self::High),
+            other => Err(format!("unknown priority '{}', expected low, normal or high", other)),
+        }
+    }
+}
+
+/// A waiter's place in the queue
+#[derive(Debug, PartialEq, Eq)]
+struct Ticket {
+    priority: ExecutionPriority,
+    seq: u64,
+}
+
+impl Ord for Ticket {
+    /// Higher priority first, then earlier arrival
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl PartialOrd for Ticket {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Default)]
+struct QueueState {
+    running: usize,
+    waiting: BinaryHeap<Ticket>,
+    next_seq: u64,
+}
+
+/// Queue handing out a fixed number of execution slots in priority order
+pub struct ExecutionQueue {
+    slots: usize,
+    state: Mutex<QueueState>,
+    freed: Condvar,
+}
+
+impl ExecutionQueue {
+    /// Create a queue with `slots` concurrent executions, at least one
+    pub fn new(slots: usize) -> Self {
+        Self {
+            slots: slots.max(1),
+            state: Mutex::new(QueueState::default()),
+            freed: Condvar::new(),
+        }
+    }
+
+    /// Block until this caller is granted a slot, held until the returned guard is dropped
+    ///
+    /// The guard keeps the queue alive, so async callers can wait on a blocking thread.
+    pub fn acquire(self: &Arc<Self>, priority: ExecutionPriority) -> QueueSlot {
+        let mut state = self.state.lock().unwrap();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.waiting.push(Ticket { priority, seq });
+
+        while state.running >= self.slots || state.waiting.peek().is_none_or(|next| next.seq != seq) {
+            state = self.freed.wait(state).unwrap();
+        }
+        state.waiting.pop();
+        state.running += 1;
+        // Another slot may still be free for the next waiter
+        self.freed.notify_all();
+        QueueSlot { queue: self.clone() }
+    }
+
+    /// Number of callers waiting for a slot
+    pub fn waiting(&self) -> usize {
+        self.state.lock().unwrap().waiting.len()
+    }
+}
+
+/// A held execution slot, released on drop
+pub struct QueueSlot {
+    queue: Arc<ExecutionQueue>,
+}
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        self.queue.state.lock().unwrap().running -= 1;
+        self.queue.freed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn wait_for_waiters(queue: &ExecutionQueue, count: usize) {
+        while queue.waiting() < count {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_high_priority_runs_before_earlier_low_priority() {
+        let queue = Arc::new(ExecutionQueue::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let blocker = queue.acquire(ExecutionPriority::Normal);
+
+        let spawn = |priority: ExecutionPriority| {
+            let (queue, order) = (queue.clone(), order.clone());
+            std::thread::spawn(move || {
+                let _slot = queue.acquire(priority);
+                order.lock().unwrap().push(priority);
+            })
+        };
+        let low = spawn(ExecutionPriority::Low);
+        wait_for_waiters(&queue, 1);
+        let high = spawn(ExecutionPriority::High);
+        wait_for_waiters(&queue, 2);
+
+        drop(blocker);
+        low.join().unwrap();
+        high.join().unwrap();
+        assert_eq!(*order.lock().unwrap(), [ExecutionPriority::High, ExecutionPriority::Low]);
+    }
+
+    #[test]
+    fn test_equal_priorities_run_in_arrival_order() {
+        let queue = Arc::new(ExecutionQueue::new(1));
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let blocker = queue.acquire(ExecutionPriority::High);
+
+        let handles: Vec<_> = (0..3)
+            .map(|i| {
+                let (waiter, order) = (queue.clone(), order.clone());
+                let handle = std::thread::spawn(move || {
+                    let _slot = waiter.acquire(ExecutionPriority::Normal);
+                    order.lock().unwrap().push(i);
+                });
+                wait_for_waiters(&queue, i + 1);
+                handle
+            })
+            .collect();
+
+        drop(blocker);
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_parse_priority() {
+        assert_eq!("HIGH".parse(), Ok(ExecutionPriority::High));
+        assert_eq!(" low ".parse(), Ok(ExecutionPriority::Low));
+        assert!("urgent".parse::<ExecutionPriority>().is_err());
+    }
+}
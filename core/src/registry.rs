@@ -1,11 +1,14 @@
-use crate::{Plugin, PluginError, PluginResult};
+use crate::{panic_hook, ExecutionContext, Plugin, PluginError, PluginExecutionError, PluginResult};
 use libloading::{Library, Symbol};
-use log::{debug, error, info, warn};
+use log::{debug, error, info, log, warn, Level};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, RwLock};
+use std::sync::{mpsc, Arc, Mutex, RwLock};
+use std::time::Duration;
+use tempfile::TempDir;
 
 /// Plugin metadata information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,18 +21,76 @@ pub struct PluginInfo {
     pub path: PathBuf,
 }
 
+/// A plugin that could not be loaded during the last scan, or registered since
+///
+/// In-process plugins have no library, so their failures carry an empty path.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginLoadFailure {
+    pub path: PathBuf,
+    pub error: String,
+    pub category: String,
+}
+
 /// A loaded plugin with its associated library
 struct LoadedPlugin {
-    plugin: Box<dyn Plugin>,
+    plugin: Arc<dyn Plugin>,
     #[allow(dead_code)] // Keep library alive to prevent unloading
-    library: Library,
+    library: Option<Library>,
     info: PluginInfo,
 }
 
+/// Observer notified of plugin lifecycle changes in a `PluginRegistry`
+///
+/// Callbacks run on the thread that made the change, after the registry lock is
+/// released. Every callback defaults to doing nothing.
+pub trait RegistryListener: Send + Sync {
+    /// A plugin was loaded or registered
+    fn on_loaded(&self, _info: &PluginInfo) {}
+    /// A disabled plugin was enabled
+    fn on_enabled(&self, _name: &str) {}
+    /// An enabled plugin was disabled
+    fn on_disabled(&self, _name: &str) {}
+    /// A plugin was removed from the registry
+    fn on_unloaded(&self, _name: &str) {}
+}
+
+/// Features this host provides to plugins, as named by `Plugin::required_host_features`
+pub const HOST_FEATURES: &[&str] = &[
+    "execution_context",
+    "kv_store",
+    "feature_flags",
+    "config_changed",
+    "reset",
+    "schemas",
+    "settings",
+];
+
+/// Words no plugin may use as its name, compared ignoring ASCII case
+///
+/// Nothing selects plugins by these words yet; they are kept free for that.
+const RESERVED_PLUGIN_NAMES: &[&str] = &["all", "none", "default", "self"];
+
+/// Log target for messages about a single plugin's execution, e.g.
+/// `dyn_plug::plugin::plugin_a`, so `RUST_LOG` can isolate one plugin.
+pub fn plugin_log_target(name: &str) -> String {
+    format!("dyn_plug::plugin::{}", name)
+}
+
 /// Plugin registry that manages dynamic loading and storage of plugins
 pub struct PluginRegistry {
     plugins: Arc<RwLock<HashMap<String, LoadedPlugin>>>,
+    /// Libraries of unloaded plugins, kept mapped until the registry is dropped
+    retired_libraries: Mutex<Vec<Library>>,
+    /// Extraction directories for archive-loaded plugins, removed when the registry is dropped
+    archive_dirs: Mutex<Vec<TempDir>>,
+    load_failures: Arc<RwLock<Vec<PluginLoadFailure>>>,
     plugins_dir: PathBuf,
+    system_plugins_dir: Option<PathBuf>,
+    expected_digests: HashMap<String, String>,
+    recursive_scan: bool,
+    max_scan_depth: usize,
+    load_timeout: Duration,
+    listeners: RwLock<Vec<Box<dyn RegistryListener>>>,
 }
 
 impl PluginRegistry {
@@ -40,10 +101,59 @@ impl PluginRegistry {
         
         Self {
             plugins: Arc::new(RwLock::new(HashMap::new())),
+            retired_libraries: Mutex::new(Vec::new()),
+            archive_dirs: Mutex::new(Vec::new()),
+            load_failures: Arc::new(RwLock::new(Vec::new())),
             plugins_dir,
+            system_plugins_dir: None,
+            expected_digests: HashMap::new(),
+            recursive_scan: false,
+            max_scan_depth: 0,
+            load_timeout: Duration::from_secs(10),
+            listeners: RwLock::new(Vec::new()),
         }
     }
 
+    /// Register a listener for plugin load, enable, disable, and unload events
+    pub fn subscribe(&self, listener: Box<dyn RegistryListener>) {
+        self.listeners.write().unwrap().push(listener);
+    }
+
+    fn notify_listeners<F: Fn(&dyn RegistryListener)>(&self, notify: F) {
+        for listener in self.listeners.read().unwrap().iter() {
+            notify(listener.as_ref());
+        }
+    }
+
+    /// Pin expected SHA-256 digests (hex) of plugin libraries, keyed by file name
+    ///
+    /// Libraries with a pinned digest are refused if their contents do not match.
+    pub fn with_expected_digests(mut self, expected_digests: HashMap<String, String>) -> Self {
+        self.expected_digests = expected_digests;
+        self
+    }
+
+    /// Bound how long a plugin's `on_load` hook may run before the plugin is skipped
+    pub fn with_load_timeout(mut self, load_timeout: Duration) -> Self {
+        self.load_timeout = load_timeout;
+        self
+    }
+
+    /// Enable scanning subdirectories of the plugins directory, up to `max_depth` levels deep
+    pub fn with_recursive_scan(mut self, recursive: bool, max_depth: usize) -> Self {
+        self.recursive_scan = recursive;
+        self.max_scan_depth = max_depth;
+        self
+    }
+
+    /// Also scan `system_plugins_dir`, when given, before the plugins directory
+    ///
+    /// A plugin in the plugins directory replaces a system plugin with the same name.
+    pub fn with_system_plugins_dir(mut self, system_plugins_dir: Option<PathBuf>) -> Self {
+        self.system_plugins_dir = system_plugins_dir;
+        self
+    }
+
     /// Scan the plugins directory and load all available plugins with retry logic
     pub fn scan_and_load(&self) -> PluginResult<Vec<String>> {
         self.scan_and_load_with_retry(3, std::time::Duration::from_millis(500))
@@ -53,58 +163,46 @@ impl PluginRegistry {
     pub fn scan_and_load_with_retry(&self, max_retries: u32, retry_delay: std::time::Duration) -> PluginResult<Vec<String>> {
         info!("Scanning plugins directory: {:?}", self.plugins_dir);
         
+        // Failures are reported per scan
+        self.load_failures.write().unwrap().clear();
+        
+        // System plugins load first so same-named plugins in the plugins directory replace them
+        let mut libraries = match &self.system_plugins_dir {
+            Some(system_dir) if system_dir.is_dir() => {
+                info!("Scanning system plugins directory: {:?}", system_dir);
+                self.discover_plugin_libraries(system_dir).unwrap_or_else(|e| {
+                    warn!("Failed to read system plugins directory {:?}: {}", system_dir, e);
+                    Vec::new()
+                })
+            }
+            Some(system_dir) => {
+                debug!("System plugins directory does not exist: {:?}", system_dir);
+                Vec::new()
+            }
+            None => Vec::new(),
+        };
+
         if !self.plugins_dir.exists() {
             warn!("Plugins directory does not exist: {:?}", self.plugins_dir);
             std::fs::create_dir_all(&self.plugins_dir)?;
             info!("Created plugins directory: {:?}", self.plugins_dir);
-            return Ok(Vec::new());
+        } else {
+            libraries.extend(self.discover_plugin_libraries(&self.plugins_dir)?);
         }
 
         let mut loaded_plugins = Vec::new();
         let mut failed_plugins = Vec::new();
         
-        let entries = std::fs::read_dir(&self.plugins_dir)?;
-
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
+        for path in libraries {
+            debug!("Found potential plugin library: {:?}", path);
             
-            if self.is_plugin_library(&path) {
-                debug!("Found potential plugin library: {:?}", path);
-                
-                // Try loading with retry logic for transient failures
-                let mut last_error = None;
-                let mut loaded = false;
-                
-                for attempt in 1..=max_retries {
-                    match self.load_plugin_from_path(&path) {
-                        Ok(plugin_name) => {
-                            if attempt > 1 {
-                                info!("Successfully loaded plugin '{}' on attempt {}", plugin_name, attempt);
-                            }
-                            loaded_plugins.push(plugin_name);
-                            loaded = true;
-                            break;
-                        }
-                        Err(e) => {
-                            last_error = Some(e);
-                            if attempt < max_retries && last_error.as_ref().unwrap().is_transient() {
-                                warn!("Transient error loading plugin from {:?} (attempt {}): {}. Retrying in {:?}...", 
-                                      path, attempt, last_error.as_ref().unwrap(), retry_delay);
-                                std::thread::sleep(retry_delay);
-                            } else {
-                                break;
-                            }
-                        }
-                    }
-                }
-                
-                if !loaded {
-                    if let Some(error) = last_error {
-                        error!("Failed to load plugin from {:?} after {} attempts: {}", path, max_retries, error);
-                        failed_plugins.push((path.clone(), error));
-                    }
+            match retry_load(&path, max_retries, retry_delay, || self.load_plugin_from_path(&path)) {
+                // A plugin replacing a same-named system plugin is reported once
+                Ok(plugin_name) if loaded_plugins.contains(&plugin_name) => {
+                    info!("Plugin '{}' from {:?} takes precedence over the system plugin", plugin_name, path);
                 }
+                Ok(plugin_name) => loaded_plugins.push(plugin_name),
+                Err(error) => failed_plugins.push((path, error)),
             }
         }
 
@@ -117,22 +215,114 @@ impl PluginRegistry {
                 error!("Plugin load failure: {:?} - {} (category: {})", 
                        path, error, error.category());
             }
+            
+            let mut load_failures = self.load_failures.write().unwrap();
+            load_failures.extend(failed_plugins.iter().map(|(path, error)| PluginLoadFailure {
+                path: path.clone(),
+                error: error.to_string(),
+                category: error.category().to_string(),
+            }));
         }
 
         info!("Successfully loaded {} plugins ({} failed)", loaded_plugins.len(), failed_plugins.len());
         Ok(loaded_plugins)
     }
 
+    /// Load plugins from explicit library paths, such as libraries outside the plugins directory
+    ///
+    /// Libraries already loaded from the same path are skipped. Failures are added to
+    /// the load failures of the last scan, so call this after scanning.
+    pub fn load_from_paths(&self, paths: &[PathBuf]) -> Vec<String> {
+        let mut loaded_plugins = Vec::new();
+        for path in paths {
+            if self.list_plugins().iter().any(|info| &info.path == path) {
+                debug!("Plugin library {:?} is already loaded, skipping preload", path);
+                continue;
+            }
+
+            let result = if path.is_file() {
+                self.load_plugin_from_path(path)
+            } else {
+                Err(PluginError::IoError {
+                    source: std::io::Error::new(std::io::ErrorKind::NotFound, format!("no plugin library at {:?}", path)),
+                })
+            };
+            match result {
+                Ok(plugin_name) => {
+                    info!("Preloaded plugin '{}' from {:?}", plugin_name, path);
+                    loaded_plugins.push(plugin_name);
+                }
+                Err(error) => {
+                    error!("Failed to preload plugin from {:?}: {} (category: {})", path, error, error.category());
+                    self.load_failures.write().unwrap().push(PluginLoadFailure {
+                        path: path.clone(),
+                        error: error.to_string(),
+                        category: error.category().to_string(),
+                    });
+                }
+            }
+        }
+        loaded_plugins
+    }
+
+    /// Collect candidate plugin libraries from a plugins directory
+    ///
+    /// Only the top level is read unless recursive scanning is enabled, in which
+    /// case subdirectories are walked up to the configured depth. Directories are
+    /// tracked by canonical path so symlink loops are visited at most once.
+    fn discover_plugin_libraries(&self, root: &Path) -> PluginResult<Vec<PathBuf>> {
+        let mut libraries = Vec::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![(root.to_path_buf(), 0usize)];
+
+        while let Some((dir, depth)) = pending.pop() {
+            let canonical = std::fs::canonicalize(&dir).unwrap_or_else(|_| dir.clone());
+            if !visited.insert(canonical) {
+                debug!("Skipping already visited directory: {:?}", dir);
+                continue;
+            }
+
+            let entries = match std::fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                // The top-level directory must be readable; nested ones are best effort
+                Err(e) if depth > 0 => {
+                    warn!("Failed to read plugin subdirectory {:?}: {}", dir, e);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            for entry in entries {
+                let path = entry?.path();
+
+                if self.is_plugin_library(&path) {
+                    libraries.push(path);
+                } else if self.recursive_scan && depth < self.max_scan_depth && path.is_dir() {
+                    pending.push((path, depth + 1));
+                }
+            }
+        }
+
+        libraries.sort();
+        Ok(libraries)
+    }
+
     /// Load a specific plugin from a file path
     pub fn load_plugin_from_path<P: AsRef<Path>>(&self, path: P) -> PluginResult<String> {
         let path = path.as_ref();
         info!("Loading plugin from: {:?}", path);
 
+        // Verify the library against its pinned digest before loading any code
+        self.verify_digest(path)?;
+
         // Load the dynamic library
         let library = unsafe {
             Library::new(path).map_err(|e| {
                 error!("Failed to load library {:?}: {}", path, e);
-                PluginError::LoadingFailed { source: e }
+                match describe_missing_dependency(path, &e.to_string()) {
+                    Some(message) => PluginError::RegistrationFailed { message },
+                    None => PluginError::LoadingFailed { source: e },
+                }
             })?
         };
 
@@ -156,7 +346,131 @@ impl PluginRegistry {
         }
 
         let plugin = unsafe { Box::from_raw(plugin_ptr) };
-        
+
+        self.insert_plugin(plugin, Some(library), path.to_path_buf())
+    }
+
+    /// Load the plugin libraries contained in a `.tar.gz` archive
+    ///
+    /// Libraries are extracted into a temporary directory owned by the registry and
+    /// removed when it is dropped. Entries without a plugin library extension are
+    /// never written to disk. Libraries that fail to load are recorded as load failures.
+    pub fn load_from_archive<P: AsRef<Path>>(&self, path: P) -> PluginResult<Vec<String>> {
+        let path = path.as_ref();
+        info!("Loading plugins from archive: {:?}", path);
+
+        let extract_dir = tempfile::Builder::new().prefix("dyn-plug-archive-").tempdir()?;
+        let libraries = Self::extract_plugin_libraries(path, extract_dir.path())?;
+
+        let mut loaded_plugins = Vec::new();
+        let mut failures = Vec::new();
+        for library in libraries {
+            match self.load_plugin_from_path(&library) {
+                Ok(plugin_name) => loaded_plugins.push(plugin_name),
+                Err(e) => {
+                    error!("Failed to load plugin {:?} from archive {:?}: {} (category: {})",
+                           library, path, e, e.category());
+                    failures.push(PluginLoadFailure {
+                        path: library,
+                        error: e.to_string(),
+                        category: e.category().to_string(),
+                    });
+                }
+            }
+        }
+
+        info!("Loaded {} plugins from archive {:?} ({} failed)", loaded_plugins.len(), path, failures.len());
+        self.load_failures.write().unwrap().extend(failures);
+        self.archive_dirs.lock().unwrap().push(extract_dir);
+        Ok(loaded_plugins)
+    }
+
+    /// Unpack the plugin libraries of a `.tar.gz` archive into `dest`
+    ///
+    /// Entries are flattened to their file name so nothing can be written outside `dest`.
+    fn extract_plugin_libraries(archive: &Path, dest: &Path) -> PluginResult<Vec<PathBuf>> {
+        let file = std::fs::File::open(archive)?;
+        let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(file));
+        let mut libraries = Vec::new();
+
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            // Libraries with holes are archived as GNU sparse entries, which are still regular files
+            let entry_type = entry.header().entry_type();
+            if !entry_type.is_file() && !entry_type.is_gnu_sparse() {
+                continue;
+            }
+
+            let entry_path = entry.path()?.into_owned();
+            let file_name = match entry_path.file_name() {
+                Some(file_name) => file_name.to_owned(),
+                None => continue,
+            };
+            let target = dest.join(file_name);
+
+            if !has_library_extension(&target) {
+                debug!("Skipping non-library archive entry: {:?}", entry_path);
+                continue;
+            }
+            if libraries.contains(&target) {
+                warn!("Skipping duplicate library in archive: {:?}", entry_path);
+                continue;
+            }
+
+            entry.unpack(&target)?;
+            libraries.push(target);
+        }
+
+        libraries.sort();
+        Ok(libraries)
+    }
+
+    /// Register an in-process plugin instance that is not backed by a dynamic library
+    ///
+    /// This is useful for embedding plugins statically into the host and for tests.
+    pub fn register_plugin(&self, plugin: Box<dyn Plugin>) -> PluginResult<String> {
+        info!("Registering in-process plugin: {}", plugin.name());
+        self.insert_plugin(plugin, None, PathBuf::new()).inspect_err(|error| {
+            self.load_failures.write().unwrap().push(PluginLoadFailure {
+                path: PathBuf::new(),
+                error: error.to_string(),
+                category: error.category().to_string(),
+            });
+        })
+    }
+
+    /// Store a plugin instance in the registry, replacing any plugin with the same name
+    fn insert_plugin(&self, plugin: Box<dyn Plugin>, library: Option<Library>, path: PathBuf) -> PluginResult<String> {
+        let plugin: Arc<dyn Plugin> = Arc::from(plugin);
+
+        if let Err(reason) = validate_plugin_name(plugin.name()) {
+            error!("Rejecting plugin {:?} from {:?}: {} (category: registration_failed)", plugin.name(), path, reason);
+            return Err(PluginError::registration_failed(format!(
+                "Invalid plugin name {:?}: {}", plugin.name(), reason
+            )));
+        }
+
+        if let Some(missing) = plugin
+            .required_host_features()
+            .into_iter()
+            .find(|feature| !HOST_FEATURES.contains(&feature.as_str()))
+        {
+            error!("Plugin {} requires unsupported host feature '{}' (category: registration_failed)",
+                   plugin.name(), missing);
+            return Err(PluginError::registration_failed(format!(
+                "Plugin '{}' requires host feature '{}', which this host does not provide",
+                plugin.name(), missing
+            )));
+        }
+
+        if let Err(e) = self.run_on_load(&plugin) {
+            if matches!(e, PluginError::TimeoutError { .. }) {
+                // The hook is still running code from the library, so keep it mapped
+                self.retired_libraries.lock().unwrap().extend(library);
+            }
+            return Err(e);
+        }
+
         // Extract plugin metadata
         let name = plugin.name().to_string();
         let version = plugin.version().to_string();
@@ -170,13 +484,13 @@ impl PluginRegistry {
             description,
             enabled: true, // Default to enabled
             loaded: true,
-            path: path.to_path_buf(),
+            path,
         };
 
         let loaded_plugin = LoadedPlugin {
             plugin,
             library,
-            info: plugin_info,
+            info: plugin_info.clone(),
         };
 
         // Store the plugin in the registry
@@ -189,15 +503,141 @@ impl PluginRegistry {
         }
 
         info!("Successfully registered plugin: {}", name);
+        self.notify_listeners(|listener| listener.on_loaded(&plugin_info));
         Ok(name)
     }
 
+    /// Run the plugin's `on_load` hook on a separate thread, bounded by the load timeout
+    fn run_on_load(&self, plugin: &Arc<dyn Plugin>) -> PluginResult<()> {
+        let name = plugin.name().to_string();
+        let (tx, rx) = mpsc::channel();
+        let hook_plugin = plugin.clone();
+
+        std::thread::Builder::new()
+            .name(format!("on-load-{}", name))
+            .spawn(move || {
+                let result = hook_plugin.on_load().map_err(|e| e.to_string());
+                // Release the plugin before reporting back, otherwise this thread could drop
+                // the last reference after the registry has already closed the library
+                drop(hook_plugin);
+                // The receiver is gone if the hook timed out
+                let _ = tx.send(result);
+            })?;
+
+        match rx.recv_timeout(self.load_timeout) {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(message)) => {
+                error!("Plugin '{}' on_load failed: {}", name, message);
+                Err(PluginError::registration_failed(format!("Plugin '{}' on_load failed: {}", name, message)))
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                error!("Plugin '{}' on_load did not complete within {:?}, skipping", name, self.load_timeout);
+                Err(PluginError::timeout_error(format!("on_load for plugin '{}'", name)))
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                error!("Plugin '{}' on_load panicked", name);
+                Err(PluginError::registration_failed(format!("Plugin '{}' on_load panicked", name)))
+            }
+        }
+    }
+
+    /// Check a library file against its configured SHA-256 digest, if one is pinned
+    fn verify_digest(&self, path: &Path) -> PluginResult<()> {
+        let file_name = match path.file_name().and_then(OsStr::to_str) {
+            Some(file_name) => file_name,
+            None => return Ok(()),
+        };
+
+        let expected = match self.expected_digests.get(file_name) {
+            Some(expected) => expected,
+            None => return Ok(()),
+        };
+
+        let bytes = std::fs::read(path)?;
+        let actual: String = Sha256::digest(&bytes)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        if !actual.eq_ignore_ascii_case(expected.trim()) {
+            error!("Digest mismatch for plugin library {:?}: expected {}, got {}", path, expected, actual);
+            return Err(PluginError::RegistrationFailed {
+                message: format!("Digest mismatch for {:?}: expected {}, got {}", path, expected, actual),
+            });
+        }
+
+        debug!("Digest verified for plugin library {:?}", path);
+        Ok(())
+    }
+
+    /// Get the plugin libraries that failed to load during the last scan
+    pub fn load_failures(&self) -> Vec<PluginLoadFailure> {
+        self.load_failures.read().unwrap().clone()
+    }
+
     /// Get plugin information by name
     pub fn get_plugin_info(&self, name: &str) -> Option<PluginInfo> {
         let plugins = self.plugins.read().unwrap();
         plugins.get(name).map(|p| p.info.clone())
     }
 
+    /// Get a plugin's example inputs by name
+    pub fn get_plugin_examples(&self, name: &str) -> Option<Vec<(String, String)>> {
+        let plugins = self.plugins.read().unwrap();
+        plugins.get(name).map(|p| p.plugin.examples())
+    }
+
+    /// Get the operations a plugin declares by name
+    pub fn get_plugin_capabilities(&self, name: &str) -> Option<Vec<String>> {
+        let plugins = self.plugins.read().unwrap();
+        plugins.get(name).map(|p| p.plugin.capabilities())
+    }
+
+    /// Get a plugin's input schema by name
+    ///
+    /// Returns `None` if the plugin does not exist and `Some(None)` if it publishes no schema.
+    pub fn get_plugin_schema(&self, name: &str) -> Option<Option<serde_json::Value>> {
+        let plugins = self.plugins.read().unwrap();
+        plugins.get(name).map(|p| p.plugin.input_schema())
+    }
+
+    /// Get a plugin's output schema by name
+    ///
+    /// Returns `None` if the plugin does not exist and `Some(None)` if it publishes no schema.
+    pub fn get_plugin_output_schema(&self, name: &str) -> Option<Option<serde_json::Value>> {
+        let plugins = self.plugins.read().unwrap();
+        plugins.get(name).map(|p| p.plugin.output_schema())
+    }
+
+    /// Notify a plugin that its settings changed
+    pub fn notify_config_changed(&self, name: &str, settings: &HashMap<String, serde_json::Value>) -> PluginResult<()> {
+        let plugin = {
+            let plugins = self.plugins.read().unwrap();
+            plugins
+                .get(name)
+                .map(|p| p.plugin.clone())
+                .ok_or_else(|| PluginError::NotFound { name: name.to_string() })?
+        };
+
+        debug!("Notifying plugin {} of changed settings", name);
+        plugin.on_config_changed(settings);
+        Ok(())
+    }
+
+    /// Have a plugin check its settings, returning the reason they were rejected
+    pub fn validate_plugin_config(&self, name: &str, settings: &HashMap<String, serde_json::Value>) -> PluginResult<Result<(), String>> {
+        let plugin = {
+            let plugins = self.plugins.read().unwrap();
+            plugins
+                .get(name)
+                .map(|p| p.plugin.clone())
+                .ok_or_else(|| PluginError::NotFound { name: name.to_string() })?
+        };
+
+        debug!("Validating settings of plugin {}", name);
+        call_plugin(name, || plugin.validate_config(settings))
+    }
+
     /// Get information for all plugins
     pub fn list_plugins(&self) -> Vec<PluginInfo> {
         let plugins = self.plugins.read().unwrap();
@@ -211,7 +651,20 @@ impl PluginRegistry {
     
     /// Execute a plugin by name with configurable retry logic
     pub fn execute_plugin_with_retry(&self, name: &str, input: &str, max_retries: u32, retry_delay: std::time::Duration) -> PluginResult<String> {
-        debug!("Executing plugin: {} with input length: {} (max_retries: {})", name, input.len(), max_retries);
+        self.execute_plugin_with_context(name, input, &ExecutionContext::new(name), max_retries, retry_delay)
+    }
+    
+    /// Execute a plugin by name with a host-provided execution context and configurable retry logic
+    pub fn execute_plugin_with_context(
+        &self,
+        name: &str,
+        input: &str,
+        ctx: &ExecutionContext,
+        max_retries: u32,
+        retry_delay: std::time::Duration,
+    ) -> PluginResult<String> {
+        let target = plugin_log_target(name);
+        log!(target: &target, Level::Debug, "Executing plugin: {} with input length: {} (max_retries: {})", name, input.len(), max_retries);
         
         let plugins = self.plugins.read().unwrap();
         let loaded_plugin = plugins.get(name).ok_or_else(|| {
@@ -222,35 +675,66 @@ impl PluginRegistry {
         })?;
 
         if !loaded_plugin.info.enabled {
-            warn!("Attempted to execute disabled plugin: {}", name);
+            log!(target: &target, Level::Warn, "Attempted to execute disabled plugin: {}", name);
             return Err(PluginError::PluginDisabled {
                 name: name.to_string(),
             });
         }
 
+        // Input without a readable operation keeps the requested retries
+        let max_retries = match crate::plugin_prelude::parse_operation(input) {
+            Ok((operation, _)) if !loaded_plugin.plugin.is_idempotent(&operation) => {
+                log!(target: &target, Level::Debug, "Operation '{}' of plugin {} is not idempotent, disabling retries", operation, name);
+                max_retries.min(1)
+            }
+            _ => max_retries,
+        };
+
         let mut last_error = None;
         
         for attempt in 1..=max_retries {
-            match loaded_plugin.plugin.execute(input) {
+            if ctx.inject_fault() {
+                log!(target: &target, Level::Warn, "Injected a transient fault into plugin {} on attempt {}/{} (category: fault_injection)",
+                     name, attempt, max_retries);
+                last_error = Some(PluginError::temporary_failure(format!("injected fault on attempt {}", attempt)));
+                if attempt < max_retries {
+                    std::thread::sleep(retry_delay);
+                }
+                continue;
+            }
+
+            let result = call_plugin(name, || loaded_plugin.plugin.execute_with_context(input, ctx))?;
+            match result {
                 Ok(result) => {
                     if attempt > 1 {
-                        info!("Plugin {} executed successfully on attempt {}, output length: {}", 
-                              name, attempt, result.len());
+                        log!(target: &target, Level::Info, "Plugin {} executed successfully on attempt {}, output length: {}",
+                             name, attempt, result.len());
                     } else {
-                        debug!("Plugin {} executed successfully, output length: {}", name, result.len());
+                        log!(target: &target, Level::Debug, "Plugin {} executed successfully, output length: {}", name, result.len());
                     }
                     return Ok(result);
                 }
                 Err(e) => {
-                    let plugin_error = PluginError::execution_failed(&e);
+                    // Typed plugin errors say for themselves whether a retry can help
+                    let (plugin_error, retryable) = match e.downcast_ref::<PluginExecutionError>() {
+                        Some(typed) => (
+                            PluginError::PluginFailure {
+                                code: typed.code.clone(),
+                                message: typed.message.clone(),
+                                retryable: typed.retryable,
+                            },
+                            typed.retryable,
+                        ),
+                        None => (PluginError::execution_failed(&e), self.is_execution_error_transient(e.as_ref())),
+                    };
                     last_error = Some(plugin_error);
                     
-                    if attempt < max_retries && self.is_execution_error_transient(&e) {
-                        warn!("Transient execution error for plugin {} (attempt {}): {}. Retrying in {:?}...", 
-                              name, attempt, e, retry_delay);
+                    if attempt < max_retries && retryable {
+                        log!(target: &target, Level::Warn, "Transient execution error for plugin {} (attempt {}): {}. Retrying in {:?}...",
+                             name, attempt, e, retry_delay);
                         std::thread::sleep(retry_delay);
                     } else {
-                        error!("Plugin {} execution failed on attempt {}: {}", name, attempt, e);
+                        log!(target: &target, Level::Error, "Plugin {} execution failed on attempt {}: {}", name, attempt, e);
                         break;
                     }
                 }
@@ -260,8 +744,100 @@ impl PluginRegistry {
         Err(last_error.unwrap())
     }
     
+    /// Execute a plugin over several inputs in one call, through its `batch_execute`
+    pub fn execute_plugin_batch(&self, name: &str, inputs: &[String]) -> PluginResult<Vec<Result<String, String>>> {
+        let target = plugin_log_target(name);
+        log!(target: &target, Level::Debug, "Executing plugin: {} over a batch of {} inputs", name, inputs.len());
+
+        let plugins = self.plugins.read().unwrap();
+        let loaded_plugin = plugins.get(name).ok_or_else(|| {
+            error!("Plugin not found: {}", name);
+            PluginError::NotFound {
+                name: name.to_string(),
+            }
+        })?;
+
+        if !loaded_plugin.info.enabled {
+            log!(target: &target, Level::Warn, "Attempted to execute disabled plugin: {}", name);
+            return Err(PluginError::PluginDisabled {
+                name: name.to_string(),
+            });
+        }
+
+        let results = call_plugin(name, || loaded_plugin.plugin.batch_execute(inputs))?.map_err(|e| {
+            log!(target: &target, Level::Error, "Plugin {} batch execution failed: {}", name, e);
+            PluginError::execution_failed(&e)
+        })?;
+        if results.len() != inputs.len() {
+            log!(target: &target, Level::Error, "Plugin {} returned {} results for {} inputs", name, results.len(), inputs.len());
+            return Err(PluginError::execution_failed(format!(
+                "Plugin '{}' returned {} results for a batch of {} inputs", name, results.len(), inputs.len()
+            )));
+        }
+        Ok(results)
+    }
+
+    /// Execute a plugin once through its `execute_multi`, returning its named outputs
+    pub fn execute_plugin_multi(&self, name: &str, input: &str) -> PluginResult<HashMap<String, String>> {
+        let target = plugin_log_target(name);
+        log!(target: &target, Level::Debug, "Executing plugin: {} for named outputs with input length: {}", name, input.len());
+
+        let plugins = self.plugins.read().unwrap();
+        let loaded_plugin = plugins.get(name).ok_or_else(|| {
+            error!("Plugin not found: {}", name);
+            PluginError::NotFound {
+                name: name.to_string(),
+            }
+        })?;
+
+        if !loaded_plugin.info.enabled {
+            log!(target: &target, Level::Warn, "Attempted to execute disabled plugin: {}", name);
+            return Err(PluginError::PluginDisabled {
+                name: name.to_string(),
+            });
+        }
+
+        call_plugin(name, || loaded_plugin.plugin.execute_multi(input))?.map_err(|e| {
+            log!(target: &target, Level::Error, "Plugin {} multi-output execution failed: {}", name, e);
+            PluginError::execution_failed(&e)
+        })
+    }
+
+    /// Call the plugin's custom route matching `method` and `path`
+    ///
+    /// Returns `Ok(None)` when the plugin declares no such route.
+    pub fn execute_plugin_route(&self, name: &str, method: &str, path: &str, input: &str) -> PluginResult<Option<String>> {
+        let target = plugin_log_target(name);
+        log!(target: &target, Level::Debug, "Calling custom route {} {} of plugin: {}", method, path, name);
+
+        let plugins = self.plugins.read().unwrap();
+        let loaded_plugin = plugins.get(name).ok_or_else(|| {
+            error!("Plugin not found: {}", name);
+            PluginError::NotFound {
+                name: name.to_string(),
+            }
+        })?;
+
+        if !loaded_plugin.info.enabled {
+            log!(target: &target, Level::Warn, "Attempted to call a route of disabled plugin: {}", name);
+            return Err(PluginError::PluginDisabled {
+                name: name.to_string(),
+            });
+        }
+
+        call_plugin(name, || {
+            let routes = loaded_plugin.plugin.routes();
+            let route = routes.iter().find(|route| route.matches(method, path))?;
+            Some((route.handler)(input).map_err(|e| {
+                log!(target: &target, Level::Error, "Plugin {} route {} {} failed: {}", name, method, path, e);
+                PluginError::execution_failed(&e)
+            }))
+        })?
+        .transpose()
+    }
+
     /// Check if a plugin execution error is transient and worth retrying
-    fn is_execution_error_transient(&self, error: &Box<dyn std::error::Error>) -> bool {
+    fn is_execution_error_transient(&self, error: &dyn std::error::Error) -> bool {
         let error_str = error.to_string().to_lowercase();
         
         // Common transient execution errors
@@ -285,25 +861,68 @@ impl PluginRegistry {
             }
         })?;
 
+        let was_enabled = loaded_plugin.info.enabled;
         loaded_plugin.info.enabled = true;
+        drop(plugins);
+
+        if !was_enabled {
+            self.notify_listeners(|listener| listener.on_enabled(name));
+        }
         info!("Plugin {} enabled successfully", name);
         Ok(())
     }
 
     /// Disable a plugin
+    ///
+    /// A plugin that was enabled has its `reset` hook invoked once disabled.
     pub fn disable_plugin(&self, name: &str) -> PluginResult<()> {
         info!("Disabling plugin: {}", name);
         
-        let mut plugins = self.plugins.write().unwrap();
-        let loaded_plugin = plugins.get_mut(name).ok_or_else(|| {
-            error!("Cannot disable plugin, not found: {}", name);
+        let plugin_to_reset = {
+            let mut plugins = self.plugins.write().unwrap();
+            let loaded_plugin = plugins.get_mut(name).ok_or_else(|| {
+                error!("Cannot disable plugin, not found: {}", name);
+                PluginError::NotFound {
+                    name: name.to_string(),
+                }
+            })?;
+
+            let was_enabled = loaded_plugin.info.enabled;
+            loaded_plugin.info.enabled = false;
+            was_enabled.then(|| loaded_plugin.plugin.clone())
+        };
+
+        // Run the hook outside the registry lock so it may take its time
+        if let Some(plugin) = plugin_to_reset {
+            if let Err(e) = plugin.reset() {
+                warn!("Plugin {} reset hook failed: {}", name, e);
+            }
+            self.notify_listeners(|listener| listener.on_disabled(name));
+        }
+
+        info!("Plugin {} disabled successfully", name);
+        Ok(())
+    }
+
+    /// Remove a plugin from the registry
+    ///
+    /// The plugin's library stays mapped until the registry is dropped: in-flight
+    /// executions and thread-local destructors may still run code from it.
+    pub fn unload_plugin(&self, name: &str) -> PluginResult<()> {
+        info!("Unloading plugin: {}", name);
+
+        let loaded_plugin = self.plugins.write().unwrap().remove(name).ok_or_else(|| {
+            error!("Cannot unload plugin, not found: {}", name);
             PluginError::NotFound {
                 name: name.to_string(),
             }
         })?;
+        let LoadedPlugin { plugin, library, .. } = loaded_plugin;
+        drop(plugin);
+        self.retired_libraries.lock().unwrap().extend(library);
 
-        loaded_plugin.info.enabled = false;
-        info!("Plugin {} disabled successfully", name);
+        self.notify_listeners(|listener| listener.on_unloaded(name));
+        info!("Plugin {} unloaded successfully", name);
         Ok(())
     }
 
@@ -321,26 +940,158 @@ impl PluginRegistry {
 
     /// Check if a file is a potential plugin library based on its extension
     fn is_plugin_library(&self, path: &Path) -> bool {
-        if !path.is_file() {
-            return false;
+        path.is_file() && has_library_extension(path)
+    }
+}
+
+/// Call into plugin code, turning a panic into an `ExecutionFailed` error
+///
+/// Panics are attributed to `name` by the hook from `panic_hook::install`.
+fn call_plugin<T, F: FnOnce() -> T>(name: &str, call: F) -> PluginResult<T> {
+    let _scope = panic_hook::enter(name);
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(call)).map_err(|_| {
+        log!(target: &plugin_log_target(name), Level::Error, "Plugin {} panicked during execution", name);
+        PluginError::execution_failed(format!("Plugin '{}' panicked", name))
+    })
+}
+
+/// Run `load` until it succeeds, retrying only errors that may clear up on their own
+///
+/// Bad libraries (bad format, missing symbols) and load timeouts fail on the first attempt.
+fn retry_load<F>(path: &Path, max_retries: u32, retry_delay: Duration, mut load: F) -> PluginResult<String>
+where
+    F: FnMut() -> PluginResult<String>,
+{
+    let mut attempt = 1;
+    loop {
+        match load() {
+            Ok(plugin_name) => {
+                if attempt > 1 {
+                    info!("Successfully loaded plugin '{}' on attempt {}", plugin_name, attempt);
+                }
+                return Ok(plugin_name);
+            }
+            Err(e) => {
+                // A hung on_load hook would only hang again, so load timeouts are final
+                let retryable = e.is_transient() && !matches!(e, PluginError::TimeoutError { .. });
+                if attempt < max_retries && retryable {
+                    warn!("Transient error loading plugin from {:?} (attempt {}): {}. Retrying in {:?}...",
+                          path, attempt, e, retry_delay);
+                    std::thread::sleep(retry_delay);
+                    attempt += 1;
+                } else {
+                    error!("Failed to load plugin from {:?} after {} attempt(s): {}", path, attempt, e);
+                    return Err(e);
+                }
+            }
         }
+    }
+}
 
-        let extension = path.extension().and_then(OsStr::to_str);
-        match extension {
-            Some("so") => true,    // Linux
-            Some("dll") => true,   // Windows
-            Some("dylib") => true, // macOS
-            _ => false,
+/// Explain a loader error caused by a dependency of the library at `path`, if it is one
+///
+/// The dynamic linker reports a missing shared library or an unresolved symbol
+/// tersely; this names the culprit and points at `ldd` for tracking it down.
+fn describe_missing_dependency(path: &Path, error: &str) -> Option<String> {
+    if let Some((prefix, _)) = error.split_once(": cannot open shared object file") {
+        // The loader reports the library itself the same way when it does not exist
+        let missing = prefix.rsplit(": ").next().unwrap_or(prefix).trim();
+        if Path::new(missing) == path || Path::new(missing).file_name() == path.file_name() {
+            return None;
         }
+        return Some(format!(
+            "Plugin library {:?} depends on shared library '{}', which could not be found. \
+             Install it or add its directory to LD_LIBRARY_PATH; run `ldd {}` to list missing dependencies",
+            path, missing, path.display()
+        ));
+    }
+
+    if let Some((_, symbol)) = error.split_once("undefined symbol: ") {
+        let symbol = symbol.split_whitespace().next().unwrap_or(symbol);
+        return Some(format!(
+            "Plugin library {:?} references undefined symbol '{}', so a dependency is missing or has an incompatible version. \
+             Run `ldd -r {}` to list unresolved dependencies and symbols",
+            path, symbol, path.display()
+        ));
+    }
+
+    None
+}
+
+/// Check whether a path carries a dynamic library extension for any supported platform
+fn has_library_extension(path: &Path) -> bool {
+    let extension = path.extension().and_then(OsStr::to_str);
+    match extension {
+        Some("so") => true,    // Linux
+        Some("dll") => true,   // Windows
+        Some("dylib") => true, // macOS
+        _ => false,
     }
 }
 
+/// Check that a plugin name is non-empty, limited to `[A-Za-z0-9_-]`, and not reserved
+fn validate_plugin_name(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() {
+        return Err("name must not be empty");
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err("name may only contain letters, digits, '_' and '-'");
+    }
+    if RESERVED_PLUGIN_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(name)) {
+        return Err("name is reserved");
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    /// Records registry events as `"<event>:<plugin>"`
+    struct RecordingListener {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl RegistryListener for RecordingListener {
+        fn on_loaded(&self, info: &PluginInfo) {
+            self.events.lock().unwrap().push(format!("loaded:{}", info.name));
+        }
+        fn on_enabled(&self, name: &str) {
+            self.events.lock().unwrap().push(format!("enabled:{}", name));
+        }
+        fn on_disabled(&self, name: &str) {
+            self.events.lock().unwrap().push(format!("disabled:{}", name));
+        }
+        fn on_unloaded(&self, name: &str) {
+            self.events.lock().unwrap().push(format!("unloaded:{}", name));
+        }
+    }
+
+    #[test]
+    fn test_registry_listener_receives_lifecycle_events() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+        let events = Arc::new(Mutex::new(Vec::new()));
+        registry.subscribe(Box::new(RecordingListener { events: events.clone() }));
+
+        registry.register_plugin(Box::new(NamedPlugin { name: "watched" })).unwrap();
+        registry.disable_plugin("watched").unwrap();
+        // Repeating a change that is already in effect fires nothing
+        registry.disable_plugin("watched").unwrap();
+        registry.enable_plugin("watched").unwrap();
+        registry.enable_plugin("watched").unwrap();
+        registry.unload_plugin("watched").unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["loaded:watched", "disabled:watched", "enabled:watched", "unloaded:watched"]
+        );
+        assert!(!registry.has_plugin("watched"));
+        assert!(matches!(registry.unload_plugin("watched"), Err(PluginError::NotFound { .. })));
+    }
+
     #[test]
     fn test_registry_creation() {
         let temp_dir = TempDir::new().unwrap();
@@ -388,6 +1139,302 @@ mod tests {
         assert!(!registry.is_plugin_library(&txt_file));
     }
 
+    #[test]
+    fn test_recursive_discovery() {
+        let temp_dir = TempDir::new().unwrap();
+        let vendor_dir = temp_dir.path().join("vendor_a");
+        let deep_dir = vendor_dir.join("nested").join("deeper");
+        fs::create_dir_all(&deep_dir).unwrap();
+
+        fs::write(temp_dir.path().join("top.so"), "").unwrap();
+        fs::write(vendor_dir.join("vendor.so"), "").unwrap();
+        fs::write(deep_dir.join("deep.so"), "").unwrap();
+        fs::write(vendor_dir.join("readme.txt"), "").unwrap();
+
+        let file_names = |registry: &PluginRegistry| -> Vec<String> {
+            let mut names: Vec<String> = registry.discover_plugin_libraries(temp_dir.path()).unwrap()
+                .iter()
+                .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        // Default behavior only reads the top level
+        let registry = PluginRegistry::new(temp_dir.path());
+        assert_eq!(file_names(&registry), vec!["top.so"]);
+
+        // Recursive scan finds everything within the depth limit
+        let registry = PluginRegistry::new(temp_dir.path()).with_recursive_scan(true, 3);
+        assert_eq!(file_names(&registry), vec!["deep.so", "top.so", "vendor.so"]);
+
+        // A depth of 1 stops before vendor_a/nested
+        let registry = PluginRegistry::new(temp_dir.path()).with_recursive_scan(true, 1);
+        assert_eq!(file_names(&registry), vec!["top.so", "vendor.so"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_recursive_discovery_symlink_loop() {
+        let temp_dir = TempDir::new().unwrap();
+        let vendor_dir = temp_dir.path().join("vendor");
+        fs::create_dir_all(&vendor_dir).unwrap();
+        fs::write(vendor_dir.join("vendor.so"), "").unwrap();
+        std::os::unix::fs::symlink(temp_dir.path(), vendor_dir.join("loop")).unwrap();
+
+        let registry = PluginRegistry::new(temp_dir.path()).with_recursive_scan(true, 10);
+        let libraries = registry.discover_plugin_libraries(temp_dir.path()).unwrap();
+        assert_eq!(libraries.len(), 1);
+    }
+
+    #[test]
+    fn test_load_failures_recorded() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("broken.so"), "not a library").unwrap();
+
+        let registry = PluginRegistry::new(temp_dir.path());
+        let loaded = registry.scan_and_load().unwrap();
+        assert!(loaded.is_empty());
+
+        let failures = registry.load_failures();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].path.ends_with("broken.so"));
+        assert_eq!(failures[0].category, "loading_failed");
+
+        // A rescan after the library is removed clears the failure list
+        fs::remove_file(temp_dir.path().join("broken.so")).unwrap();
+        registry.scan_and_load().unwrap();
+        assert!(registry.load_failures().is_empty());
+    }
+
+    struct SlowLoadPlugin;
+
+    impl Plugin for SlowLoadPlugin {
+        fn name(&self) -> &str { "slow_load" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Never finishes loading in time" }
+        fn on_load(&self) -> Result<(), Box<dyn std::error::Error>> {
+            std::thread::sleep(Duration::from_secs(5));
+            Ok(())
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+
+    struct FailingLoadPlugin;
+
+    impl Plugin for FailingLoadPlugin {
+        fn name(&self) -> &str { "failing_load" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Fails to load" }
+        fn on_load(&self) -> Result<(), Box<dyn std::error::Error>> {
+            Err("missing resource".into())
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+
+    struct FeatureHungryPlugin {
+        features: Vec<&'static str>,
+    }
+
+    impl Plugin for FeatureHungryPlugin {
+        fn name(&self) -> &str { "feature_hungry" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Requires host features" }
+        fn required_host_features(&self) -> Vec<String> {
+            self.features.iter().map(|f| f.to_string()).collect()
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+
+    struct NamedPlugin {
+        name: &'static str,
+    }
+
+    impl Plugin for NamedPlugin {
+        fn name(&self) -> &str { self.name }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Plugin with a configurable name" }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+
+    /// Fails with a typed error until it has been called `failures` times
+    struct TypedErrorPlugin {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+        failures: usize,
+        retryable: bool,
+    }
+
+    impl Plugin for TypedErrorPlugin {
+        fn name(&self) -> &str { "typed_error" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Returns typed errors" }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if call <= self.failures {
+                // The message alone would never be classified as transient
+                let error = PluginExecutionError::new("upstream_unavailable", "upstream service refused the request");
+                return Err(Box::new(if self.retryable { error.retryable() } else { error }));
+            }
+            Ok(input.to_string())
+        }
+    }
+
+    /// Fails transiently on every call; its `charge` operation is not idempotent
+    struct PaymentPlugin {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Plugin for PaymentPlugin {
+        fn name(&self) -> &str { "payment" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Charges and looks up payments" }
+        fn is_idempotent(&self, operation: &str) -> bool {
+            operation != "charge"
+        }
+        fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err("gateway timeout".into())
+        }
+    }
+
+    struct ResetCountingPlugin {
+        resets: Arc<std::sync::atomic::AtomicUsize>,
+        fail: bool,
+    }
+
+    impl Plugin for ResetCountingPlugin {
+        fn name(&self) -> &str { "reset_counting" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Counts reset invocations" }
+        fn reset(&self) -> Result<(), Box<dyn std::error::Error>> {
+            self.resets.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.fail {
+                return Err("flush failed".into());
+            }
+            Ok(())
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+
+    #[test]
+    fn test_disable_invokes_reset_once() {
+        for fail in [false, true] {
+            let temp_dir = TempDir::new().unwrap();
+            let registry = PluginRegistry::new(temp_dir.path());
+            let resets = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            registry.register_plugin(Box::new(ResetCountingPlugin { resets: resets.clone(), fail })).unwrap();
+
+            registry.disable_plugin("reset_counting").unwrap();
+            assert_eq!(resets.load(std::sync::atomic::Ordering::SeqCst), 1);
+            assert!(!registry.get_plugin_info("reset_counting").unwrap().enabled);
+
+            // Disabling an already disabled plugin does not reset it again
+            registry.disable_plugin("reset_counting").unwrap();
+            assert_eq!(resets.load(std::sync::atomic::Ordering::SeqCst), 1);
+        }
+    }
+
+    #[test]
+    fn test_on_load_timeout_skips_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path()).with_load_timeout(Duration::from_millis(50));
+
+        let start = std::time::Instant::now();
+        let result = registry.register_plugin(Box::new(SlowLoadPlugin));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(matches!(result, Err(PluginError::TimeoutError { .. })));
+        assert!(!registry.has_plugin("slow_load"));
+
+        let failures = registry.load_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].category, "timeout_error");
+        assert!(failures[0].error.contains("slow_load"), "{}", failures[0].error);
+    }
+
+    #[test]
+    fn test_on_load_failure_rejects_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+
+        let result = registry.register_plugin(Box::new(FailingLoadPlugin));
+        assert!(matches!(result, Err(PluginError::RegistrationFailed { .. })));
+        assert_eq!(registry.plugin_count(), 0);
+    }
+
+    #[test]
+    fn test_invalid_plugin_name_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+
+        for name in ["", "   ", "has space", "dots.not.allowed", "ünïcode", "all", "NONE"] {
+            let result = registry.register_plugin(Box::new(NamedPlugin { name }));
+            match result {
+                Err(PluginError::RegistrationFailed { message }) => assert!(message.contains("Invalid plugin name")),
+                other => panic!("expected RegistrationFailed for {:?}, got {:?}", name, other),
+            }
+        }
+        assert_eq!(registry.plugin_count(), 0);
+
+        registry.register_plugin(Box::new(NamedPlugin { name: "valid-name_2" })).unwrap();
+        assert!(registry.has_plugin("valid-name_2"));
+    }
+
+    #[test]
+    fn test_unsupported_host_feature_rejects_plugin() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+
+        let result = registry.register_plugin(Box::new(FeatureHungryPlugin {
+            features: vec!["kv_store", "quantum_entanglement"],
+        }));
+        match result {
+            Err(PluginError::RegistrationFailed { message }) => assert!(message.contains("quantum_entanglement")),
+            other => panic!("expected RegistrationFailed, got {:?}", other),
+        }
+        assert_eq!(registry.plugin_count(), 0);
+
+        // Plugins that only need supported features load normally
+        registry.register_plugin(Box::new(FeatureHungryPlugin { features: vec!["kv_store"] })).unwrap();
+        assert!(registry.has_plugin("feature_hungry"));
+    }
+
+    #[test]
+    fn test_digest_verification() {
+        let temp_dir = TempDir::new().unwrap();
+        let lib_path = temp_dir.path().join("libmock.so");
+        fs::write(&lib_path, b"not a real library").unwrap();
+
+        let matching: String = Sha256::digest(b"not a real library")
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+
+        // A matching digest passes the check and proceeds to (failing) library loading
+        let mut digests = HashMap::new();
+        digests.insert("libmock.so".to_string(), matching.to_uppercase());
+        let registry = PluginRegistry::new(temp_dir.path()).with_expected_digests(digests);
+        let result = registry.load_plugin_from_path(&lib_path);
+        assert!(matches!(result, Err(PluginError::LoadingFailed { .. })));
+
+        // A mismatching digest is refused before the library is opened
+        let mut digests = HashMap::new();
+        digests.insert("libmock.so".to_string(), "0".repeat(64));
+        let registry = PluginRegistry::new(temp_dir.path()).with_expected_digests(digests);
+        let result = registry.load_plugin_from_path(&lib_path);
+        assert!(matches!(result, Err(PluginError::RegistrationFailed { .. })));
+        assert_eq!(registry.plugin_count(), 0);
+    }
+
     #[test]
     fn test_plugin_not_found() {
         let temp_dir = TempDir::new().unwrap();
@@ -396,4 +1443,157 @@ mod tests {
         let result = registry.execute_plugin("nonexistent", "test");
         assert!(matches!(result, Err(PluginError::NotFound { .. })));
     }
-}
\ No newline at end of file
+
+    /// Build a `.tar.gz` archive from `(path, contents)` entries
+    fn write_archive(path: &Path, entries: &[(&str, &[u8])]) {
+        let encoder = flate2::write::GzEncoder::new(fs::File::create(path).unwrap(), flate2::Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *contents).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_extract_plugin_libraries_skips_non_libraries() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("plugins.tar.gz");
+        write_archive(&archive, &[
+            ("libmock.so", b"not really a library"),
+            ("README.md", b"docs"),
+            ("nested/dir/libother.dylib", b"nested"),
+        ]);
+
+        let dest = temp_dir.path().join("extracted");
+        fs::create_dir_all(&dest).unwrap();
+        let libraries = PluginRegistry::extract_plugin_libraries(&archive, &dest).unwrap();
+
+        assert_eq!(libraries, vec![dest.join("libmock.so"), dest.join("libother.dylib")]);
+        assert!(!dest.join("README.md").exists());
+        assert_eq!(fs::read_dir(&dest).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_load_from_archive_records_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("plugins.tar.gz");
+        write_archive(&archive, &[("libmock.so", b"not really a library")]);
+
+        let registry = PluginRegistry::new(temp_dir.path());
+        let loaded = registry.load_from_archive(&archive).unwrap();
+
+        assert!(loaded.is_empty());
+        let failures = registry.load_failures();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].path.ends_with("libmock.so"));
+
+        // The extraction directory lives exactly as long as the registry
+        let extract_dir = failures[0].path.parent().unwrap().to_path_buf();
+        assert!(extract_dir.exists());
+        drop(registry);
+        assert!(!extract_dir.exists());
+    }
+
+    #[test]
+    fn test_retry_load_fails_fast_on_bad_library() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+        let garbage = temp_dir.path().join("libgarbage.so");
+        fs::write(&garbage, "not a shared library").unwrap();
+
+        let mut attempts = 0;
+        let result = retry_load(&garbage, 3, Duration::from_millis(10), || {
+            attempts += 1;
+            registry.load_plugin_from_path(&garbage)
+        });
+
+        assert!(matches!(result, Err(PluginError::LoadingFailed { .. })));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn test_retry_load_retries_transient_io_error() {
+        let mut attempts = 0;
+        let result = retry_load(Path::new("libbusy.so"), 3, Duration::from_millis(10), || {
+            attempts += 1;
+            if attempts == 1 {
+                Err(std::io::Error::new(std::io::ErrorKind::WouldBlock, "file temporarily locked").into())
+            } else {
+                Ok("busy".to_string())
+            }
+        });
+
+        assert_eq!(result.unwrap(), "busy");
+        assert_eq!(attempts, 2);
+    }
+
+    #[test]
+    fn test_describe_missing_dependency() {
+        let path = Path::new("/opt/plugins/libplugin_db.so");
+
+        let message = describe_missing_dependency(
+            path,
+            "libpq.so.5: cannot open shared object file: No such file or directory",
+        ).unwrap();
+        assert!(message.contains("'libpq.so.5'"), "{}", message);
+        assert!(message.contains("ldd /opt/plugins/libplugin_db.so"), "{}", message);
+
+        let message = describe_missing_dependency(
+            path,
+            "/opt/plugins/libplugin_db.so: undefined symbol: PQconnectdb",
+        ).unwrap();
+        assert!(message.contains("'PQconnectdb'"), "{}", message);
+        assert!(message.contains("ldd -r"), "{}", message);
+
+        // The library itself missing, or an unrelated loader error, is left as is
+        assert_eq!(describe_missing_dependency(
+            path,
+            "/opt/plugins/libplugin_db.so: cannot open shared object file: No such file or directory",
+        ), None);
+        assert_eq!(describe_missing_dependency(path, "/opt/plugins/libplugin_db.so: invalid ELF header"), None);
+    }
+
+    #[test]
+    fn test_typed_error_retryable_flag_drives_retries() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        registry.register_plugin(Box::new(TypedErrorPlugin { calls: calls.clone(), failures: 2, retryable: true })).unwrap();
+
+        let output = registry.execute_plugin_with_retry("typed_error", "ok", 3, Duration::from_millis(1)).unwrap();
+        assert_eq!(output, "ok");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_typed_error_code_propagates_without_retry() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        registry.register_plugin(Box::new(TypedErrorPlugin { calls: calls.clone(), failures: 5, retryable: false })).unwrap();
+
+        let error = registry.execute_plugin_with_retry("typed_error", "ok", 3, Duration::from_millis(1)).unwrap_err();
+        assert_eq!(error.code(), Some("upstream_unavailable"));
+        assert_eq!(error.category(), "execution_failed");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_non_idempotent_operation_is_not_retried() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = PluginRegistry::new(temp_dir.path());
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        registry.register_plugin(Box::new(PaymentPlugin { calls: calls.clone() })).unwrap();
+
+        registry.execute_plugin_with_retry("payment", r#"{"operation":"charge"}"#, 3, Duration::from_millis(1)).unwrap_err();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Idempotent operations still get the full retry budget
+        registry.execute_plugin_with_retry("payment", r#"{"operation":"lookup"}"#, 3, Duration::from_millis(1)).unwrap_err();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+}
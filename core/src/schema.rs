@@ -0,0 +1,133 @@
+//! Lightweight JSON Schema checks for plugin output
+//!
+//! Supports the subset of keywords plugins in this workspace publish: `type`,
+//! `enum`, `const`, `required`, `properties`, `additionalProperties` (as a
+//! boolean) and `items`. Other keywords are ignored, so a document is only
+//! rejected for a violation this module understands.
+
+use serde_json::Value;
+
+/// Check `value` against `schema`, returning the first violation found
+///
+/// Violations name the offending location as a JSON pointer, e.g.
+/// `/result: expected number, found string`.
+pub fn validate(value: &Value, schema: &Value) -> Result<(), String> {
+    check(value, schema, "")
+}
+
+fn check(value: &Value, schema: &Value, path: &str) -> Result<(), String> {
+    let location = if path.is_empty() { "/" } else { path };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| has_type(value, name)) {
+            return Err(format!("{}: expected {}, found {}", location, allowed.join(" or "), type_name(value)));
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            return Err(format!("{}: {} is not one of the allowed values", location, value));
+        }
+    }
+
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            return Err(format!("{}: expected {}, found {}", location, constant, value));
+        }
+    }
+
+    if let Value::Object(object) = value {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            if let Some(missing) = required.iter().filter_map(Value::as_str).find(|field| !object.contains_key(*field)) {
+                return Err(format!("{}: missing required field '{}'", location, missing));
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (field, field_value) in object {
+            match properties.and_then(|properties| properties.get(field)) {
+                Some(field_schema) => check(field_value, field_schema, &format!("{}/{}", path, field))?,
+                None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                    return Err(format!("{}: unexpected field '{}'", location, field));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            check(item, item_schema, &format!("{}/{}", path, index))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `value` is an instance of the JSON Schema type `name`
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some() || value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_accepts_matching_documents() {
+        let schema = json!({
+            "type": "object",
+            "required": ["operation", "result"],
+            "properties": {
+                "operation": { "enum": ["add", "sqrt"] },
+                "result": { "type": ["number", "null"] },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        });
+        assert_eq!(validate(&json!({"operation": "add", "result": 3, "tags": ["a"]}), &schema), Ok(()));
+        assert_eq!(validate(&json!({"operation": "sqrt", "result": null, "extra": true}), &schema), Ok(()));
+        assert_eq!(validate(&json!(2.0), &json!({"type": "integer"})), Ok(()));
+    }
+
+    #[test]
+    fn test_reports_violation_location() {
+        let schema = json!({
+            "type": "object",
+            "required": ["result"],
+            "additionalProperties": false,
+            "properties": {
+                "result": { "type": "number" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        });
+        for (value, expected) in [
+            (json!("text"), "/: expected object, found string"),
+            (json!({}), "/: missing required field 'result'"),
+            (json!({"result": "3"}), "/result: expected number, found string"),
+            (json!({"result": 3, "tags": ["a", 1]}), "/tags/1: expected string, found number"),
+            (json!({"result": 3, "other": 1}), "/: unexpected field 'other'"),
+        ] {
+            assert_eq!(validate(&value, &schema), Err(expected.to_string()));
+        }
+    }
+}
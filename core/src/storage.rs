@@ -0,0 +1,113 @@
+use crate::{PluginError, PluginResult};
+use log::{debug, info};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Persistent key-value storage owned by a single plugin
+///
+/// Values are kept in memory and written through to a JSON file on every
+/// `set`, so they survive restarts of the host. The host hands a store to
+/// plugins through the [`ExecutionContext`](crate::ExecutionContext).
+pub struct KvStore {
+    path: PathBuf,
+    values: Mutex<HashMap<String, String>>,
+}
+
+impl KvStore {
+    /// Open the store backed by the given file, loading existing values if present
+    pub fn open<P: AsRef<Path>>(path: P) -> PluginResult<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let values = if path.exists() {
+            debug!("Loading plugin state from: {:?}", path);
+            let content = fs::read_to_string(&path)?;
+            serde_json::from_str(&content)?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self {
+            path,
+            values: Mutex::new(values),
+        })
+    }
+
+    /// Get the value stored under a key
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.values.lock().unwrap().get(key).cloned()
+    }
+
+    /// Store a value under a key and persist the store
+    pub fn set(&self, key: &str, value: &str) -> PluginResult<()> {
+        let mut values = self.values.lock().unwrap();
+        values.insert(key.to_string(), value.to_string());
+        self.persist(&values)
+    }
+
+    /// Remove a key and persist the store, returning the previous value
+    pub fn remove(&self, key: &str) -> PluginResult<Option<String>> {
+        let mut values = self.values.lock().unwrap();
+        let previous = values.remove(key);
+        if previous.is_some() {
+            self.persist(&values)?;
+        }
+        Ok(previous)
+    }
+
+    /// Get the file backing this store
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn persist(&self, values: &HashMap<String, String>) -> PluginResult<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write to a sibling file first so a crash never leaves a truncated store
+        let content = serde_json::to_string_pretty(values)?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, &self.path).map_err(|e| {
+            PluginError::config_error(format!("Failed to persist plugin state to {:?}: {}", self.path, e))
+        })?;
+
+        info!("Plugin state saved to: {:?}", self.path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_kv_store_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("state").join("plugin.json");
+
+        let store = KvStore::open(&path).unwrap();
+        assert_eq!(store.get("counter"), None);
+        store.set("counter", "1").unwrap();
+        assert_eq!(store.get("counter"), Some("1".to_string()));
+        assert!(path.exists());
+
+        // A fresh store sees the persisted value
+        let store = KvStore::open(&path).unwrap();
+        assert_eq!(store.get("counter"), Some("1".to_string()));
+        assert_eq!(store.remove("counter").unwrap(), Some("1".to_string()));
+        assert_eq!(KvStore::open(&path).unwrap().get("counter"), None);
+    }
+
+    #[test]
+    fn test_kv_store_corrupt_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("plugin.json");
+        fs::write(&path, "not json").unwrap();
+
+        assert!(matches!(KvStore::open(&path), Err(PluginError::SerializationError { .. })));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::panic_hook::exec_thread_name;
+use crate::{ExecStatus, ExecutionContext, PluginError, PluginRegistry, PluginResult};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// File name of the helper binary that hosts plugins for subprocess isolation
+pub const HOST_BINARY: &str = "dyn-plug-host";
+
+/// How often a running helper process is checked for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A single execution sent to the helper process as a line of JSON on its stdin
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubprocessRequest {
+    pub input: String,
+    #[serde(default)]
+    pub settings: HashMap<String, serde_json::Value>,
+    /// Settings overridden for this execution only
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub settings_override: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub feature_flags: HashMap<String, bool>,
+}
+
+/// Outcome of an execution, written by the helper process as a line of JSON on its stdout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SubprocessResponse {
+    Success {
+        output: String,
+        /// Status the plugin reported for its output
+        #[serde(default, skip_serializing_if = "ExecStatus::is_ok")]
+        exec_status: ExecStatus,
+    },
+    Failure {
+        message: String,
+        /// Plugin-defined error code, when the plugin reported a typed error
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        code: Option<String>,
+        /// Whether the plugin marked its typed error as worth retrying
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        retryable: bool,
+    },
+}
+
+/// Runs plugin executions in a helper process, one process per execution
+///
+/// The helper loads the plugin library itself, so a plugin that crashes or hangs
+/// takes down only its own process. Executions have no persistent storage.
+#[derive(Debug, Clone)]
+pub struct SubprocessExecutor {
+    host: PathBuf,
+}
+
+impl SubprocessExecutor {
+    /// Create an executor that starts `host` as the helper binary
+    pub fn new<P: Into<PathBuf>>(host: P) -> Self {
+        Self { host: host.into() }
+    }
+
+    /// Create an executor for the configured helper, or `dyn-plug-host` next to the running executable
+    pub fn with_host_override(host: Option<&Path>) -> Self {
+        match host {
+            Some(host) => Self::new(host),
+            None => {
+                let dir = std::env::current_exe()
+                    .ok()
+                    .and_then(|exe| exe.parent().map(Path::to_path_buf))
+                    .unwrap_or_default();
+                Self::new(dir.join(format!("{}{}", HOST_BINARY, std::env::consts::EXE_SUFFIX)))
+            }
+        }
+    }
+
+    /// Start a helper process for `library` with piped stdin and stdout
+    fn spawn(&self, library: &Path) -> PluginResult<Child> {
+        debug!("Starting plugin host {:?} for {:?}", self.host, library);
+        Command::new(&self.host)
+            .arg(library)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| PluginError::execution_failed(format!("Failed to start plugin host {:?}: {}", self.host, e)))
+    }
+
+    /// Execute the plugin in `library` in a new helper process
+    ///
+    /// Returns the output and the status the plugin reported. When `timeout` elapses
+    /// first the helper is killed and a timeout error returned.
+    pub fn execute(&self, library: &Path, request: &SubprocessRequest, timeout: Option<Duration>) -> PluginResult<(String, ExecStatus)> {
+        let start_time = Instant::now();
+        let mut child = self.spawn(library)?;
+
+        let mut stdout = child.stdout.take().expect("helper stdout is piped");
+        let reader = std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            stdout.read_to_end(&mut buf).map(|_| buf)
+        });
+
+        // Written on its own thread so a large input cannot block past the timeout;
+        // a helper that exits before reading its input is reported through its exit status
+        let request = serde_json::to_vec(request)?;
+        let mut stdin = child.stdin.take().expect("helper stdin is piped");
+        std::thread::spawn(move || {
+            let _ = stdin.write_all(&request);
+        });
+
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if timeout.is_some_and(|timeout| start_time.elapsed() >= timeout) {
+                warn!("Plugin host for {:?} timed out after {:?}, killing it (category: timeout_error)", library, start_time.elapsed());
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(PluginError::timeout_error(format!("Subprocess execution of {:?}", library)));
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+
+        let stdout = reader
+            .join()
+            .map_err(|_| PluginError::execution_failed("Plugin host output reader panicked"))??;
+        if !status.success() {
+            return Err(PluginError::execution_failed(format!("Plugin host exited with {}", status)));
+        }
+
+        serde_json::from_slice::<SubprocessResponse>(&stdout)?.into_result()
+    }
+}
+
+impl SubprocessResponse {
+    /// The output and status of a successful execution, or the error it failed with
+    fn into_result(self) -> PluginResult<(String, ExecStatus)> {
+        match self {
+            SubprocessResponse::Success { output, exec_status } => Ok((output, exec_status)),
+            SubprocessResponse::Failure { message, code: Some(code), retryable } => {
+                Err(PluginError::PluginFailure { code, message, retryable })
+            }
+            SubprocessResponse::Failure { message, code: None, .. } => Err(PluginError::ExecutionFailed { message }),
+        }
+    }
+}
+
+/// A helper process kept running to serve executions one at a time
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    /// Response lines read from the helper's stdout by a background thread
+    responses: Receiver<std::io::Result<String>>,
+}
+
+impl Worker {
+    fn spawn(executor: &SubprocessExecutor, library: &Path) -> PluginResult<Self> {
+        let mut child = executor.spawn(library)?;
+        let stdin = child.stdin.take().expect("helper stdin is piped");
+        let stdout = child.stdout.take().expect("helper stdout is piped");
+
+        let (tx, responses) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self { child, stdin, responses })
+    }
+
+    /// Whether the helper process is still running
+    fn is_alive(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// Send one request and wait for its response
+    ///
+    /// An `Err` means the worker can no longer be used: it crashed, broke the
+    /// protocol or timed out.
+    fn execute(&mut self, request: &SubprocessRequest, timeout: Option<Duration>) -> PluginResult<SubprocessResponse> {
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+
+        // The request is written on its own thread so a helper that stops reading its
+        // input still times out; killing the helper then unblocks the writer
+        let response = std::thread::scope(|scope| {
+            let stdin = &mut self.stdin;
+            scope.spawn(move || {
+                let _ = stdin.write_all(&line).and_then(|()| stdin.flush());
+            });
+            let response = match timeout {
+                Some(timeout) => self.responses.recv_timeout(timeout),
+                None => self.responses.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            if response.is_err() {
+                let _ = self.child.kill();
+            }
+            response
+        });
+        match response {
+            Ok(line) => Ok(serde_json::from_str(&line?)?),
+            Err(RecvTimeoutError::Timeout) => Err(PluginError::timeout_error(format!("Pooled subprocess execution of pid {}", self.child.id()))),
+            Err(RecvTimeoutError::Disconnected) => {
+                let status = self.child.wait()?;
+                Err(PluginError::execution_failed(format!("Plugin host exited with {}", status)))
+            }
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Helper processes for one plugin library, kept running between executions
+///
+/// Each execution takes an idle process, replacing it first if it has exited,
+/// and returns it afterwards. Processes that fail or time out are discarded.
+pub struct WarmPool {
+    executor: SubprocessExecutor,
+    library: PathBuf,
+    size: usize,
+    idle: Mutex<Vec<Worker>>,
+}
+
+impl WarmPool {
+    /// Start `size` helper processes for the plugin in `library`
+    pub fn new<P: Into<PathBuf>>(executor: SubprocessExecutor, library: P, size: usize) -> PluginResult<Self> {
+        let library = library.into();
+        let idle = (0..size)
+            .map(|_| Worker::spawn(&executor, &library))
+            .collect::<PluginResult<Vec<_>>>()?;
+        debug!("Started {} warm plugin hosts for {:?}", size, library);
+        Ok(Self { executor, library, size, idle: Mutex::new(idle) })
+    }
+
+    /// Process ids of the idle helper processes
+    pub fn idle_pids(&self) -> Vec<u32> {
+        self.idle.lock().unwrap().iter().map(|worker| worker.child.id()).collect()
+    }
+
+    /// Execute a request on a warm helper process, starting one if none is idle
+    ///
+    /// When `timeout` elapses first the helper is killed and a timeout error returned.
+    pub fn execute(&self, request: &SubprocessRequest, timeout: Option<Duration>) -> PluginResult<(String, ExecStatus)> {
+        let mut worker = self.checkout()?;
+        match worker.execute(request, timeout) {
+            Ok(response) => {
+                let mut idle = self.idle.lock().unwrap();
+                if idle.len() < self.size {
+                    idle.push(worker);
+                }
+                drop(idle);
+                response.into_result()
+            }
+            Err(e) => {
+                warn!("Discarding plugin host {} for {:?}: {} (category: {})", worker.child.id(), self.library, e, e.category());
+                Err(e)
+            }
+        }
+    }
+
+    /// Take a live idle worker, replacing any that exited while idle
+    fn checkout(&self) -> PluginResult<Worker> {
+        while let Some(mut worker) = self.idle.lock().unwrap().pop() {
+            if worker.is_alive() {
+                return Ok(worker);
+            }
+            warn!("Plugin host {} for {:?} exited while idle, replacing it (category: subprocess_crash)", worker.child.id(), self.library);
+        }
+        Worker::spawn(&self.executor, &self.library)
+    }
+}
+
+/// Serve requests as the helper process: load `library`, then run each request line read
+/// from `input` and write its response line to `output` until `input` ends
+///
+/// Errors loading the plugin or reading a request are returned rather than written.
+pub fn serve<R: Read, W: Write>(library: &Path, input: R, mut output: W) -> PluginResult<()> {
+    let registry = PluginRegistry::new(library.parent().unwrap_or(Path::new(".")));
+    let name = registry.load_plugin_from_path(library)?;
+
+    for line in BufReader::new(input).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: SubprocessRequest = serde_json::from_str(&line)?;
+        let response = serve_request(&registry, &name, request);
+        serde_json::to_writer(&mut output, &response)?;
+        output.write_all(b"\n")?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// Run one request against the loaded plugin
+fn serve_request(registry: &PluginRegistry, name: &str, request: SubprocessRequest) -> SubprocessResponse {
+    let ctx = ExecutionContext::new(name)
+        .with_settings(request.settings)
+        .with_settings_override(request.settings_override)
+        .with_feature_flags(request.feature_flags);
+
+    // Run the plugin on a short-lived thread so thread-local destructors it registers
+    // run before the registry unloads the library
+    let result = std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .name(exec_thread_name(name))
+            .spawn_scoped(scope, || registry.execute_plugin_with_context(name, &request.input, &ctx, 1, Duration::ZERO))
+            .map_err(PluginError::from)?
+            .join()
+            .unwrap_or_else(|_| Err(PluginError::execution_failed("Plugin panicked")))
+    });
+
+    match result {
+        Ok(output) => SubprocessResponse::Success { output, exec_status: ctx.status() },
+        Err(PluginError::PluginFailure { code, message, retryable }) => {
+            SubprocessResponse::Failure { message, code: Some(code), retryable }
+        }
+        Err(PluginError::ExecutionFailed { message }) => SubprocessResponse::Failure { message, code: None, retryable: false },
+        Err(e) => SubprocessResponse::Failure { message: e.to_string(), code: None, retryable: false },
+    }
+}
@@ -1,4 +1,4 @@
-use dyn_plug_core::{ConfigManager, Config, ServerConfig, PluginConfig};
+use dyn_plug_core::{ConfigManager, Config, ServerConfig, PluginConfig, CONFIG_VERSION};
 use tempfile::TempDir;
 use std::fs;
 
@@ -278,4 +278,68 @@ fn test_config_structs_defaults() {
     let plugin_config = PluginConfig::default();
     assert!(plugin_config.enabled);
     assert!(plugin_config.settings.is_empty());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_concurrent_saves_leave_valid_config() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    ConfigManager::new(&config_path)?;
+
+    std::thread::scope(|scope| {
+        for thread in 0..16 {
+            let config_path = &config_path;
+            scope.spawn(move || {
+                let mut config_manager = ConfigManager::new(config_path).unwrap();
+                for i in 0..20 {
+                    let plugin = format!("plugin_{}", thread);
+                    config_manager.set_plugin_setting(&plugin, "iteration", serde_json::json!(i)).unwrap();
+                    if i % 2 == 0 {
+                        config_manager.disable_plugin(&plugin).unwrap();
+                    } else {
+                        config_manager.enable_plugin(&plugin).unwrap();
+                    }
+                }
+            });
+        }
+    });
+
+    let content = fs::read_to_string(&config_path)?;
+    let config: Config = serde_yaml::from_str(&content)?;
+    // Each manager only knows its own plugin, so the file holds the last writer's view
+    assert_eq!(config.plugins.len(), 1);
+    let last_writer = config.plugins.values().next().unwrap();
+    assert!(last_writer.enabled);
+    assert_eq!(last_writer.settings["iteration"], serde_json::json!(19));
+
+    // Only the config file and its lock file remain; no temp files are left behind
+    assert_eq!(fs::read_dir(temp_dir.path())?.count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_v0_config_is_migrated_in_memory() -> anyhow::Result<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    let content = format!(
+        "plugins_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\nplugins: {{}}\n",
+        temp_dir.path().join("plugins").display()
+    );
+    fs::write(&config_path, &content)?;
+
+    let mut config_manager = ConfigManager::new(&config_path)?;
+    assert_eq!(config_manager.config().config_version, CONFIG_VERSION);
+
+    // Loading leaves the file alone; the next save writes the current version
+    assert_eq!(fs::read_to_string(&config_path)?, content);
+    assert!(!temp_dir.path().join("config.yaml.lock").exists());
+    config_manager.save()?;
+    let mut on_disk: Config = serde_yaml::from_str(&fs::read_to_string(&config_path)?)?;
+    assert_eq!(on_disk.config_version, CONFIG_VERSION);
+    assert!(ConfigManager::migrate(&mut on_disk).is_empty());
+
+    Ok(())
+}
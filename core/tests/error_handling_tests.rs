@@ -172,6 +172,11 @@ fn test_transient_error_detection() {
     
     let resource_exhausted = PluginError::ResourceExhausted { resource: "test".to_string() };
     assert!(resource_exhausted.is_transient());
+    
+    // Typed plugin failures are transient only when the plugin said so
+    let plugin_failure = |retryable| PluginError::PluginFailure { code: "E".to_string(), message: "test".to_string(), retryable };
+    assert!(plugin_failure(true).is_transient());
+    assert!(!plugin_failure(false).is_transient());
 }
 
 #[test]
@@ -145,7 +145,7 @@ fn test_plugin_manager_with_real_plugins() -> PluginResult<()> {
     let manager = PluginManager::with_config_path(&config_path)?;
     
     // Test basic functionality even without plugins
-    let plugins = manager.list_plugins();
+    let _plugins = manager.list_plugins();
     // Could be 0 if no plugins built, any non-negative count is valid
     
     // Test plugin operations with non-existent plugin
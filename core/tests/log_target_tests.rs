@@ -0,0 +1,136 @@
+use dyn_plug_core::{plugin_log_target, ConfigManager, Plugin, PluginRegistry};
+use log::{LevelFilter, Log, Metadata, Record};
+use std::sync::{Mutex, Once};
+use tempfile::TempDir;
+
+/// Logger that keeps every record's target and message for inspection
+struct CapturingLogger {
+    records: Mutex<Vec<(String, String)>>,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records
+            .lock()
+            .unwrap()
+            .push((record.target().to_string(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CapturingLogger = CapturingLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+static INIT: Once = Once::new();
+
+/// Install the capturing logger; tests share it, so they filter records by content
+fn capture_logs() {
+    INIT.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Trace);
+    });
+}
+
+struct EchoPlugin;
+
+impl Plugin for EchoPlugin {
+    fn name(&self) -> &str { "echo" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Echoes its input" }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(input.to_string())
+    }
+}
+
+#[test]
+fn test_execution_logs_use_per_plugin_target() {
+    capture_logs();
+
+    let temp_dir = TempDir::new().unwrap();
+    let registry = PluginRegistry::new(temp_dir.path());
+    registry.register_plugin(Box::new(EchoPlugin)).unwrap();
+
+    registry.execute_plugin("echo", "hello").unwrap();
+
+    let target = plugin_log_target("echo");
+    assert_eq!(target, "dyn_plug::plugin::echo");
+
+    let records = LOGGER.records.lock().unwrap();
+    let messages: Vec<&String> = records
+        .iter()
+        .filter(|(t, _)| *t == target)
+        .map(|(_, m)| m)
+        .collect();
+    assert!(messages.iter().any(|m| m.starts_with("Executing plugin: echo")));
+    assert!(messages.iter().any(|m| m.contains("executed successfully")));
+}
+
+#[test]
+fn test_save_warns_when_config_uses_yaml_aliases() {
+    capture_logs();
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    std::fs::write(&config_path, r#"
+plugins_dir: plugins
+log_level: info
+defaults: &defaults
+  enabled: true
+  settings: {}
+server:
+  host: 127.0.0.1
+  port: 8080
+  enabled: true
+plugins:
+  plugin_a: *defaults
+  plugin_b: *defaults
+"#).unwrap();
+
+    let mut manager = ConfigManager::new(&config_path).unwrap();
+    assert!(manager.uses_yaml_aliases());
+    manager.save().unwrap();
+    assert!(!manager.uses_yaml_aliases());
+
+    let records = LOGGER.records.lock().unwrap();
+    let expected = format!("Config file {} uses YAML anchors or aliases", config_path.display());
+    assert!(records.iter().any(|(_, message)| message.starts_with(&expected)));
+}
+
+struct PanickingPlugin;
+
+impl Plugin for PanickingPlugin {
+    fn name(&self) -> &str { "panicker" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Panics on every execution" }
+
+    fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        panic!("plugin state corrupted");
+    }
+}
+
+#[test]
+fn test_plugin_panic_is_logged_with_plugin_name() {
+    capture_logs();
+    dyn_plug_core::panic_hook::install();
+
+    let temp_dir = TempDir::new().unwrap();
+    let registry = PluginRegistry::new(temp_dir.path());
+    registry.register_plugin(Box::new(PanickingPlugin)).unwrap();
+
+    let error = registry.execute_plugin("panicker", "x").unwrap_err();
+    assert!(error.to_string().contains("Plugin 'panicker' panicked"), "{}", error);
+
+    let records = LOGGER.records.lock().unwrap();
+    assert!(records.iter().any(|(_, message)| {
+        message.starts_with("Plugin 'panicker' panicked on thread") && message.contains("plugin state corrupted")
+    }));
+    // Debug logging is on, so the backtrace is logged too
+    assert!(records.iter().any(|(_, message)| message.starts_with("Backtrace of plugin 'panicker' panic")));
+}
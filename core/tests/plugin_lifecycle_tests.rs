@@ -1,6 +1,39 @@
-use dyn_plug_core::{PluginManager, PluginRegistry, PluginResult, PluginError};
+use dyn_plug_core::{ExecutionOptions, PluginManager, PluginRegistry, PluginResult, PluginError, SubprocessExecutor, SubprocessRequest, WarmPool};
 use tempfile::TempDir;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Build plugin_a and return the path of its library
+fn build_plugin_a() -> PathBuf {
+    let output = Command::new("cargo")
+        .args(["build", "-p", "plugin_a"])
+        .output()
+        .expect("Failed to build plugin_a");
+    assert!(output.status.success(), "Failed to build plugin_a: {}", String::from_utf8_lossy(&output.stderr));
+
+    let library_name = format!("{}plugin_a.{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_EXTENSION);
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/debug").join(library_name)
+}
+
+/// Build plugin_a and copy its library into `dir`, returning the copy's path
+fn copy_plugin_a(dir: &Path) -> PluginResult<PathBuf> {
+    let library_path = build_plugin_a();
+    fs::create_dir_all(dir)?;
+    let copy = dir.join(library_path.file_name().expect("library path has a file name"));
+    fs::copy(&library_path, &copy)?;
+    Ok(copy)
+}
+
+/// Write a config scanning `plugins_dir` with its state in `temp_dir`, followed by `extra`
+fn write_config(temp_dir: &TempDir, plugins_dir: &Path, extra: &str) -> PluginResult<PathBuf> {
+    let config_path = temp_dir.path().join("config.yaml");
+    fs::write(&config_path, format!(
+        "plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\n{}",
+        plugins_dir.display(), temp_dir.path().join("state").display(), extra
+    ))?;
+    Ok(config_path)
+}
 
 #[test]
 fn test_plugin_lifecycle_without_real_plugins() -> PluginResult<()> {
@@ -292,4 +325,221 @@ fn test_plugin_registry_with_retry_logic() -> PluginResult<()> {
     assert!(result.is_err());
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_load_plugin_from_archive() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let library_path = build_plugin_a();
+    let library_name = library_path.file_name().unwrap().to_string_lossy();
+
+    let temp_dir = TempDir::new().unwrap();
+    let archive_path = temp_dir.path().join("plugins.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(fs::File::create(&archive_path)?, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_path_with_name(&library_path, format!("dist/{}", library_name))?;
+    builder.into_inner()?.finish()?;
+
+    let registry = PluginRegistry::new(temp_dir.path().join("plugins"));
+    let loaded = registry.load_from_archive(&archive_path)?;
+
+    assert_eq!(loaded, vec!["plugin_a".to_string()]);
+    assert!(registry.load_failures().is_empty());
+    // Call into the plugin from a short-lived thread so any thread-local destructors it
+    // registers run before the registry unloads the library at the end of the test
+    let output = std::thread::scope(|scope| {
+        scope.spawn(|| registry.execute_plugin("plugin_a", r#"{"operation":"uppercase","text":"hi"}"#)).join().unwrap()
+    })?;
+    assert!(output.contains("HI"));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_subprocess_isolation_executes_and_kills_on_timeout() -> PluginResult<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let plugins_dir = temp_dir.path().join("plugins");
+    copy_plugin_a(&plugins_dir)?;
+
+    let write_host_config = |host: &Path| write_config(&temp_dir, &plugins_dir, &format!(
+        "isolation_host: \"{}\"\nplugins:\n  plugin_a:\n    enabled: true\n    settings: {{}}\n    isolation: subprocess\n",
+        host.display()
+    ));
+
+    let config_path = write_host_config(Path::new(env!("CARGO_BIN_EXE_dyn-plug-host")))?;
+    let manager = PluginManager::with_config_path(&config_path)?;
+    let result = manager.execute_plugin("plugin_a", r#"{"operation":"uppercase","text":"hi"}"#)?;
+    assert!(result.success, "subprocess execution failed: {}", result.output);
+    assert!(result.output.contains("HI"));
+
+    // A host that never answers is killed once the execution times out
+    let hanging_host = temp_dir.path().join("hanging-host");
+    fs::write(&hanging_host, "#!/bin/sh\nexec sleep 30\n")?;
+    fs::set_permissions(&hanging_host, fs::Permissions::from_mode(0o755))?;
+    write_host_config(&hanging_host)?;
+    let manager = PluginManager::with_config_path(&config_path)?;
+
+    let start = std::time::Instant::now();
+    let options = ExecutionOptions { timeout: Some(std::time::Duration::from_millis(200)), ..ExecutionOptions::no_retry() };
+    let result = manager.execute_plugin_with_options("plugin_a", "{}", options)?;
+    assert!(!result.success);
+    assert_eq!(result.error_category.as_deref(), Some("timeout_error"));
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    // Batches of isolated executions honor the plugin's configured timeout
+    write_config(&temp_dir, &plugins_dir, &format!(
+        "isolation_host: \"{}\"\nplugins:\n  plugin_a:\n    enabled: true\n    settings: {{}}\n    isolation: subprocess\n    timeout_ms: 200\n",
+        hanging_host.display()
+    ))?;
+    let manager = PluginManager::with_config_path(&config_path)?;
+
+    let start = std::time::Instant::now();
+    let results = manager.execute_plugin_batch("plugin_a", &["{}".to_string(), "{}".to_string()])?;
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| !result.success));
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_warm_pool_reuses_and_replaces_hosts() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let library_path = copy_plugin_a(temp_dir.path())?;
+
+    let executor = SubprocessExecutor::new(env!("CARGO_BIN_EXE_dyn-plug-host"));
+    let pool = WarmPool::new(executor, &library_path, 1)?;
+    let request = SubprocessRequest {
+        input: r#"{"operation":"uppercase","text":"hi"}"#.to_string(),
+        ..Default::default()
+    };
+
+    // Sequential executions are served by the same helper process
+    let pids = pool.idle_pids();
+    assert_eq!(pids.len(), 1);
+    for _ in 0..3 {
+        let (output, _) = pool.execute(&request, Some(std::time::Duration::from_secs(10)))?;
+        assert!(output.contains("HI"), "{}", output);
+        assert_eq!(pool.idle_pids(), pids);
+    }
+
+    // A helper that crashes while idle is replaced on the next execution
+    let status = Command::new("kill").args(["-9", &pids[0].to_string()]).status()?;
+    assert!(status.success());
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let (output, _) = pool.execute(&request, Some(std::time::Duration::from_secs(10)))?;
+    assert!(output.contains("HI"), "{}", output);
+    let replaced = pool.idle_pids();
+    assert_eq!(replaced.len(), 1);
+    assert_ne!(replaced, pids);
+
+    Ok(())
+}
+
+#[test]
+fn test_allowed_operations_restrict_plugin_a() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let plugins_dir = temp_dir.path().join("plugins");
+    copy_plugin_a(&plugins_dir)?;
+
+    let config_path = write_config(
+        &temp_dir,
+        &plugins_dir,
+        "plugins:\n  plugin_a:\n    enabled: true\n    settings: {}\n    allowed_operations: [uppercase]\n",
+    )?;
+    let manager = PluginManager::with_config_path(&config_path)?;
+
+    let result = manager.execute_plugin("plugin_a", r#"{"operation":"uppercase","text":"hi"}"#)?;
+    assert!(result.success, "allowed operation failed: {}", result.output);
+    assert!(result.output.contains("HI"));
+
+    // The plugin supports reverse, but the host rejects it before dispatch
+    let result = manager.execute_plugin("plugin_a", r#"{"operation":"reverse","text":"hi"}"#)?;
+    assert!(!result.success);
+    assert_eq!(result.error_category.as_deref(), Some("invalid_input"));
+    assert!(result.output.contains("operation 'reverse' is not allowed; allowed operations: uppercase"), "{}", result.output);
+
+    // Input whose operation cannot be read is rejected too
+    let result = manager.execute_plugin("plugin_a", "hi")?;
+    assert!(!result.success);
+    assert_eq!(result.error_category.as_deref(), Some("invalid_input"));
+
+    Ok(())
+}
+
+#[test]
+fn test_preload_plugin_outside_plugins_dir() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let elsewhere = temp_dir.path().join("elsewhere");
+    let library_path = copy_plugin_a(&elsewhere)?;
+    let missing_path = elsewhere.join("missing.so");
+
+    let config_path = write_config(&temp_dir, &temp_dir.path().join("plugins"), &format!(
+        "plugins: {{}}\npreload:\n  - path: \"{}\"\n  - path: \"{}\"\n",
+        library_path.display(), missing_path.display()
+    ))?;
+
+    let manager = PluginManager::with_config_path(&config_path)?;
+    let status = manager.get_plugin_status("plugin_a").expect("preloaded plugin is registered");
+    assert!(status.enabled);
+    assert_eq!(status.path, library_path);
+
+    let failures = manager.load_failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].path, missing_path);
+    assert_eq!(failures[0].category, "io_error");
+
+    Ok(())
+}
+
+#[test]
+fn test_system_plugins_dir_discovery_and_precedence() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let temp_dir = TempDir::new().unwrap();
+    let plugins_dir = temp_dir.path().join("plugins");
+    let system_dir = temp_dir.path().join("system");
+    fs::create_dir_all(&plugins_dir)?;
+    let system_library = copy_plugin_a(&system_dir)?;
+    let library_name = system_library.file_name().unwrap();
+
+    let write_system_config = |use_system_plugins: bool| write_config(&temp_dir, &plugins_dir, &format!(
+        "plugins: {{}}\nuse_system_plugins: {}\nsystem_plugins_dir: \"{}\"\n",
+        use_system_plugins, system_dir.display()
+    ));
+
+    // The system directory is ignored unless enabled
+    let config_path = write_system_config(false)?;
+    let manager = PluginManager::with_config_path(&config_path)?;
+    assert!(manager.get_plugin_status("plugin_a").is_none());
+    drop(manager);
+
+    write_system_config(true)?;
+    let manager = PluginManager::with_config_path(&config_path)?;
+    let status = manager.get_plugin_status("plugin_a").expect("system plugin is discovered");
+    assert_eq!(status.path, system_library);
+    assert!(manager.execute_plugin("plugin_a", r#"{"operation":"uppercase","text":"hi"}"#)?.success);
+    drop(manager);
+
+    // A plugin with the same name in plugins_dir wins
+    fs::copy(&system_library, plugins_dir.join(library_name))?;
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    let status = manager.get_plugin_status("plugin_a").expect("local plugin is loaded");
+    assert_eq!(status.path, plugins_dir.join(library_name));
+    assert_eq!(manager.plugin_count(), 1);
+    assert_eq!(manager.load_plugins()?, vec!["plugin_a".to_string()]);
+
+    Ok(())
+}
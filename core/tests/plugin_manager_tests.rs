@@ -1,7 +1,133 @@
-use dyn_plug_core::{PluginManager, PluginResult, PluginError, ExecutionOptions};
+use dyn_plug_core::{CircuitState, ExecutionContext, InputMiddleware, OutputMiddleware, Plugin, PluginManager, PluginResult, PluginError, ExecutionOptions, ExecutionPriority, ExecutionQueue};
 use tempfile::TempDir;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+/// Mock plugin that counts its executions in persistent storage
+struct CounterPlugin;
+
+impl Plugin for CounterPlugin {
+    fn name(&self) -> &str { "counter" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Counts executions in plugin storage" }
+
+    fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Err("counter requires host storage".into())
+    }
+
+    fn execute_with_context(&self, _input: &str, ctx: &ExecutionContext) -> Result<String, Box<dyn std::error::Error>> {
+        let kv = ctx.kv().ok_or("no storage available")?;
+        let count: u64 = kv.get("count").map(|v| v.parse()).transpose()?.unwrap_or(0) + 1;
+        kv.set("count", &count.to_string())?;
+        Ok(count.to_string())
+    }
+}
+
+struct EchoPlugin;
+
+impl Plugin for EchoPlugin {
+    fn name(&self) -> &str { "echo" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Echoes its input" }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(input.to_string())
+    }
+}
+
+struct UppercaseInput;
+
+impl InputMiddleware for UppercaseInput {
+    fn process(&self, _name: &str, input: String) -> Result<String, PluginError> {
+        Ok(input.to_uppercase())
+    }
+}
+
+struct RejectEmptyInput;
+
+impl InputMiddleware for RejectEmptyInput {
+    fn process(&self, _name: &str, input: String) -> Result<String, PluginError> {
+        if input.is_empty() {
+            return Err(PluginError::invalid_input("input must not be empty"));
+        }
+        Ok(input)
+    }
+}
+
+struct MarkerOutput;
+
+impl OutputMiddleware for MarkerOutput {
+    fn process(&self, name: &str, output: String) -> Result<String, PluginError> {
+        Ok(format!("[{}]{}[/{}]", name, output, name))
+    }
+}
+
+struct FailingOutput;
+
+impl OutputMiddleware for FailingOutput {
+    fn process(&self, _name: &str, output: String) -> Result<String, PluginError> {
+        if output.contains("secret") {
+            return Err(PluginError::execution_failed("output contains a secret"));
+        }
+        Ok(output)
+    }
+}
+
+struct SettingsRecordingPlugin {
+    name: &'static str,
+    received: Arc<Mutex<Vec<HashMap<String, serde_json::Value>>>>,
+}
+
+impl Plugin for SettingsRecordingPlugin {
+    fn name(&self) -> &str { self.name }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Records settings change notifications" }
+
+    fn on_config_changed(&self, settings: &HashMap<String, serde_json::Value>) {
+        self.received.lock().unwrap().push(settings.clone());
+    }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(input.to_string())
+    }
+}
+
+/// Write a config whose plugin and state directories live inside `temp_dir`
+fn write_isolated_config(temp_dir: &TempDir) -> std::path::PathBuf {
+    write_isolated_config_with(temp_dir, "plugins: {}\n")
+}
+
+/// Like `write_isolated_config`, with `extra` in place of the empty plugin list
+fn write_isolated_config_with(temp_dir: &TempDir, extra: &str) -> std::path::PathBuf {
+    let config_path = temp_dir.path().join("config.yaml");
+    std::fs::write(&config_path, format!("plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\n{}",
+        temp_dir.path().join("plugins").to_string_lossy(), temp_dir.path().join("state").to_string_lossy(), extra)).unwrap();
+    config_path
+}
+
+struct FlaggedPlugin;
+
+impl Plugin for FlaggedPlugin {
+    fn name(&self) -> &str { "flagged" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Gates an operation behind a feature flag" }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        self.execute_with_context(input, &ExecutionContext::new("flagged"))
+    }
+
+    fn execute_with_context(&self, input: &str, ctx: &ExecutionContext) -> Result<String, Box<dyn std::error::Error>> {
+        match input {
+            "stable_op" => Ok("stable".to_string()),
+            "experimental_op" if ctx.feature_enabled("experimental_op") => Ok("experimental".to_string()),
+            "experimental_op" => Err("operation 'experimental_op' is disabled by feature flag".into()),
+            _ => Err(format!("Unknown operation: {}", input).into()),
+        }
+    }
+}
+
 #[test]
 fn test_plugin_manager_basic_operations() -> PluginResult<()> {
     let _ = env_logger::builder().is_test(true).try_init();
@@ -161,4 +287,701 @@ fn test_plugin_manager_simple_execution() -> PluginResult<()> {
     assert!(matches!(result, Err(PluginError::NotFound { .. })));
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_plugin_storage_persists_across_executions() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    let state_dir = temp_dir.path().join("state");
+    std::fs::write(&config_path, format!("plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\nplugins: {{}}\n",
+        temp_dir.path().join("plugins").to_string_lossy(), state_dir.to_string_lossy())).unwrap();
+    
+    {
+        let mut manager = PluginManager::with_config_path(&config_path)?;
+        manager.register_plugin(Box::new(CounterPlugin))?;
+        
+        for expected in 1..=3 {
+            let result = manager.execute_plugin("counter", "")?;
+            assert!(result.success);
+            assert_eq!(result.output, expected.to_string());
+        }
+    }
+    
+    assert!(state_dir.join("counter.json").exists());
+    
+    // A new manager picks up the persisted state
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(CounterPlugin))?;
+    let result = manager.execute_plugin("counter", "")?;
+    assert_eq!(result.output, "4");
+    
+    Ok(())
+}
+
+#[test]
+fn test_isolated_execution_skips_bookkeeping() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(EchoPlugin))?;
+
+    assert_eq!(manager.execute_plugin_isolated("echo", "hello")?, "hello");
+    assert!(manager.recent_executions(None, None).is_empty());
+    assert!(manager.metrics().snapshot().plugins.is_empty());
+    assert!(matches!(manager.execute_plugin_isolated("missing", "x"), Err(PluginError::NotFound { .. })));
+
+    Ok(())
+}
+
+#[test]
+fn test_state_change_timestamp_updates_and_persists() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+
+    let disabled_at = {
+        let mut manager = PluginManager::with_config_path(&config_path)?;
+        manager.register_plugin(Box::new(EchoPlugin))?;
+        assert_eq!(manager.get_plugin_status("echo").unwrap().last_state_change, None);
+
+        manager.disable_plugin("echo")?;
+        let disabled_at = manager.get_plugin_status("echo").unwrap().last_state_change.expect("timestamp set on disable");
+        chrono::DateTime::parse_from_rfc3339(&disabled_at).unwrap();
+        disabled_at
+    };
+
+    // A new manager reads the timestamp back from the saved config
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(EchoPlugin))?;
+    assert_eq!(manager.get_plugin_status("echo").unwrap().last_state_change.as_ref(), Some(&disabled_at));
+
+    std::thread::sleep(Duration::from_millis(10));
+    manager.enable_plugin("echo")?;
+    let enabled_at = manager.list_plugins().into_iter().find(|p| p.name == "echo").unwrap().last_state_change.unwrap();
+    assert!(chrono::DateTime::parse_from_rfc3339(&enabled_at).unwrap() > chrono::DateTime::parse_from_rfc3339(&disabled_at).unwrap());
+
+    Ok(())
+}
+
+/// Mock plugin that cannot work without an `endpoint` setting
+struct EndpointPlugin;
+
+impl Plugin for EndpointPlugin {
+    fn name(&self) -> &str { "endpoint" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Requires an endpoint setting" }
+
+    fn validate_config(&self, settings: &HashMap<String, serde_json::Value>) -> Result<(), String> {
+        match settings.get("endpoint") {
+            Some(serde_json::Value::String(_)) => Ok(()),
+            _ => Err("missing required setting 'endpoint'".to_string()),
+        }
+    }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(input.to_string())
+    }
+}
+
+#[test]
+fn test_plugin_config_validated_at_load() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let write_config = |extra: &str| write_isolated_config_with(&temp_dir, extra);
+
+    // By default a plugin rejecting its settings stays disabled
+    let mut manager = PluginManager::with_config_path(write_config("plugins: {}\n"))?;
+    manager.register_plugin(Box::new(EndpointPlugin))?;
+    let status = manager.get_plugin_status("endpoint").unwrap();
+    assert!(!status.enabled);
+    assert!(status.config_enabled);
+    assert_eq!(status.config_error.as_deref(), Some("missing required setting 'endpoint'"));
+    assert!(matches!(manager.enable_plugin("endpoint"), Err(PluginError::ConfigError { .. })));
+    assert!(!manager.get_plugin_status("endpoint").unwrap().enabled);
+
+    // Settings changes re-run validation and enable or disable the plugin to match
+    manager.set_plugin_setting("endpoint", "endpoint", serde_json::json!("http://localhost"))?;
+    let status = manager.get_plugin_status("endpoint").unwrap();
+    assert!(status.enabled);
+    assert_eq!(status.config_error, None);
+
+    manager.set_plugin_setting("endpoint", "endpoint", serde_json::json!(42))?;
+    assert!(!manager.get_plugin_status("endpoint").unwrap().enabled);
+
+    let mut config = manager.config().clone();
+    config.plugins.get_mut("endpoint").unwrap().settings
+        .insert("endpoint".to_string(), serde_json::json!("http://localhost"));
+    manager.replace_config(config)?;
+    assert!(manager.get_plugin_status("endpoint").unwrap().enabled);
+
+    let mut config = manager.config().clone();
+    config.plugins.get_mut("endpoint").unwrap().settings.clear();
+    manager.replace_config(config)?;
+    assert!(!manager.get_plugin_status("endpoint").unwrap().enabled);
+
+    // The warn policy only flags it
+    let mut manager = PluginManager::with_config_path(write_config("invalid_plugin_config: warn\nplugins: {}\n"))?;
+    manager.register_plugin(Box::new(EndpointPlugin))?;
+    let status = manager.get_plugin_status("endpoint").unwrap();
+    assert!(status.enabled);
+    assert!(status.config_error.is_some());
+    assert!(manager.execute_plugin("endpoint", "hi")?.success);
+
+    let mut manager = PluginManager::with_config_path(write_config(
+        "plugins:\n  endpoint:\n    enabled: true\n    settings:\n      endpoint: http://localhost\n",
+    ))?;
+    manager.register_plugin(Box::new(EndpointPlugin))?;
+    let status = manager.get_plugin_status("endpoint").unwrap();
+    assert!(status.enabled);
+    assert_eq!(status.config_error, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_output_hash_tracks_output() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+
+    // Hashing is off by default
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(EchoPlugin))?;
+    assert_eq!(manager.execute_plugin("echo", "hello")?.output_hash, None);
+
+    let mut config = manager.config().clone();
+    config.hash_outputs = true;
+    manager.replace_config(config)?;
+
+    let first = manager.execute_plugin("echo", "hello")?.output_hash.unwrap();
+    let second = manager.execute_plugin("echo", "hello")?.output_hash.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first, "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824");
+
+    let changed = manager.execute_plugin("echo", "goodbye")?.output_hash.unwrap();
+    assert_ne!(changed, first);
+
+    Ok(())
+}
+
+#[test]
+fn test_feature_flags_gate_operations() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(FlaggedPlugin))?;
+    
+    // Flags default to off
+    assert!(manager.execute_plugin("flagged", "stable_op")?.success);
+    let result = manager.execute_plugin("flagged", "experimental_op")?;
+    assert!(!result.success);
+    assert!(result.output.contains("disabled by feature flag"));
+    
+    manager.set_feature_flag("flagged", "experimental_op", true)?;
+    let result = manager.execute_plugin("flagged", "experimental_op")?;
+    assert!(result.success);
+    assert_eq!(result.output, "experimental");
+    
+    manager.set_feature_flag("flagged", "experimental_op", false)?;
+    assert!(!manager.execute_plugin("flagged", "experimental_op")?.success);
+    
+    // Flags are persisted with the plugin configuration
+    let config_content = std::fs::read_to_string(&config_path).unwrap();
+    assert!(config_content.contains("feature_flags"));
+    
+    Ok(())
+}
+
+#[test]
+fn test_input_middleware_runs_before_plugin() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    
+    let mut manager = PluginManager::with_config_path(&config_path)?
+        .with_input_middleware(RejectEmptyInput)
+        .with_input_middleware(UppercaseInput);
+    manager.register_plugin(Box::new(EchoPlugin))?;
+    
+    let result = manager.execute_plugin("echo", "hello")?;
+    assert!(result.success);
+    assert_eq!(result.output, "HELLO");
+    
+    // A middleware error fails the execution without running the plugin
+    let result = manager.execute_plugin("echo", "")?;
+    assert!(!result.success);
+    assert_eq!(result.error_category.as_deref(), Some("invalid_input"));
+    
+    Ok(())
+}
+
+#[test]
+fn test_output_middleware_runs_after_plugin() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    
+    let mut manager = PluginManager::with_config_path(&config_path)?
+        .with_output_middleware(FailingOutput)
+        .with_output_middleware(MarkerOutput);
+    manager.register_plugin(Box::new(EchoPlugin))?;
+    
+    let result = manager.execute_plugin("echo", "hello")?;
+    assert!(result.success);
+    assert_eq!(result.output, "[echo]hello[/echo]");
+    
+    // A middleware error yields a failed result that still carries timing and history
+    let result = manager.execute_plugin("echo", "a secret")?;
+    assert!(!result.success);
+    assert!(result.output.contains("output contains a secret"));
+    assert_eq!(result.error_category.as_deref(), Some("execution_failed"));
+    let recent = manager.recent_executions(None, Some(1));
+    assert!(!recent[0].success);
+    assert_eq!(recent[0].duration_ms, result.duration_ms);
+    
+    Ok(())
+}
+
+#[test]
+fn test_reload_notifies_plugins_with_changed_settings() -> PluginResult<()> {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    let changed = Arc::new(Mutex::new(Vec::new()));
+    let unchanged = Arc::new(Mutex::new(Vec::new()));
+    manager.register_plugin(Box::new(SettingsRecordingPlugin { name: "changed", received: changed.clone() }))?;
+    manager.register_plugin(Box::new(SettingsRecordingPlugin { name: "unchanged", received: unchanged.clone() }))?;
+    manager.set_plugin_setting("unchanged", "mode", serde_json::json!("fast"))?;
+    
+    // Change one plugin's settings externally, leaving the other untouched
+    let content = std::fs::read_to_string(&config_path).unwrap();
+    let mut config: serde_yaml::Value = serde_yaml::from_str(&content).unwrap();
+    config["plugins"]["changed"] = serde_yaml::from_str("enabled: true\nsettings:\n  threshold: 5\n").unwrap();
+    std::fs::write(&config_path, serde_yaml::to_string(&config).unwrap()).unwrap();
+    
+    manager.reload_config()?;
+    
+    let received = changed.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].get("threshold"), Some(&serde_json::json!(5)));
+    assert!(unchanged.lock().unwrap().is_empty());
+    
+    Ok(())
+}
+
+/// Mock plugin whose health can be toggled, counting how often it is called
+struct FlakyPlugin {
+    healthy: Arc<AtomicBool>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl Plugin for FlakyPlugin {
+    fn name(&self) -> &str { "flaky" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Fails until marked healthy" }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.healthy.load(Ordering::SeqCst) {
+            Ok(input.to_string())
+        } else {
+            Err("backend unreachable".into())
+        }
+    }
+}
+
+#[test]
+fn test_circuit_breaker_fails_fast_and_recovers() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("circuit_breaker:\n  failure_threshold: 2\n  window_ms: 60000\n  cooldown_ms: 100\n");
+    std::fs::write(&config_path, config).unwrap();
+
+    let healthy = Arc::new(AtomicBool::new(false));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(FlakyPlugin { healthy: healthy.clone(), calls: calls.clone() }))?;
+
+    for _ in 0..2 {
+        assert!(!manager.execute_plugin("flaky", "x")?.success);
+    }
+    assert_eq!(manager.circuit_state("flaky"), CircuitState::Open);
+
+    // While open, executions fail fast without reaching the plugin
+    let calls_before = calls.load(Ordering::SeqCst);
+    match manager.execute_plugin("flaky", "x") {
+        Err(PluginError::ResourceExhausted { resource }) => assert_eq!(resource, "circuit_open"),
+        other => panic!("expected circuit_open, got {:?}", other.map(|r| r.output)),
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), calls_before);
+
+    // After the cooldown a successful probe closes the circuit
+    std::thread::sleep(Duration::from_millis(150));
+    healthy.store(true, Ordering::SeqCst);
+    assert!(manager.execute_plugin("flaky", "x")?.success);
+    assert_eq!(manager.circuit_state("flaky"), CircuitState::Closed);
+
+    Ok(())
+}
+
+#[test]
+fn test_fault_injection_fails_transiently_with_retries() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("fault_injection:\n  probability: 1.0\n");
+    std::fs::write(&config_path, config).unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(FlakyPlugin { healthy: Arc::new(AtomicBool::new(true)), calls: calls.clone() }))?;
+
+    let options = ExecutionOptions {
+        max_retries: 3,
+        retry_delay: Duration::from_millis(50),
+        ..Default::default()
+    };
+    let start = std::time::Instant::now();
+    let result = manager.execute_plugin_with_options("flaky", "x", options)?;
+
+    // Every attempt was failed before the plugin ran, waiting between retries
+    assert!(!result.success);
+    assert_eq!(result.error_category.as_deref(), Some("temporary_failure"));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    Ok(())
+}
+
+/// Mock plugin that takes a while to answer
+struct SlowPlugin;
+
+impl Plugin for SlowPlugin {
+    fn name(&self) -> &str { "slow" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Sleeps before echoing its input" }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        std::thread::sleep(Duration::from_millis(200));
+        Ok(input.to_string())
+    }
+}
+
+#[test]
+fn test_per_plugin_timeout_applies_to_default_execution() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    let config = std::fs::read_to_string(&config_path).unwrap()
+        .replace("plugins: {}", "plugins:\n  slow:\n    enabled: true\n    settings: {}\n    timeout_ms: 50");
+    std::fs::write(&config_path, config).unwrap();
+
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(SlowPlugin))?;
+
+    let result = manager.execute_plugin("slow", "x")?;
+    assert!(!result.success);
+    assert_eq!(result.error_category.as_deref(), Some("timeout_error"));
+
+    // Explicit options still take precedence over the plugin's timeout
+    let result = manager.execute_plugin_with_options("slow", "x", ExecutionOptions::default())?;
+    assert!(result.success);
+    assert_eq!(result.output, "x");
+
+    Ok(())
+}
+
+/// Mock plugin whose output breaks its declared output schema for some inputs
+struct SchemaBreakingPlugin;
+
+impl Plugin for SchemaBreakingPlugin {
+    fn name(&self) -> &str { "schema_breaking" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Returns a string result when asked to misbehave" }
+
+    fn output_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "type": "object",
+            "required": ["result"],
+            "properties": { "result": { "type": "number" } }
+        }))
+    }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        match input {
+            "misbehave" => Ok(r#"{"result": "three"}"#.to_string()),
+            _ => Ok(r#"{"result": 3}"#.to_string()),
+        }
+    }
+}
+
+#[test]
+fn test_strict_output_validation_rejects_schema_violations() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(SchemaBreakingPlugin))?;
+
+    // Outputs are not checked by default
+    assert!(manager.execute_plugin("schema_breaking", "misbehave")?.success);
+
+    let mut config = std::fs::read_to_string(&config_path).unwrap();
+    config.push_str("strict_output_validation: true\n");
+    std::fs::write(&config_path, config).unwrap();
+    manager.reload_config()?;
+
+    assert!(manager.execute_plugin("schema_breaking", "behave")?.success);
+    let result = manager.execute_plugin("schema_breaking", "misbehave")?;
+    assert!(!result.success);
+    assert_eq!(result.error_category.as_deref(), Some("execution_failed"));
+    assert!(result.output.contains("/result: expected number, found string"), "{}", result.output);
+
+    Ok(())
+}
+
+#[test]
+fn test_execute_plugin_versioned() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = write_isolated_config(&temp_dir);
+    
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(EchoPlugin))?;
+    
+    let result = manager.execute_plugin_versioned("echo", "^1.0", "pinned")?;
+    assert!(result.success);
+    assert_eq!(result.output, "pinned");
+    
+    match manager.execute_plugin_versioned("echo", "^0.1", "pinned") {
+        Err(PluginError::VersionMismatch { name, required, found }) => {
+            assert_eq!(name, "echo");
+            assert_eq!(required, "^0.1");
+            assert_eq!(found, "1.0.0");
+        }
+        other => panic!("expected VersionMismatch, got {:?}", other),
+    }
+    
+    assert!(matches!(
+        manager.execute_plugin_versioned("echo", "not a version", "pinned"),
+        Err(PluginError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        manager.execute_plugin_versioned("missing", "^1", "pinned"),
+        Err(PluginError::NotFound { .. })
+    ));
+    
+    Ok(())
+}
+
+/// Mock plugin that greets with its `greeting` setting
+struct GreetingPlugin;
+
+impl Plugin for GreetingPlugin {
+    fn name(&self) -> &str { "greeter" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Greets using a configured greeting" }
+    
+    fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Err("requires an execution context".into())
+    }
+    
+    fn execute_with_context(&self, input: &str, ctx: &ExecutionContext) -> Result<String, Box<dyn std::error::Error>> {
+        let greeting = ctx.setting("greeting").and_then(|v| v.as_str()).unwrap_or("hello");
+        Ok(format!("{} {}", greeting, input))
+    }
+}
+
+#[test]
+fn test_settings_override_applies_to_single_execution() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    std::fs::write(&config_path, format!(
+        "plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\nplugins:\n  greeter:\n    enabled: true\n    settings:\n      greeting: hi\n    overridable_settings: [greeting]\n",
+        temp_dir.path().join("plugins").to_string_lossy(), temp_dir.path().join("state").to_string_lossy())).unwrap();
+    let config_before = std::fs::read_to_string(&config_path).unwrap();
+    
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(GreetingPlugin))?;
+    
+    let options = ExecutionOptions::default()
+        .with_settings_override(HashMap::from([("greeting".to_string(), serde_json::json!("howdy"))]));
+    let result = manager.execute_plugin_with_options("greeter", "world", options)?;
+    assert_eq!(result.output, "howdy world");
+    
+    // The configured setting is back for the next execution and was never persisted
+    assert_eq!(manager.execute_plugin("greeter", "world")?.output, "hi world");
+    assert_eq!(manager.config().plugins["greeter"].settings["greeting"], "hi");
+    assert_eq!(std::fs::read_to_string(&config_path).unwrap(), config_before);
+    
+    // Settings not listed in overridable_settings are refused before the plugin runs
+    let options = ExecutionOptions::default()
+        .with_settings_override(HashMap::from([("api_key".to_string(), serde_json::json!("stolen"))]));
+    match manager.execute_plugin_with_options("greeter", "world", options) {
+        Err(PluginError::InvalidInput { message }) => assert!(message.contains("api_key"), "{}", message),
+        other => panic!("expected InvalidInput, got {:?}", other.map(|r| r.output)),
+    }
+    
+    Ok(())
+}
+
+/// Mock plugin that allocates and keeps memory on every execution
+struct AllocatingPlugin {
+    retained: Mutex<Vec<Vec<u8>>>,
+}
+
+impl Plugin for AllocatingPlugin {
+    fn name(&self) -> &str { "allocating" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Retains a 64 MiB buffer per execution" }
+
+    fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        // Filled with non-zero bytes so every page is resident
+        self.retained.lock().unwrap().push(vec![1u8; 64 * 1024 * 1024]);
+        Ok("allocated".to_string())
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_track_memory_reports_allocation() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    std::fs::write(&config_path, format!(
+        "plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\ntrack_memory: true\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\nplugins: {{}}\n",
+        temp_dir.path().join("plugins").to_string_lossy(), temp_dir.path().join("state").to_string_lossy())).unwrap();
+
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(AllocatingPlugin { retained: Mutex::new(Vec::new()) }))?;
+
+    let result = manager.execute_plugin("allocating", "")?;
+    assert!(result.success);
+    let delta = result.approx_memory_kb.expect("memory is tracked on Linux");
+    assert!(delta > 32 * 1024, "expected a large positive delta, got {} KiB", delta);
+
+    // Nothing is recorded unless tracking is configured
+    let untracked_dir = TempDir::new().unwrap();
+    let mut manager = PluginManager::with_config_path(write_isolated_config(&untracked_dir))?;
+    manager.register_plugin(Box::new(EchoPlugin))?;
+    assert!(manager.execute_plugin("echo", "hi")?.approx_memory_kb.is_none());
+
+    Ok(())
+}
+
+/// Name and recorded fields of a closed span
+type ClosedSpan = (String, HashMap<String, String>);
+
+/// Collects the fields of every closed span
+#[derive(Clone, Default)]
+struct SpanCapture {
+    closed: Arc<Mutex<Vec<ClosedSpan>>>,
+}
+
+struct SpanFields(HashMap<String, String>);
+
+impl tracing::field::Visit for SpanFields {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl<S> tracing_subscriber::Layer<S> for SpanCapture
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let mut fields = SpanFields(HashMap::new());
+        attrs.record(&mut fields);
+        ctx.span(id).unwrap().extensions_mut().insert(fields);
+    }
+
+    fn on_record(&self, id: &tracing::span::Id, values: &tracing::span::Record<'_>, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        if let Some(fields) = ctx.span(id).unwrap().extensions_mut().get_mut::<SpanFields>() {
+            values.record(fields);
+        }
+    }
+
+    fn on_close(&self, id: tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let span = ctx.span(&id).unwrap();
+        let fields = span.extensions_mut().remove::<SpanFields>().map(|fields| fields.0).unwrap_or_default();
+        self.closed.lock().unwrap().push((span.name().to_string(), fields));
+    }
+}
+
+#[test]
+fn test_tracing_span_wraps_execution() -> PluginResult<()> {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    std::fs::write(&config_path, format!(
+        "plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\ntracing_enabled: true\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\nplugins: {{}}\n",
+        temp_dir.path().join("plugins").to_string_lossy(), temp_dir.path().join("state").to_string_lossy())).unwrap();
+    let mut manager = PluginManager::with_config_path(&config_path)?;
+    manager.register_plugin(Box::new(EchoPlugin))?;
+
+    let capture = SpanCapture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        manager.execute_plugin_with_options("echo", "hi", ExecutionOptions::default().with_request_id("req-42"))
+    })?;
+
+    let closed = capture.closed.lock().unwrap();
+    let (_, fields) = closed.iter().find(|(name, _)| name == "plugin_execution").expect("execution span closed");
+    assert_eq!(fields["plugin"], "echo");
+    assert_eq!(fields["request_id"], "req-42");
+    assert_eq!(fields["success"], "true");
+    assert!(fields["duration_ms"].parse::<u64>().is_ok(), "{:?}", fields);
+
+    Ok(())
+}
+
+#[test]
+fn test_execution_priority_orders_queued_executions() -> PluginResult<()> {
+    let temp_dir = TempDir::new().unwrap();
+    let mut manager = PluginManager::with_config_path(write_isolated_config(&temp_dir))?;
+    manager.register_plugin(Box::new(EchoPlugin))?;
+    let manager = Arc::new(manager);
+    let queue = Arc::new(ExecutionQueue::new(1));
+    let outputs = Arc::new(Mutex::new(Vec::new()));
+
+    // Each execution waits for a slot in the order its options ask for
+    let spawn = |priority: ExecutionPriority, input: &str| {
+        let (manager, queue, outputs) = (manager.clone(), queue.clone(), outputs.clone());
+        let input = input.to_string();
+        std::thread::spawn(move || {
+            let options = ExecutionOptions::default().with_priority(priority);
+            let _slot = queue.acquire(options.priority);
+            let result = manager.execute_plugin_with_options("echo", &input, options).unwrap();
+            outputs.lock().unwrap().push(result.output);
+        })
+    };
+    let wait_for_waiters = |count: usize| {
+        while queue.waiting() < count {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    };
+
+    let blocker = queue.acquire(ExecutionPriority::Normal);
+    let low = spawn(ExecutionPriority::Low, "low");
+    wait_for_waiters(1);
+    let high = spawn(ExecutionPriority::High, "high");
+    wait_for_waiters(2);
+
+    drop(blocker);
+    low.join().unwrap();
+    high.join().unwrap();
+    assert_eq!(*outputs.lock().unwrap(), ["high", "low"]);
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use dyn_plug_core::plugin_prelude::{optional_field, parse_operation_with_example, require_str};
 use dyn_plug_core::{Plugin, register_plugin};
 use std::error::Error;
 
@@ -7,11 +8,24 @@ use std::error::Error;
 /// - uppercase: Convert string to uppercase
 /// - lowercase: Convert string to lowercase  
 /// - reverse: Reverse the string
+/// - template: Substitute `{key}` placeholders in a template from a `vars` object
 /// 
 /// Input format: JSON with "operation" and "text" fields
 /// Example: {"operation": "uppercase", "text": "hello world"}
+/// 
+/// The template operation takes "template", "vars" and an optional "strict" flag instead:
+/// {"operation": "template", "template": "Hello {name}", "vars": {"name": "world"}}
 pub struct PluginA;
 
+/// Example input shown to callers who pass plain text
+const EXAMPLE_INPUT: &str = r#"{"operation":"uppercase","text":"hello"}"#;
+
+impl Default for PluginA {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PluginA {
     pub fn new() -> Self {
         Self
@@ -28,27 +42,58 @@ impl Plugin for PluginA {
     }
 
     fn description(&self) -> &str {
-        "String processing plugin with uppercase, lowercase, and reverse operations"
+        "String processing plugin with uppercase, lowercase, reverse, and template operations"
+    }
+
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![
+            ("Convert text to uppercase".to_string(), r#"{"operation": "uppercase", "text": "hello world"}"#.to_string()),
+            ("Convert text to lowercase".to_string(), r#"{"operation": "lowercase", "text": "HELLO WORLD"}"#.to_string()),
+            ("Reverse text".to_string(), r#"{"operation": "reverse", "text": "hello"}"#.to_string()),
+            ("Fill in a template".to_string(), r#"{"operation": "template", "template": "Hello {name}", "vars": {"name": "world"}}"#.to_string()),
+        ]
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "operation": { "enum": ["uppercase", "lowercase", "reverse", "template"] },
+                "text": { "type": "string" },
+                "template": { "type": "string" },
+                "vars": { "type": "object" },
+                "strict": { "type": "boolean", "default": true }
+            },
+            "required": ["operation"]
+        }))
     }
 
     fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
         // Parse JSON input
-        let parsed: serde_json::Value = serde_json::from_str(input)
-            .map_err(|e| format!("Invalid JSON input: {}", e))?;
-
-        let operation = parsed["operation"]
-            .as_str()
-            .ok_or("Missing 'operation' field")?;
-
-        let text = parsed["text"]
-            .as_str()
-            .ok_or("Missing 'text' field")?;
-
-        let result = match operation {
-            "uppercase" => text.to_uppercase(),
-            "lowercase" => text.to_lowercase(),
-            "reverse" => text.chars().rev().collect(),
-            _ => return Err(format!("Unknown operation: {}. Supported operations: uppercase, lowercase, reverse", operation).into()),
+        let (operation, parsed) = parse_operation_with_example(input, EXAMPLE_INPUT)?;
+
+        let (text, result) = if operation == "template" {
+            let template = require_str(&parsed, "template")?;
+            let empty = serde_json::Map::new();
+            let vars = match optional_field(&parsed, "vars") {
+                Some(vars) => vars.as_object().ok_or("field 'vars' must be an object")?,
+                None => &empty,
+            };
+            let strict = match optional_field(&parsed, "strict") {
+                Some(strict) => strict.as_bool().ok_or("field 'strict' must be a boolean")?,
+                None => true,
+            };
+            (template, render_template(template, vars, strict)?)
+        } else {
+            let text = require_str(&parsed, "text")?;
+            let result = match operation.as_str() {
+                "uppercase" => text.to_uppercase(),
+                "lowercase" => text.to_lowercase(),
+                "reverse" => text.chars().rev().collect(),
+                _ => return Err(format!("Unknown operation: {}. Supported operations: uppercase, lowercase, reverse, template", operation).into()),
+            };
+            (text, result)
         };
 
         // Return result as JSON
@@ -62,4 +107,122 @@ impl Plugin for PluginA {
     }
 }
 
-register_plugin!(PluginA);
\ No newline at end of file
+/// Substitute `{key}` placeholders in `template` with values from `vars`
+///
+/// `{{` and `}}` produce literal braces. String values are inserted as-is and other
+/// values as JSON. Unknown or unclosed placeholders are an error when `strict` is
+/// set and are left as written otherwise.
+fn render_template(
+    template: &str,
+    vars: &serde_json::Map<String, serde_json::Value>,
+    strict: bool,
+) -> Result<String, String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(index) = rest.find(['{', '}']) {
+        output.push_str(&rest[..index]);
+        let brace = rest.as_bytes()[index];
+        rest = &rest[index + 1..];
+
+        // Doubled braces escape themselves; a lone '}' is kept literally
+        if brace == b'}' || rest.starts_with('{') {
+            output.push(brace as char);
+            if brace == b'{' || rest.starts_with('}') {
+                rest = &rest[1..];
+            }
+            continue;
+        }
+
+        let Some(end) = rest.find('}') else {
+            if strict {
+                return Err(format!("Unclosed placeholder in template at '{{{}'", rest));
+            }
+            output.push('{');
+            continue;
+        };
+        let key = &rest[..end];
+        match vars.get(key) {
+            Some(serde_json::Value::String(value)) => output.push_str(value),
+            Some(value) => output.push_str(&value.to_string()),
+            None if strict => return Err(format!("Missing template variable '{}'", key)),
+            None => {
+                output.push('{');
+                output.push_str(key);
+                output.push('}');
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+register_plugin!(PluginA);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_input_guidance() {
+        let error = PluginA::new().execute("hello").unwrap_err().to_string();
+        assert_eq!(
+            error,
+            r#"Invalid input: input must be JSON, e.g. {"operation":"uppercase","text":"hello"}"#
+        );
+    }
+
+    fn template(input: serde_json::Value) -> Result<String, String> {
+        PluginA::new()
+            .execute(&input.to_string())
+            .map(|result| serde_json::from_str::<serde_json::Value>(&result).unwrap()["output"].as_str().unwrap().to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_template_substitutes_all_variables() {
+        let output = template(serde_json::json!({
+            "operation": "template",
+            "template": "Hello {name}, you are {age} ({name})",
+            "vars": {"name": "Ada", "age": 36}
+        }));
+        assert_eq!(output.unwrap(), "Hello Ada, you are 36 (Ada)");
+    }
+
+    #[test]
+    fn test_template_missing_variable() {
+        let strict = template(serde_json::json!({
+            "operation": "template",
+            "template": "Hello {name} from {place}",
+            "vars": {"name": "Ada"}
+        }));
+        assert_eq!(strict.unwrap_err(), "Missing template variable 'place'");
+
+        let lenient = template(serde_json::json!({
+            "operation": "template",
+            "template": "Hello {name} from {place} {unclosed",
+            "vars": {"name": "Ada"},
+            "strict": false
+        }));
+        assert_eq!(lenient.unwrap(), "Hello Ada from {place} {unclosed");
+    }
+
+    #[test]
+    fn test_template_escaped_braces() {
+        let output = template(serde_json::json!({
+            "operation": "template",
+            "template": "{{name}} is {name} }} {{",
+            "vars": {"name": "Ada"}
+        }));
+        assert_eq!(output.unwrap(), "{name} is Ada } {");
+    }
+
+    #[test]
+    fn test_example_input_executes() {
+        let result = PluginA::new().execute(EXAMPLE_INPUT).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed["output"], "HELLO");
+    }
+}
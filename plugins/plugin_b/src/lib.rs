@@ -1,3 +1,5 @@
+use dyn_plug_core::plugin_prelude::{optional_field, parse_operation_with_example, require_f64_array, require_str};
+use dyn_plug_core::PluginError;
 use dyn_plug_core::{Plugin, register_plugin};
 use std::error::Error;
 
@@ -10,16 +12,116 @@ use std::error::Error;
 /// - divide: Divide first number by second
 /// - power: Raise first number to the power of second
 /// - sqrt: Square root of a number
+/// - round: Round a number to `precision` decimal places (default 0)
+/// - to_base: Write an integer in `base` (2-36): {"operation": "to_base", "numbers": [255], "base": 16}
+/// - from_base: Read a string in `base` (2-36) as an integer: {"operation": "from_base", "value": "ff", "base": 16}
 /// 
 /// Input format: JSON with "operation" and "numbers" fields
 /// Example: {"operation": "add", "numbers": [5, 3]}
 /// For single number operations: {"operation": "sqrt", "numbers": [16]}
+/// 
+/// An optional "precision" field (decimal places, clamped to 0..=15) rounds
+/// the result of any operation: {"operation": "add", "numbers": [0.1, 0.2], "precision": 2}
 pub struct PluginB;
 
+/// Largest number of decimal places that `f64` can meaningfully represent
+const MAX_PRECISION: u64 = 15;
+
+/// Example input shown to callers who pass plain text
+const EXAMPLE_INPUT: &str = r#"{"operation":"add","numbers":[5,3]}"#;
+
+/// Digits used for bases up to 36
+const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+impl Default for PluginB {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PluginB {
     pub fn new() -> Self {
         Self
     }
+
+    /// Read the optional "precision" field, clamping it to `MAX_PRECISION`
+    fn parse_precision(parsed: &serde_json::Value) -> Result<Option<u32>, PluginError> {
+        match optional_field(parsed, "precision") {
+            None => Ok(None),
+            Some(value) => {
+                let precision = value.as_u64().ok_or_else(|| {
+                    PluginError::invalid_input("field 'precision' must be a non-negative integer")
+                })?;
+                Ok(Some(precision.min(MAX_PRECISION) as u32))
+            }
+        }
+    }
+
+    /// Read the required "base" field, which must be an integer from 2 to 36
+    fn parse_base(parsed: &serde_json::Value) -> Result<u32, PluginError> {
+        match optional_field(parsed, "base").and_then(|value| value.as_u64()) {
+            Some(base @ 2..=36) => Ok(base as u32),
+            _ => Err(PluginError::invalid_input("field 'base' must be an integer between 2 and 36")),
+        }
+    }
+
+    /// Write an integer in the given base using lowercase digits
+    fn format_in_base(value: i64, base: u32) -> String {
+        let mut magnitude = value.unsigned_abs();
+        let mut digits = Vec::new();
+        loop {
+            digits.push(DIGITS[(magnitude % base as u64) as usize]);
+            magnitude /= base as u64;
+            if magnitude == 0 {
+                break;
+            }
+        }
+        if value < 0 {
+            digits.push(b'-');
+        }
+        digits.reverse();
+        String::from_utf8(digits).expect("digits are ASCII")
+    }
+
+    /// Run the `to_base` and `from_base` operations
+    fn convert_base(operation: &str, parsed: &serde_json::Value) -> Result<serde_json::Value, PluginError> {
+        let base = Self::parse_base(parsed)?;
+        if operation == "to_base" {
+            let numbers = require_f64_array(parsed, "numbers")?;
+            if numbers.len() != 1 {
+                return Err(PluginError::invalid_input("To base operation requires exactly 1 number"));
+            }
+            let value = parsed["numbers"][0].as_i64().ok_or_else(|| {
+                PluginError::invalid_input(format!("Base conversion requires a 64-bit integer, got {}", parsed["numbers"][0]))
+            })?;
+            Ok(serde_json::json!({
+                "operation": operation,
+                "input": parsed["numbers"],
+                "output": Self::format_in_base(value, base)
+            }))
+        } else {
+            let text = require_str(parsed, "value")?;
+            let value = i64::from_str_radix(text.trim(), base).map_err(|e| {
+                PluginError::invalid_input(format!("'{}' is not a valid base {} integer: {}", text, base, e))
+            })?;
+            Ok(serde_json::json!({
+                "operation": operation,
+                "input": text,
+                "output": value
+            }))
+        }
+    }
+
+    /// Round a value to the given number of decimal places
+    fn round_to(value: f64, precision: u32) -> f64 {
+        let factor = 10f64.powi(precision as i32);
+        let scaled = value * factor;
+        // Values too large to scale safely have no fractional digits left to round
+        if !scaled.is_finite() || scaled.abs() >= 2f64.powi(53) {
+            return value;
+        }
+        scaled.round() / factor
+    }
 }
 
 impl Plugin for PluginB {
@@ -32,53 +134,82 @@ impl Plugin for PluginB {
     }
 
     fn description(&self) -> &str {
-        "Numeric processing plugin with arithmetic operations (add, subtract, multiply, divide, power, sqrt)"
+        "Numeric processing plugin with arithmetic operations (add, subtract, multiply, divide, power, sqrt, round, to_base, from_base)"
     }
 
-    fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
-        // Parse JSON input
-        let parsed: serde_json::Value = serde_json::from_str(input)
-            .map_err(|e| format!("Invalid JSON input: {}", e))?;
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![
+            ("Add two numbers".to_string(), r#"{"operation": "add", "numbers": [5, 3]}"#.to_string()),
+            ("Square root of a number".to_string(), r#"{"operation": "sqrt", "numbers": [16]}"#.to_string()),
+            ("Round a sum to two decimal places".to_string(), r#"{"operation": "add", "numbers": [0.1, 0.2], "precision": 2}"#.to_string()),
+            ("Write a number in hexadecimal".to_string(), r#"{"operation": "to_base", "numbers": [255], "base": 16}"#.to_string()),
+            ("Read a binary number".to_string(), r#"{"operation": "from_base", "value": "1010", "base": 2}"#.to_string()),
+        ]
+    }
 
-        let operation = parsed["operation"]
-            .as_str()
-            .ok_or("Missing 'operation' field")?;
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "operation": { "enum": ["add", "subtract", "multiply", "divide", "power", "sqrt", "round", "to_base", "from_base"] },
+                "numbers": { "type": "array", "items": { "type": "number" }, "minItems": 1, "maxItems": 2 },
+                "precision": { "type": "integer", "minimum": 0 },
+                "base": { "type": "integer", "minimum": 2, "maximum": 36 },
+                "value": { "type": "string" }
+            },
+            "required": ["operation"],
+            // Every operation but from_base works on "numbers"; base conversions also need "base"
+            "allOf": [
+                {
+                    "if": { "properties": { "operation": { "const": "from_base" } } },
+                    "then": { "required": ["value", "base"] },
+                    "else": { "required": ["numbers"] }
+                },
+                {
+                    "if": { "properties": { "operation": { "const": "to_base" } } },
+                    "then": { "required": ["base"] }
+                }
+            ]
+        }))
+    }
 
-        let numbers = parsed["numbers"]
-            .as_array()
-            .ok_or("Missing 'numbers' field or not an array")?;
+    fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
+        // Parse JSON input
+        let (operation, parsed) = parse_operation_with_example(input, EXAMPLE_INPUT)?;
+        if operation == "to_base" || operation == "from_base" {
+            return Ok(Self::convert_base(&operation, &parsed)?.to_string());
+        }
+        let numbers = require_f64_array(&parsed, "numbers")?;
+        let precision = Self::parse_precision(&parsed)?;
 
-        let result = match operation {
+        let result = match operation.as_str() {
             "add" => {
                 if numbers.len() != 2 {
                     return Err("Add operation requires exactly 2 numbers".into());
                 }
-                let a = numbers[0].as_f64().ok_or("First number is not a valid number")?;
-                let b = numbers[1].as_f64().ok_or("Second number is not a valid number")?;
+                let (a, b) = (numbers[0], numbers[1]);
                 a + b
             },
             "subtract" => {
                 if numbers.len() != 2 {
                     return Err("Subtract operation requires exactly 2 numbers".into());
                 }
-                let a = numbers[0].as_f64().ok_or("First number is not a valid number")?;
-                let b = numbers[1].as_f64().ok_or("Second number is not a valid number")?;
+                let (a, b) = (numbers[0], numbers[1]);
                 a - b
             },
             "multiply" => {
                 if numbers.len() != 2 {
                     return Err("Multiply operation requires exactly 2 numbers".into());
                 }
-                let a = numbers[0].as_f64().ok_or("First number is not a valid number")?;
-                let b = numbers[1].as_f64().ok_or("Second number is not a valid number")?;
+                let (a, b) = (numbers[0], numbers[1]);
                 a * b
             },
             "divide" => {
                 if numbers.len() != 2 {
                     return Err("Divide operation requires exactly 2 numbers".into());
                 }
-                let a = numbers[0].as_f64().ok_or("First number is not a valid number")?;
-                let b = numbers[1].as_f64().ok_or("Second number is not a valid number")?;
+                let (a, b) = (numbers[0], numbers[1]);
                 if b == 0.0 {
                     return Err("Division by zero is not allowed".into());
                 }
@@ -88,27 +219,42 @@ impl Plugin for PluginB {
                 if numbers.len() != 2 {
                     return Err("Power operation requires exactly 2 numbers".into());
                 }
-                let a = numbers[0].as_f64().ok_or("First number is not a valid number")?;
-                let b = numbers[1].as_f64().ok_or("Second number is not a valid number")?;
+                let (a, b) = (numbers[0], numbers[1]);
                 a.powf(b)
             },
             "sqrt" => {
                 if numbers.len() != 1 {
                     return Err("Square root operation requires exactly 1 number".into());
                 }
-                let a = numbers[0].as_f64().ok_or("Number is not a valid number")?;
+                let a = numbers[0];
                 if a < 0.0 {
                     return Err("Cannot calculate square root of negative number".into());
                 }
                 a.sqrt()
             },
-            _ => return Err(format!("Unknown operation: {}. Supported operations: add, subtract, multiply, divide, power, sqrt", operation).into()),
+            "round" => {
+                if numbers.len() != 1 {
+                    return Err("Round operation requires exactly 1 number".into());
+                }
+                Self::round_to(numbers[0], precision.unwrap_or(0))
+            },
+            _ => return Err(format!("Unknown operation: {}. Supported operations: add, subtract, multiply, divide, power, sqrt, round, to_base, from_base", operation).into()),
+        };
+
+        // JSON has no infinity or NaN; serde_json would emit them as null
+        if !result.is_finite() {
+            return Err("result is not finite (overflow or invalid operation)".into());
+        }
+
+        let result = match precision {
+            Some(precision) => Self::round_to(result, precision),
+            None => result,
         };
 
         // Return result as JSON
         let response = serde_json::json!({
             "operation": operation,
-            "input": numbers,
+            "input": parsed["numbers"],
             "output": result
         });
 
@@ -116,4 +262,110 @@ impl Plugin for PluginB {
     }
 }
 
-register_plugin!(PluginB);
\ No newline at end of file
+register_plugin!(PluginB);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(input: &str) -> serde_json::Value {
+        let result = PluginB::new().execute(input).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        parsed["output"].clone()
+    }
+
+    #[test]
+    fn test_output_unchanged_without_precision() {
+        let result = output(r#"{"operation": "add", "numbers": [0.1, 0.2]}"#);
+        assert_eq!(result.as_f64().unwrap(), 0.1 + 0.2);
+    }
+
+    #[test]
+    fn test_precision_rounds_result() {
+        let result = output(r#"{"operation": "add", "numbers": [0.1, 0.2], "precision": 2}"#);
+        assert_eq!(result.as_f64().unwrap(), 0.3);
+
+        let result = output(r#"{"operation": "divide", "numbers": [2, 3], "precision": 4}"#);
+        assert_eq!(result.as_f64().unwrap(), 0.6667);
+    }
+
+    #[test]
+    fn test_round_operation() {
+        let result = output(r#"{"operation": "round", "numbers": [1.23456], "precision": 3}"#);
+        assert_eq!(result.as_f64().unwrap(), 1.235);
+
+        let result = output(r#"{"operation": "round", "numbers": [2.5]}"#);
+        assert_eq!(result.as_f64().unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_precision_clamped() {
+        let result = output(r#"{"operation": "add", "numbers": [0.1, 0.2], "precision": 100}"#);
+        assert_eq!(result.as_f64().unwrap(), 0.3);
+
+        let result = output(r#"{"operation": "multiply", "numbers": [1e300, 10], "precision": 15}"#);
+        assert_eq!(result.as_f64().unwrap(), 1e301);
+    }
+
+    #[test]
+    fn test_non_finite_results_rejected() {
+        for input in [
+            r#"{"operation": "power", "numbers": [10, 400]}"#,
+            r#"{"operation": "power", "numbers": [0, -1]}"#,
+            r#"{"operation": "power", "numbers": [-8, 0.5]}"#,
+            r#"{"operation": "multiply", "numbers": [1e300, 1e300]}"#,
+        ] {
+            let error = PluginB::new().execute(input).unwrap_err().to_string();
+            assert_eq!(error, "result is not finite (overflow or invalid operation)", "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_invalid_precision() {
+        let result = PluginB::new().execute(r#"{"operation": "add", "numbers": [1, 2], "precision": -1}"#);
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("field 'precision' must be a non-negative integer"));
+    }
+
+    #[test]
+    fn test_to_base() {
+        assert_eq!(output(r#"{"operation": "to_base", "numbers": [10], "base": 2}"#), "1010");
+        assert_eq!(output(r#"{"operation": "to_base", "numbers": [255], "base": 16}"#), "ff");
+        assert_eq!(output(r#"{"operation": "to_base", "numbers": [-35], "base": 36}"#), "-z");
+        assert_eq!(output(r#"{"operation": "to_base", "numbers": [0], "base": 7}"#), "0");
+    }
+
+    #[test]
+    fn test_from_base() {
+        assert_eq!(output(r#"{"operation": "from_base", "value": "1010", "base": 2}"#), 10);
+        assert_eq!(output(r#"{"operation": "from_base", "value": "FF", "base": 16}"#), 255);
+        assert_eq!(output(r#"{"operation": "from_base", "value": "zz", "base": 36}"#), 1295);
+    }
+
+    #[test]
+    fn test_base_conversion_errors() {
+        let plugin = PluginB::new();
+        for input in [
+            r#"{"operation": "to_base", "numbers": [10], "base": 1}"#,
+            r#"{"operation": "to_base", "numbers": [10], "base": 37}"#,
+            r#"{"operation": "from_base", "value": "10"}"#,
+        ] {
+            let error = plugin.execute(input).unwrap_err().to_string();
+            assert!(error.contains("field 'base' must be an integer between 2 and 36"), "{}", error);
+        }
+
+        let error = plugin.execute(r#"{"operation": "to_base", "numbers": [2.5], "base": 2}"#).unwrap_err().to_string();
+        assert!(error.contains("requires a 64-bit integer"), "{}", error);
+
+        let error = plugin.execute(r#"{"operation": "from_base", "value": "12", "base": 2}"#).unwrap_err().to_string();
+        assert!(error.contains("not a valid base 2 integer"), "{}", error);
+    }
+
+    #[test]
+    fn test_examples_execute() {
+        let plugin = PluginB::new();
+        for (description, input) in plugin.examples() {
+            assert!(plugin.execute(&input).is_ok(), "example failed: {}", description);
+        }
+    }
+}
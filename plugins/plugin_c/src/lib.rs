@@ -1,3 +1,4 @@
+use dyn_plug_core::plugin_prelude::{parse_operation_with_example, require_str};
 use dyn_plug_core::{Plugin, register_plugin};
 use std::error::Error;
 
@@ -10,12 +11,22 @@ use std::error::Error;
 /// - query: Extract value from JSON using dot notation (e.g., "user.name")
 /// - keys: Get all keys from a JSON object
 /// - type: Get the type of a JSON value
+/// - csv: Convert a JSON array of flat objects to CSV
 /// 
 /// Input format: JSON with "operation" and "data" fields
 /// Example: {"operation": "format", "data": "{\"name\":\"John\",\"age\":30}"}
 /// For query: {"operation": "query", "data": "{\"user\":{\"name\":\"John\"}}", "path": "user.name"}
 pub struct PluginC;
 
+/// Example input shown to callers who pass plain text
+const EXAMPLE_INPUT: &str = r#"{"operation":"format","data":"{\"name\":\"John\"}"}"#;
+
+impl Default for PluginC {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PluginC {
     pub fn new() -> Self {
         Self
@@ -44,6 +55,69 @@ impl PluginC {
         Some(current)
     }
 
+    /// Re-indent or minify JSON text without parsing it into values
+    ///
+    /// Number literals, string escapes and key order come out exactly as written.
+    /// `data` must already be valid JSON; an `indent` of `None` minifies.
+    fn reformat_json(data: &str, indent: Option<&str>) -> String {
+        let mut out = String::with_capacity(data.len());
+        let newline = |out: &mut String, depth: usize| {
+            if let Some(indent) = indent {
+                out.push('\n');
+                for _ in 0..depth {
+                    out.push_str(indent);
+                }
+            }
+        };
+
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        let mut chars = data.chars().peekable();
+        while let Some(c) = chars.next() {
+            if in_string {
+                out.push(c);
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '"' => {
+                    in_string = true;
+                    out.push(c);
+                }
+                '{' | '[' => {
+                    out.push(c);
+                    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+                    // Empty containers stay on one line
+                    if let Some(close) = chars.next_if(|&c| c == '}' || c == ']') {
+                        out.push(close);
+                    } else {
+                        depth += 1;
+                        newline(&mut out, depth);
+                    }
+                }
+                '}' | ']' => {
+                    depth -= 1;
+                    newline(&mut out, depth);
+                    out.push(c);
+                }
+                ',' => {
+                    out.push(c);
+                    newline(&mut out, depth);
+                }
+                ':' => out.push_str(if indent.is_some() { ": " } else { ":" }),
+                c if c.is_ascii_whitespace() => {}
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
     fn get_json_keys(value: &serde_json::Value) -> Vec<String> {
         match value {
             serde_json::Value::Object(map) => map.keys().cloned().collect(),
@@ -61,6 +135,49 @@ impl PluginC {
             serde_json::Value::Object(_) => "object",
         }
     }
+
+    /// Convert a JSON array of objects to CSV
+    ///
+    /// The header is the union of all keys, taking each object's new keys in turn.
+    /// Parsed objects keep their keys sorted, so each object's contribution is alphabetical.
+    /// Missing values and nulls are empty cells; nested arrays and objects are
+    /// JSON-encoded into their cell.
+    fn json_to_csv(value: &serde_json::Value) -> Result<String, String> {
+        let rows = value
+            .as_array()
+            .ok_or_else(|| format!("csv requires a JSON array of objects, got {}", Self::get_json_type(value)))?;
+        if rows.is_empty() {
+            return Err("csv requires at least one object to derive the header from".to_string());
+        }
+
+        let mut objects = Vec::with_capacity(rows.len());
+        let mut header: Vec<&str> = Vec::new();
+        for (index, row) in rows.iter().enumerate() {
+            let object = row
+                .as_object()
+                .ok_or_else(|| format!("csv row {} must be an object, got {}", index, Self::get_json_type(row)))?;
+            for key in object.keys() {
+                if !header.contains(&key.as_str()) {
+                    header.push(key);
+                }
+            }
+            objects.push(object);
+        }
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(&header).map_err(|e| format!("Failed to write CSV: {}", e))?;
+        for object in objects {
+            let record = header.iter().map(|key| match object.get(*key) {
+                None | Some(serde_json::Value::Null) => String::new(),
+                Some(serde_json::Value::String(text)) => text.clone(),
+                Some(other) => other.to_string(),
+            });
+            writer.write_record(record).map_err(|e| format!("Failed to write CSV: {}", e))?;
+        }
+
+        let bytes = writer.into_inner().map_err(|e| format!("Failed to write CSV: {}", e))?;
+        String::from_utf8(bytes).map_err(|e| format!("Failed to write CSV: {}", e))
+    }
 }
 
 impl Plugin for PluginC {
@@ -73,34 +190,41 @@ impl Plugin for PluginC {
     }
 
     fn description(&self) -> &str {
-        "JSON processing plugin with format, minify, validate, query, keys, and type operations"
+        "JSON processing plugin with format, minify, validate, query, keys, type, and csv operations"
     }
 
-    fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
-        // Parse JSON input
-        let parsed: serde_json::Value = serde_json::from_str(input)
-            .map_err(|e| format!("Invalid JSON input: {}", e))?;
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![
+            ("Pretty-format JSON".to_string(), r#"{"operation": "format", "data": "{\"name\":\"John\",\"age\":30}"}"#.to_string()),
+            ("Query a nested value".to_string(), r#"{"operation": "query", "data": "{\"user\":{\"name\":\"John\"}}", "path": "user.name"}"#.to_string()),
+            ("Convert records to CSV".to_string(), r#"{"operation": "csv", "data": "[{\"name\":\"John\",\"age\":30}]"}"#.to_string()),
+        ]
+    }
 
-        let operation = parsed["operation"]
-            .as_str()
-            .ok_or("Missing 'operation' field")?;
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "operation": { "enum": ["format", "minify", "validate", "query", "keys", "type", "csv"] },
+                "data": { "type": "string", "description": "JSON document encoded as a string" },
+                "path": { "type": "string", "description": "Dot-separated path, required by query" }
+            },
+            "required": ["operation", "data"]
+        }))
+    }
 
-        let data_str = parsed["data"]
-            .as_str()
-            .ok_or("Missing 'data' field")?;
+    fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
+        // Parse JSON input
+        let (operation, parsed) = parse_operation_with_example(input, EXAMPLE_INPUT)?;
+        let data_str = require_str(&parsed, "data")?;
 
-        let result = match operation {
-            "format" => {
-                let json_data: serde_json::Value = serde_json::from_str(data_str)
+        let result = match operation.as_str() {
+            "format" | "minify" => {
+                // Parsed only to validate, as parsed numbers are rounded to f64
+                serde_json::from_str::<serde_json::Value>(data_str)
                     .map_err(|e| format!("Invalid JSON data: {}", e))?;
-                serde_json::to_string_pretty(&json_data)
-                    .map_err(|e| format!("Failed to format JSON: {}", e))?
-            },
-            "minify" => {
-                let json_data: serde_json::Value = serde_json::from_str(data_str)
-                    .map_err(|e| format!("Invalid JSON data: {}", e))?;
-                serde_json::to_string(&json_data)
-                    .map_err(|e| format!("Failed to minify JSON: {}", e))?
+                Self::reformat_json(data_str, (operation == "format").then_some("  "))
             },
             "validate" => {
                 match serde_json::from_str::<serde_json::Value>(data_str) {
@@ -109,9 +233,7 @@ impl Plugin for PluginC {
                 }
             },
             "query" => {
-                let path = parsed["path"]
-                    .as_str()
-                    .ok_or("Missing 'path' field for query operation")?;
+                let path = require_str(&parsed, "path")?;
                 
                 let json_data: serde_json::Value = serde_json::from_str(data_str)
                     .map_err(|e| format!("Invalid JSON data: {}", e))?;
@@ -136,7 +258,13 @@ impl Plugin for PluginC {
                 
                 Self::get_json_type(&json_data).to_string()
             },
-            _ => return Err(format!("Unknown operation: {}. Supported operations: format, minify, validate, query, keys, type", operation).into()),
+            "csv" => {
+                let json_data: serde_json::Value = serde_json::from_str(data_str)
+                    .map_err(|e| format!("Invalid JSON data: {}", e))?;
+                
+                Self::json_to_csv(&json_data)?
+            },
+            _ => return Err(format!("Unknown operation: {}. Supported operations: format, minify, validate, query, keys, type, csv", operation).into()),
         };
 
         // Return result as JSON
@@ -150,4 +278,95 @@ impl Plugin for PluginC {
     }
 }
 
-register_plugin!(PluginC);
\ No newline at end of file
+register_plugin!(PluginC);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv(data: &str) -> Result<String, String> {
+        let input = serde_json::json!({"operation": "csv", "data": data}).to_string();
+        PluginC::new()
+            .execute(&input)
+            .map(|result| serde_json::from_str::<serde_json::Value>(&result).unwrap()["output"].as_str().unwrap().to_string())
+            .map_err(|e| e.to_string())
+    }
+
+    fn output(operation: &str, data: &str) -> String {
+        let input = serde_json::json!({"operation": operation, "data": data}).to_string();
+        let result = PluginC::new().execute(&input).unwrap();
+        serde_json::from_str::<serde_json::Value>(&result).unwrap()["output"].as_str().unwrap().to_string()
+    }
+
+    /// Random inputs, both arbitrary bytes and well-formed requests with arbitrary data,
+    /// must produce `Ok` or `Err` and never panic
+    #[test]
+    fn test_random_input_never_panics() {
+        use rand::{Rng, SeedableRng};
+
+        const OPERATIONS: &[&str] = &["format", "minify", "validate", "query", "keys", "type", "csv", "other"];
+        const ALPHABET: &[u8] = br#"{}[]":,.-+eE0123456789 abctrufnl\"#;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+        let plugin = PluginC::new();
+
+        for _ in 0..2000 {
+            let len = rng.random_range(0..64);
+            let bytes: Vec<u8> = (0..len).map(|_| rng.random()).collect();
+            let _ = plugin.execute(&String::from_utf8_lossy(&bytes));
+
+            let data: String = (0..len).map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())] as char).collect();
+            let operation = OPERATIONS[rng.random_range(0..OPERATIONS.len())];
+            let input = serde_json::json!({"operation": operation, "data": data, "path": "a.0.b"}).to_string();
+            let _ = plugin.execute(&input);
+        }
+    }
+
+    #[test]
+    fn test_format_and_minify_preserve_number_literals() {
+        let data = r#"{"id": 12345678901234567890, "ratio": 0.1000000000000000055511151231257827, "small": -1.5e-300}"#;
+
+        assert_eq!(
+            output("minify", data),
+            r#"{"id":12345678901234567890,"ratio":0.1000000000000000055511151231257827,"small":-1.5e-300}"#
+        );
+        assert_eq!(
+            output("format", data),
+            "{\n  \"id\": 12345678901234567890,\n  \"ratio\": 0.1000000000000000055511151231257827,\n  \"small\": -1.5e-300\n}"
+        );
+    }
+
+    #[test]
+    fn test_format_and_minify_keep_strings_and_key_order() {
+        let data = r#" { "z": "a \" , { [ b", "a": [ ], "m": { } , "list": [1, {"k": null}] } "#;
+
+        assert_eq!(output("minify", data), r#"{"z":"a \" , { [ b","a":[],"m":{},"list":[1,{"k":null}]}"#);
+        assert_eq!(
+            output("format", data),
+            "{\n  \"z\": \"a \\\" , { [ b\",\n  \"a\": [],\n  \"m\": {},\n  \"list\": [\n    1,\n    {\n      \"k\": null\n    }\n  ]\n}"
+        );
+        assert!(PluginC::new().execute(r#"{"operation": "minify", "data": "{\"a\": }"}"#).is_err());
+    }
+
+    #[test]
+    fn test_csv_with_heterogeneous_keys() {
+        let output = csv(r#"[
+            {"name": "Ada", "age": 36},
+            {"name": "Linus, Jr.", "city": "Helsinki", "tags": ["a", "b"]},
+            {"age": null, "meta": {"x": 1}}
+        ]"#).unwrap();
+        assert_eq!(
+            output,
+            "age,name,city,tags,meta\n\
+             36,Ada,,,\n\
+             ,\"Linus, Jr.\",Helsinki,\"[\"\"a\"\",\"\"b\"\"]\",\n\
+             ,,,,\"{\"\"x\"\":1}\"\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_rejects_empty_and_non_array_input() {
+        assert_eq!(csv("[]").unwrap_err(), "csv requires at least one object to derive the header from");
+        assert_eq!(csv(r#"{"name": "Ada"}"#).unwrap_err(), "csv requires a JSON array of objects, got object");
+        assert_eq!(csv(r#"[{"name": "Ada"}, 3]"#).unwrap_err(), "csv row 1 must be an object, got number");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,206 @@
+use dyn_plug_core::plugin_prelude::{optional_field, parse_operation_with_example};
+use dyn_plug_core::PluginError;
+use dyn_plug_core::{Plugin, register_plugin};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::error::Error;
+use std::sync::Mutex;
+
+/// Plugin E - Utility Plugin
+///
+/// This plugin provides non-deterministic utility operations including:
+/// - uuid: Generate a random (version 4) UUID
+/// - now: Current time as an RFC 3339 timestamp
+/// - epoch: Current time as Unix seconds
+/// - random: Random number in [0, 1), or in [min, max) when both are given
+///
+/// Input format: JSON with an "operation" field
+/// Example: {"operation": "uuid"}
+/// For a range: {"operation": "random", "min": 1, "max": 10}
+pub struct PluginE {
+    rng: Mutex<StdRng>,
+}
+
+/// Example input shown to callers who pass plain text
+const EXAMPLE_INPUT: &str = r#"{"operation":"uuid"}"#;
+
+impl Default for PluginE {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PluginE {
+    pub fn new() -> Self {
+        Self {
+            rng: Mutex::new(StdRng::from_os_rng()),
+        }
+    }
+
+    /// Create a plugin whose UUIDs and random numbers are reproducible
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    fn uuid(&self) -> String {
+        let bytes: [u8; 16] = self.rng.lock().unwrap().random();
+        uuid::Builder::from_random_bytes(bytes).into_uuid().to_string()
+    }
+
+    /// Read the optional "min" and "max" fields, which must be given together with min < max
+    fn parse_range(parsed: &serde_json::Value) -> Result<Option<(f64, f64)>, PluginError> {
+        let bound = |field: &str| {
+            optional_field(parsed, field)
+                .map(|value| {
+                    value
+                        .as_f64()
+                        .filter(|bound| bound.is_finite())
+                        .ok_or_else(|| PluginError::invalid_input(format!("field '{}' must be a finite number", field)))
+                })
+                .transpose()
+        };
+
+        match (bound("min")?, bound("max")?) {
+            (None, None) => Ok(None),
+            (Some(min), Some(max)) if min < max && (max - min).is_finite() => Ok(Some((min, max))),
+            (Some(min), Some(max)) if min < max => Err(PluginError::invalid_input("range from 'min' to 'max' is too wide")),
+            (Some(_), Some(_)) => Err(PluginError::invalid_input("field 'min' must be less than field 'max'")),
+            _ => Err(PluginError::invalid_input("fields 'min' and 'max' must be given together")),
+        }
+    }
+
+    fn random(&self, range: Option<(f64, f64)>) -> f64 {
+        let mut rng = self.rng.lock().unwrap();
+        match range {
+            Some((min, max)) => rng.random_range(min..max),
+            None => rng.random(),
+        }
+    }
+}
+
+impl Plugin for PluginE {
+    fn name(&self) -> &str {
+        "plugin_e"
+    }
+
+    fn version(&self) -> &str {
+        "0.1.0"
+    }
+
+    fn description(&self) -> &str {
+        "Utility plugin generating UUIDs, timestamps, and random numbers (uuid, now, epoch, random)"
+    }
+
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![
+            ("Generate a UUID".to_string(), r#"{"operation": "uuid"}"#.to_string()),
+            ("Current timestamp".to_string(), r#"{"operation": "now"}"#.to_string()),
+            ("Random number between 1 and 10".to_string(), r#"{"operation": "random", "min": 1, "max": 10}"#.to_string()),
+        ]
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "operation": { "enum": ["uuid", "now", "epoch", "random"] },
+                "min": { "type": "number", "description": "Inclusive lower bound for random, requires max" },
+                "max": { "type": "number", "description": "Exclusive upper bound for random, requires min" }
+            },
+            "required": ["operation"]
+        }))
+    }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
+        // Parse JSON input
+        let (operation, parsed) = parse_operation_with_example(input, EXAMPLE_INPUT)?;
+
+        let result = match operation.as_str() {
+            "uuid" => serde_json::json!(self.uuid()),
+            "now" => serde_json::json!(chrono::Utc::now().to_rfc3339()),
+            "epoch" => serde_json::json!(chrono::Utc::now().timestamp()),
+            "random" => serde_json::json!(self.random(Self::parse_range(&parsed)?)),
+            _ => return Err(format!("Unknown operation: {}. Supported operations: uuid, now, epoch, random", operation).into()),
+        };
+
+        // Return result as JSON
+        let response = serde_json::json!({
+            "operation": operation,
+            "output": result
+        });
+
+        Ok(response.to_string())
+    }
+}
+
+register_plugin!(PluginE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(plugin: &PluginE, input: &str) -> serde_json::Value {
+        let result = plugin.execute(input).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        parsed["output"].clone()
+    }
+
+    #[test]
+    fn test_uuid_is_v4_and_seeded() {
+        let first = output(&PluginE::with_seed(7), r#"{"operation": "uuid"}"#);
+        let uuid = uuid::Uuid::parse_str(first.as_str().unwrap()).unwrap();
+        assert_eq!(uuid.get_version_num(), 4);
+
+        assert_eq!(output(&PluginE::with_seed(7), r#"{"operation": "uuid"}"#), first);
+        let plugin = PluginE::new();
+        assert_ne!(output(&plugin, r#"{"operation": "uuid"}"#), output(&plugin, r#"{"operation": "uuid"}"#));
+    }
+
+    #[test]
+    fn test_now_and_epoch() {
+        let now = output(&PluginE::new(), r#"{"operation": "now"}"#);
+        assert!(chrono::DateTime::parse_from_rfc3339(now.as_str().unwrap()).is_ok());
+
+        let epoch = output(&PluginE::new(), r#"{"operation": "epoch"}"#).as_i64().unwrap();
+        assert!((epoch - chrono::Utc::now().timestamp()).abs() <= 1);
+    }
+
+    #[test]
+    fn test_random_in_range() {
+        let plugin = PluginE::with_seed(42);
+        for _ in 0..100 {
+            let unit = output(&plugin, r#"{"operation": "random"}"#).as_f64().unwrap();
+            assert!((0.0..1.0).contains(&unit));
+            let ranged = output(&plugin, r#"{"operation": "random", "min": -5, "max": 5}"#).as_f64().unwrap();
+            assert!((-5.0..5.0).contains(&ranged));
+        }
+
+        let seeded = |seed| output(&PluginE::with_seed(seed), r#"{"operation": "random"}"#);
+        assert_eq!(seeded(42), seeded(42));
+    }
+
+    #[test]
+    fn test_random_rejects_invalid_range() {
+        let plugin = PluginE::new();
+        for (input, expected) in [
+            (r#"{"operation": "random", "min": 5, "max": 5}"#, "must be less than"),
+            (r#"{"operation": "random", "min": 1}"#, "must be given together"),
+            (r#"{"operation": "random", "min": "a", "max": 2}"#, "must be a finite number"),
+            (r#"{"operation": "random", "min": -1e308, "max": 1e308}"#, "too wide"),
+        ] {
+            let error = plugin.execute(input).unwrap_err().to_string();
+            assert!(error.contains(expected), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_examples_execute() {
+        let plugin = PluginE::new();
+        for (description, input) in plugin.examples() {
+            assert!(plugin.execute(&input).is_ok(), "example failed: {}", description);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use dyn_plug_core::plugin_prelude::{parse_operation_with_example, require_str};
+use dyn_plug_core::{ExecutionContext, PluginError, PluginExecutionError};
+use dyn_plug_core::{Plugin, register_plugin};
+use reqwest::Url;
+use std::error::Error;
+use std::time::Duration;
+
+/// Plugin HTTP - HTTP Proxy Plugin
+///
+/// This plugin fetches resources over the network:
+/// - get: Perform an HTTP GET and return the response status and body
+///
+/// Only hosts listed in the plugin's `allowed_hosts` setting may be fetched, so
+/// the operator decides where the plugin can reach. The setting is read from the
+/// configuration alone; per-request overrides of it are ignored. With no setting
+/// every host is rejected.
+///
+/// Input format: JSON with "operation" and "url" fields
+/// Example: {"operation": "get", "url": "https://example.com/"}
+///
+/// Configuration:
+/// plugins:
+///   plugin_http:
+///     settings:
+///       allowed_hosts: ["example.com"]
+pub struct PluginHttp;
+
+/// Example input shown to callers who pass plain text
+const EXAMPLE_INPUT: &str = r#"{"operation":"get","url":"https://example.com/"}"#;
+
+/// Setting listing the hosts the plugin may fetch from
+const ALLOWED_HOSTS_SETTING: &str = "allowed_hosts";
+
+/// How long a single request may take, including reading the body
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+impl Default for PluginHttp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PluginHttp {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Read the configured `allowed_hosts` setting, which must be an array of host names
+    fn allowed_hosts(ctx: &ExecutionContext) -> Result<Vec<String>, PluginError> {
+        let Some(value) = ctx.configured_setting(ALLOWED_HOSTS_SETTING) else {
+            return Ok(Vec::new());
+        };
+        value
+            .as_array()
+            .and_then(|hosts| hosts.iter().map(|host| host.as_str().map(str::to_ascii_lowercase)).collect())
+            .ok_or_else(|| PluginError::config_error(format!("setting '{}' must be an array of host names", ALLOWED_HOSTS_SETTING)))
+    }
+
+    /// Parse an http or https URL whose host is in `allowed_hosts`
+    fn check_url(url: &str, allowed_hosts: &[String]) -> Result<Url, Box<dyn Error>> {
+        let url = Url::parse(url).map_err(|e| PluginError::invalid_input(format!("invalid url '{}': {}", url, e)))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(PluginError::invalid_input(format!("unsupported url scheme '{}', expected http or https", url.scheme())).into());
+        }
+
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        if !allowed_hosts.contains(&host) {
+            let message = format!("host '{}' is not in the plugin's {} setting", host, ALLOWED_HOSTS_SETTING);
+            return Err(PluginExecutionError::new("host_not_allowed", message).into());
+        }
+        Ok(url)
+    }
+
+    /// GET `url`, returning the status code and body
+    ///
+    /// Redirects are not followed, since their target has not been checked
+    /// against the allowlist.
+    fn get(url: Url) -> Result<(u16, String), PluginError> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| PluginError::execution_failed(format!("failed to create HTTP client: {}", e)))?;
+
+        let response = client
+            .get(url.clone())
+            .send()
+            .map_err(|e| PluginError::network_error(format!("GET {} failed: {}", url, e)))?;
+        let status = response.status().as_u16();
+        let body = response
+            .text()
+            .map_err(|e| PluginError::network_error(format!("failed to read response from {}: {}", url, e)))?;
+        Ok((status, body))
+    }
+
+    fn run(&self, input: &str, allowed_hosts: &[String]) -> Result<String, Box<dyn Error>> {
+        let (operation, parsed) = parse_operation_with_example(input, EXAMPLE_INPUT)?;
+
+        let (url, status, body) = match operation.as_str() {
+            "get" => {
+                let url = Self::check_url(require_str(&parsed, "url")?, allowed_hosts)?;
+                let (status, body) = Self::get(url.clone())?;
+                (url, status, body)
+            }
+            _ => return Err(format!("Unknown operation: {}. Supported operations: get", operation).into()),
+        };
+
+        let response = serde_json::json!({
+            "operation": operation,
+            "url": url.as_str(),
+            "status": status,
+            "output": body
+        });
+
+        Ok(response.to_string())
+    }
+}
+
+impl Plugin for PluginHttp {
+    fn name(&self) -> &str {
+        "plugin_http"
+    }
+
+    fn version(&self) -> &str {
+        "0.1.0"
+    }
+
+    fn description(&self) -> &str {
+        "HTTP proxy plugin fetching URLs from hosts allowed by its allowed_hosts setting (get)"
+    }
+
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![("Fetch a page".to_string(), EXAMPLE_INPUT.to_string())]
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "operation": { "enum": ["get"] },
+                "url": { "type": "string", "description": "http or https URL on a host in allowed_hosts" }
+            },
+            "required": ["operation", "url"]
+        }))
+    }
+
+    /// Without a context no hosts are allowed
+    fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
+        self.run(input, &[])
+    }
+
+    fn execute_with_context(&self, input: &str, ctx: &ExecutionContext) -> Result<String, Box<dyn Error>> {
+        self.run(input, &Self::allowed_hosts(ctx)?)
+    }
+}
+
+register_plugin!(PluginHttp);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Serve a fixed response to every connection, counting the connections
+    fn mock_server(body: &'static str) -> (u16, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (port, connections)
+    }
+
+    fn context(allowed_hosts: serde_json::Value) -> ExecutionContext {
+        ExecutionContext::new("plugin_http").with_settings(HashMap::from([(ALLOWED_HOSTS_SETTING.to_string(), allowed_hosts)]))
+    }
+
+    #[test]
+    fn test_get_from_allowed_host() {
+        let (port, connections) = mock_server("hello from mock");
+        let input = format!(r#"{{"operation": "get", "url": "http://127.0.0.1:{}/greeting"}}"#, port);
+
+        let result = PluginHttp::new().execute_with_context(&input, &context(serde_json::json!(["127.0.0.1"]))).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(parsed["status"], 200);
+        assert_eq!(parsed["output"], "hello from mock");
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_blocked_host_is_never_contacted() {
+        let (port, connections) = mock_server("secret");
+        let input = format!(r#"{{"operation": "get", "url": "http://localhost:{}/"}}"#, port);
+        let plugin = PluginHttp::new();
+
+        for ctx in [context(serde_json::json!(["127.0.0.1"])), ExecutionContext::new("plugin_http")] {
+            let error = plugin.execute_with_context(&input, &ctx).unwrap_err();
+            let typed = error.downcast_ref::<PluginExecutionError>().unwrap();
+            assert_eq!(typed.code, "host_not_allowed");
+        }
+        assert!(plugin.execute(&input).is_err());
+        assert_eq!(connections.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_allowed_hosts_override_is_ignored() {
+        let (port, connections) = mock_server("secret");
+        let input = format!(r#"{{"operation": "get", "url": "http://127.0.0.1:{}/"}}"#, port);
+        let override_hosts = HashMap::from([(ALLOWED_HOSTS_SETTING.to_string(), serde_json::json!(["127.0.0.1"]))]);
+
+        for ctx in [
+            context(serde_json::json!(["example.com"])).with_settings_override(override_hosts.clone()),
+            ExecutionContext::new("plugin_http").with_settings_override(override_hosts),
+        ] {
+            assert_eq!(ctx.setting(ALLOWED_HOSTS_SETTING), Some(&serde_json::json!(["127.0.0.1"])));
+            let error = PluginHttp::new().execute_with_context(&input, &ctx).unwrap_err();
+            assert_eq!(error.downcast_ref::<PluginExecutionError>().unwrap().code, "host_not_allowed");
+        }
+        assert_eq!(connections.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_urls_and_settings() {
+        let plugin = PluginHttp::new();
+        let allowed = context(serde_json::json!(["127.0.0.1"]));
+        for (input, expected) in [
+            (r#"{"operation": "get", "url": "not a url"}"#, "invalid url"),
+            (r#"{"operation": "get", "url": "file:///etc/passwd"}"#, "unsupported url scheme"),
+            (r#"{"operation": "get"}"#, "url"),
+        ] {
+            let error = plugin.execute_with_context(input, &allowed).unwrap_err().to_string();
+            assert!(error.contains(expected), "{}", error);
+        }
+
+        let error = plugin
+            .execute_with_context(r#"{"operation": "get", "url": "http://127.0.0.1/"}"#, &context(serde_json::json!("127.0.0.1")))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("must be an array"), "{}", error);
+    }
+}
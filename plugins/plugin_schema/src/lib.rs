@@ -0,0 +1,173 @@
+use dyn_plug_core::plugin_prelude::parse_operation_with_example;
+use dyn_plug_core::{Plugin, PluginError, register_plugin};
+use serde_json::Value;
+use std::error::Error;
+
+/// Plugin Schema - JSON Schema Validation Plugin
+///
+/// This plugin checks documents against a JSON Schema supplied with the request:
+/// - validate: Report whether `data` is valid against `schema`, listing each violation
+///
+/// Input format: JSON with "operation", "data" and "schema" fields
+/// Example: {"operation": "validate", "data": {"age": 30}, "schema": {"type": "object"}}
+///
+/// Output: {"valid": true}, or {"valid": false, "errors": [{"path": ..., "message": ...}]}
+/// where each path is a JSON pointer into `data`.
+pub struct PluginSchema;
+
+/// Example input shown to callers who pass plain text
+const EXAMPLE_INPUT: &str = r#"{"operation":"validate","data":{"age":30},"schema":{"type":"object","properties":{"age":{"type":"integer"}}}}"#;
+
+impl Default for PluginSchema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PluginSchema {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Get a field that must be present, though it may be `null`
+    fn require_value<'a>(parsed: &'a Value, field: &str) -> Result<&'a Value, PluginError> {
+        parsed
+            .get(field)
+            .ok_or_else(|| PluginError::invalid_input(format!("missing required field '{}'", field)))
+    }
+
+    /// Validate `data` against `schema`, collecting every violation
+    fn validate(data: &Value, schema: &Value) -> Result<Value, PluginError> {
+        let validator = jsonschema::validator_for(schema)
+            .map_err(|e| PluginError::invalid_input(format!("invalid schema: {}", e)))?;
+
+        let errors: Vec<Value> = validator
+            .iter_errors(data)
+            .map(|error| {
+                serde_json::json!({
+                    "path": error.instance_path().to_string(),
+                    "message": error.to_string()
+                })
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(serde_json::json!({ "valid": true }))
+        } else {
+            Ok(serde_json::json!({ "valid": false, "errors": errors }))
+        }
+    }
+}
+
+impl Plugin for PluginSchema {
+    fn name(&self) -> &str {
+        "plugin_schema"
+    }
+
+    fn version(&self) -> &str {
+        "0.1.0"
+    }
+
+    fn description(&self) -> &str {
+        "JSON Schema plugin validating documents against a schema given with each request (validate)"
+    }
+
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![
+            ("Validate a document".to_string(), EXAMPLE_INPUT.to_string()),
+            (
+                "Report a missing field".to_string(),
+                r#"{"operation":"validate","data":{},"schema":{"type":"object","required":["name"]}}"#.to_string(),
+            ),
+        ]
+    }
+
+    fn input_schema(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "operation": { "enum": ["validate"] },
+                "data": { "description": "Document to validate" },
+                "schema": { "type": ["object", "boolean"], "description": "JSON Schema the document must satisfy" }
+            },
+            "required": ["operation", "data", "schema"]
+        }))
+    }
+
+    fn execute(&self, input: &str) -> Result<String, Box<dyn Error>> {
+        let (operation, parsed) = parse_operation_with_example(input, EXAMPLE_INPUT)?;
+
+        let result = match operation.as_str() {
+            "validate" => {
+                let data = Self::require_value(&parsed, "data")?;
+                let schema = Self::require_value(&parsed, "schema")?;
+                Self::validate(data, schema)?
+            }
+            _ => return Err(format!("Unknown operation: {}. Supported operations: validate", operation).into()),
+        };
+
+        Ok(result.to_string())
+    }
+}
+
+register_plugin!(PluginSchema);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(data: Value, schema: Value) -> Result<Value, String> {
+        let input = serde_json::json!({"operation": "validate", "data": data, "schema": schema}).to_string();
+        PluginSchema::new()
+            .execute(&input)
+            .map(|result| serde_json::from_str(&result).unwrap())
+            .map_err(|e| e.to_string())
+    }
+
+    fn person_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer", "minimum": 0 },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            }
+        })
+    }
+
+    #[test]
+    fn test_valid_document() {
+        let result = validate(serde_json::json!({"name": "Ada", "age": 36, "tags": ["math"]}), person_schema()).unwrap();
+        assert_eq!(result, serde_json::json!({"valid": true}));
+    }
+
+    #[test]
+    fn test_invalid_document_reports_error_paths() {
+        let result = validate(serde_json::json!({"age": -1, "tags": ["ok", 7]}), person_schema()).unwrap();
+        assert_eq!(result["valid"], false);
+
+        let mut paths: Vec<&str> = result["errors"].as_array().unwrap().iter().map(|e| e["path"].as_str().unwrap()).collect();
+        paths.sort();
+        assert_eq!(paths, ["", "/age", "/tags/1"]);
+        assert!(result["errors"].as_array().unwrap().iter().all(|e| !e["message"].as_str().unwrap().is_empty()));
+    }
+
+    #[test]
+    fn test_malformed_schema_is_rejected() {
+        let error = validate(serde_json::json!({}), serde_json::json!({"type": "not-a-type"})).unwrap_err();
+        assert!(error.contains("invalid schema"), "{}", error);
+
+        let error = PluginSchema::new().execute(r#"{"operation": "validate", "data": {}}"#).unwrap_err().to_string();
+        assert!(error.contains("missing required field 'schema'"), "{}", error);
+    }
+
+    #[test]
+    fn test_examples_execute() {
+        let plugin = PluginSchema::new();
+        for (description, input) in plugin.examples() {
+            assert!(plugin.execute(&input).is_ok(), "example '{}' failed", description);
+        }
+    }
+}
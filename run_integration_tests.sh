@@ -81,6 +81,9 @@ echo "Building plugins..."
 cargo build --release --package plugin_a
 cargo build --release --package plugin_b  
 cargo build --release --package plugin_c
+cargo build --release --package plugin_e
+cargo build --release --package plugin_http
+cargo build --release --package plugin_schema
 echo "Plugins built successfully"
 EOF
         chmod +x build_plugins.sh
@@ -1,14 +1,41 @@
 use actix_web::{
-    web, App, HttpResponse, HttpServer, Result as ActixResult, middleware::Logger,
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    middleware::{from_fn, Next},
+    web, App, HttpMessage, HttpRequest, HttpResponse, HttpServer, Result as ActixResult,
 };
-use dyn_plug_core::{PluginManager, PluginError};
+use dyn_plug_core::{AccessLogFormat, Config, ExecStatus, ExecutionOptions, ExecutionPriority, ExecutionQueue, PluginManager, PluginError, PluginLoadFailure, ResponseStyle};
 use log::{info, error, warn, debug};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+use crate::idempotency::{CachedResponse, IdempotencyCache, Lookup};
+
+/// Request header carrying a client-chosen idempotency key
+pub const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+
+/// Request header carrying the request id, echoed in the response and the access log
+pub const REQUEST_ID: &str = "X-Request-Id";
+
+/// Longest client-sent request id that is kept
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Request header carrying the execution priority: `low`, `normal` or `high`
+pub const PRIORITY_HEADER: &str = "X-Priority";
+
+/// Request header carrying the caller's deadline, as RFC 3339 or milliseconds from now
+pub const DEADLINE_HEADER: &str = "X-Deadline";
+
+/// Log target of access log records
+pub const ACCESS_LOG_TARGET: &str = "dyn_plug::access";
+
+/// Seconds clients are told to wait when executions are rejected as overloaded
+const RETRY_AFTER_SECS: &str = "1";
 
 /// API response wrapper for consistent response format
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -35,19 +62,272 @@ impl<T> ApiResponse<T> {
     }
 }
 
+/// Sets an `ApiResponse` as a response body in the app's `ResponseStyle`
+pub trait ApiJson {
+    /// With `bare`, a successful response carries only its `data` and a failed one
+    /// only `{"error": ...}`; with `wrapped`, the whole `ApiResponse`.
+    fn api_json<T: Serialize>(&mut self, req: &HttpRequest, response: ApiResponse<T>) -> HttpResponse;
+}
+
+impl ApiJson for actix_web::HttpResponseBuilder {
+    fn api_json<T: Serialize>(&mut self, req: &HttpRequest, response: ApiResponse<T>) -> HttpResponse {
+        match req.app_data::<ResponseStyle>().copied().unwrap_or_default() {
+            ResponseStyle::Wrapped => self.json(response),
+            ResponseStyle::Bare if response.success => self.json(response.data),
+            ResponseStyle::Bare => self.json(serde_json::json!({ "error": response.error })),
+        }
+    }
+}
+
 /// Plugin execution request payload
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Default)]
 pub struct ExecuteRequest {
     #[serde(default)]
     pub input: String,
+    /// Plugin settings overridden for this execution only, never persisted
+    ///
+    /// Only keys in the plugin's `overridable_settings` are accepted; others get `400`.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub settings: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// Batch execution request payload
+#[derive(Deserialize, Serialize, Default)]
+pub struct BatchExecuteRequest {
+    pub inputs: Vec<String>,
+    /// Run each distinct input once and copy its result to every position it appears at
+    ///
+    /// Only safe for pure plugins, whose output depends on nothing but the input.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deduplicate: bool,
 }
 
-/// Plugin execution result
+/// Outcome of a single input in a batch execution
 #[derive(Serialize)]
-pub struct ExecutionResult {
-    pub plugin: String,
+pub struct BatchItemResult {
+    pub success: bool,
+    /// Plugin output, or the error message when the input failed
     pub output: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_category: Option<String>,
+}
+
+/// Batch execution response, with one result per input in request order
+#[derive(Serialize)]
+pub struct BatchExecuteResponse {
+    pub plugin: String,
+    pub results: Vec<BatchItemResult>,
+    pub duration_ms: u64,
+}
+
+/// Request to compare two plugins' outputs on the same input
+#[derive(Deserialize, Serialize, Default)]
+pub struct DiffRequest {
+    pub left: String,
+    pub right: String,
+    #[serde(default)]
+    pub input: String,
+}
+
+/// Example input for a plugin
+#[derive(Serialize)]
+pub struct PluginExample {
+    pub description: String,
+    pub input: String,
+}
+
+/// Input and output JSON Schemas published by a plugin
+#[derive(Serialize)]
+pub struct PluginSchemas {
+    pub input: Option<serde_json::Value>,
+    pub output: Option<serde_json::Value>,
+}
+
+/// Per-plugin execution and payload size statistics
+#[derive(Serialize)]
+pub struct PluginStats {
+    pub plugin: String,
+    pub executions: u64,
+    pub failures: u64,
+    pub avg_input_bytes: f64,
+    pub max_input_bytes: u64,
+    pub avg_output_bytes: f64,
+    pub max_output_bytes: u64,
+}
+
+/// Server build and configuration details for support diagnostics
+#[derive(Serialize)]
+pub struct ServerInfo {
+    pub version: &'static str,
+    pub target: &'static str,
+    pub git_hash: &'static str,
+    pub plugin_count: usize,
+    pub config: ConfigSummary,
+}
+
+/// Subset of the effective configuration reported by `/api/v1/info`
+#[derive(Serialize)]
+pub struct ConfigSummary {
+    pub plugins_dir: String,
+    pub log_level: String,
+}
+
+/// Query parameters accepted by the execute endpoint
+#[derive(Deserialize, Serialize, Default)]
+pub struct ExecuteQuery {
+    /// Embed plugin output as structured JSON when it parses as JSON
+    #[serde(default)]
+    pub parse_output: bool,
+    /// Return only the plugin output (or error message) as `text/plain`
+    #[serde(default)]
+    pub raw: bool,
+    /// Semver requirement the loaded plugin version must satisfy, e.g. `^0.1`
+    pub version: Option<String>,
+    /// Return the plugin's named outputs from `execute_multi` as a JSON object
+    #[serde(default)]
+    pub multi: bool,
+}
+
+/// Plugin execution response
+///
+/// `output` is the plugin's output string, or the parsed JSON value when the
+/// client asked for structured output and the plugin produced valid JSON.
+#[derive(Serialize)]
+pub struct ExecuteResponse {
+    pub plugin: String,
+    pub output: serde_json::Value,
     pub duration_ms: u64,
+    /// Time spent waiting for the plugin manager before execution started
+    pub queue_ms: u64,
+    /// Time spent in the plugin call itself
+    pub exec_ms: u64,
+    /// Set when `output` was cut to the server's `max_response_output_bytes`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Set when the plugin succeeded with empty output
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub output_empty: bool,
+    /// Status the plugin reported for its output; `rejected` is returned with `422`
+    #[serde(skip_serializing_if = "ExecStatus::is_ok")]
+    pub status: ExecStatus,
+}
+
+/// Response of the execute endpoint with `?multi=true`
+#[derive(Serialize)]
+pub struct MultiExecuteResponse {
+    pub plugin: String,
+    /// Named outputs of the plugin, sorted by name
+    pub outputs: std::collections::BTreeMap<String, String>,
+    pub duration_ms: u64,
+}
+
+/// JSON body extractor configuration for the API
+///
+/// When `strict` is set, bodies without a JSON content type are rejected with
+/// `415 Unsupported Media Type`; otherwise any body that parses as JSON is accepted.
+pub fn json_config(strict: bool) -> web::JsonConfig {
+    if !strict {
+        return web::JsonConfig::default().content_type_required(false);
+    }
+    
+    web::JsonConfig::default()
+        .content_type_required(true)
+        .error_handler(|err, req| {
+            if matches!(err, actix_web::error::JsonPayloadError::ContentType) {
+                warn!("API: Rejected request to {} with unsupported content type (category: unsupported_media_type)", req.path());
+                let response = HttpResponse::UnsupportedMediaType()
+                    .api_json(req, ApiResponse::<()>::error("Content-Type must be application/json".to_string()));
+                return actix_web::error::InternalError::from_response(err, response).into();
+            }
+            err.into()
+        })
+}
+
+/// Check whether the request's `Accept` header admits a JSON response
+fn accepts_json(req: &HttpRequest) -> bool {
+    match req.headers().get(actix_web::http::header::ACCEPT).and_then(|v| v.to_str().ok()) {
+        None => true,
+        Some(accept) => accept.split(',').any(|media| {
+            let media = media.split(';').next().unwrap_or("").trim();
+            media == "application/json" || media == "application/*" || media == "*/*"
+        }),
+    }
+}
+
+/// Content type of execute responses, which are plain text in raw mode
+fn execute_content_type(raw: bool) -> actix_web::http::header::ContentType {
+    if raw {
+        actix_web::http::header::ContentType::plaintext()
+    } else {
+        actix_web::http::header::ContentType::json()
+    }
+}
+
+/// Build an execute error response, as a bare message in raw mode
+fn execute_error(req: &HttpRequest, status: actix_web::http::StatusCode, message: String, raw: bool) -> HttpResponse {
+    if raw {
+        HttpResponse::build(status).content_type(execute_content_type(true)).body(message)
+    } else {
+        HttpResponse::build(status).api_json(req, ApiResponse::<()>::error(message))
+    }
+}
+
+/// Cut output to at most `max_bytes` on a character boundary, reporting whether it was cut
+fn truncate_output(output: String, max_bytes: Option<usize>) -> (String, bool) {
+    match max_bytes {
+        Some(max_bytes) if output.len() > max_bytes => {
+            (crate::prefix_at_char_boundary(&output, max_bytes).to_string(), true)
+        }
+        _ => (output, false),
+    }
+}
+
+/// Build the response output, parsing it as JSON when requested and possible
+fn response_output(output: String, parse: bool) -> serde_json::Value {
+    if parse {
+        match serde_json::from_str(&output) {
+            Ok(value) => return value,
+            Err(e) => debug!("API: Plugin output is not JSON, returning it as a string: {}", e),
+        }
+    }
+    serde_json::Value::String(output)
+}
+
+/// Query parameters for the plugin list endpoint
+///
+/// Every provided filter must match; unknown parameters are ignored.
+#[derive(Deserialize, Default)]
+pub struct ListPluginsQuery {
+    pub enabled: Option<bool>,
+    pub loaded: Option<bool>,
+    /// Case-insensitive substring the plugin name must contain
+    pub name_contains: Option<String>,
+}
+
+impl ListPluginsQuery {
+    fn matches(&self, plugin: &PluginInfo) -> bool {
+        self.enabled.is_none_or(|enabled| plugin.enabled == enabled)
+            && self.loaded.is_none_or(|loaded| plugin.loaded == loaded)
+            && self.name_contains.as_deref().is_none_or(|needle| {
+                plugin.name.to_lowercase().contains(&needle.to_lowercase())
+            })
+    }
+}
+
+/// Query parameters for the recent executions endpoint
+#[derive(Deserialize)]
+pub struct RecentExecutionsQuery {
+    /// Only return executions strictly after this RFC 3339 timestamp
+    pub since: Option<String>,
+    /// Maximum number of most recent executions to return
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for the log stream endpoint
+#[derive(Deserialize, Default)]
+pub struct LogStreamQuery {
+    /// Only stream records at this level or more severe (e.g. `warn`)
+    pub level: Option<String>,
 }
 
 /// Plugin information for API responses
@@ -60,13 +340,193 @@ pub struct PluginInfo {
     pub loaded: bool,
 }
 
+/// Description of a single HTTP endpoint
+#[derive(Serialize)]
+pub struct EndpointDoc {
+    pub method: &'static str,
+    pub path: &'static str,
+    pub description: &'static str,
+}
+
+/// Endpoints served by the API, used for `GET /` and the startup banner
+pub const ENDPOINTS: &[EndpointDoc] = &[
+    EndpointDoc { method: "GET", path: "/", description: "List available endpoints" },
+    EndpointDoc { method: "GET", path: "/health", description: "Health check" },
+    EndpointDoc { method: "GET", path: "/api/v1/info", description: "Server version, build, and configuration summary" },
+    EndpointDoc { method: "GET", path: "/api/v1/config", description: "File configuration, without environment overrides, with secrets redacted (API key if configured)" },
+    EndpointDoc { method: "PUT", path: "/api/v1/config", description: "Validate, persist and apply a new configuration (API key if configured)" },
+    EndpointDoc { method: "GET", path: "/api/v1/plugins", description: "List plugins (?enabled=&loaded=&name_contains=)" },
+    EndpointDoc { method: "GET", path: "/api/v1/operations", description: "Operations declared by each plugin" },
+    EndpointDoc { method: "POST", path: "/api/v1/plugins/{name}/execute", description: "Execute plugin (?version=<semver requirement>)" },
+    EndpointDoc { method: "POST", path: "/api/v1/plugins/{name}/execute_batch", description: "Execute plugin over several inputs in one call" },
+    EndpointDoc { method: "POST", path: "/api/v1/plugins/diff", description: "Run two plugins on the same input and compare outputs" },
+    EndpointDoc { method: "PUT", path: "/api/v1/plugins/{name}/enable", description: "Enable plugin" },
+    EndpointDoc { method: "PUT", path: "/api/v1/plugins/{name}/disable", description: "Disable plugin" },
+    EndpointDoc { method: "POST", path: "/api/v1/plugins/{name}/reload-settings", description: "Re-read a plugin's settings from the config file" },
+    EndpointDoc { method: "POST", path: "/api/v1/plugins/{name}/toggle", description: "Toggle plugin" },
+    EndpointDoc { method: "GET", path: "/api/v1/plugins/{name}/examples", description: "Plugin example inputs" },
+    EndpointDoc { method: "GET", path: "/api/v1/plugins/{name}/schema", description: "Plugin input and output schemas" },
+    EndpointDoc { method: "GET", path: "/api/v1/plugins/{name}/stats", description: "Plugin execution and payload size statistics" },
+    EndpointDoc { method: "ANY", path: "/api/v1/plugins/{name}/custom/{path}", description: "Custom route declared by the plugin (in process, no timeout or circuit breaker; non-GET refused when read-only)" },
+    EndpointDoc { method: "GET", path: "/metrics", description: "Execution metrics (Prometheus text format)" },
+    EndpointDoc { method: "GET", path: "/metrics/json", description: "Execution metrics as JSON" },
+    EndpointDoc { method: "POST", path: "/metrics/reset", description: "Reset execution metrics (API key if configured, refused when read-only)" },
+    EndpointDoc { method: "GET", path: "/api/v1/executions/recent", description: "Recent executions (?since=<rfc3339>&limit=N)" },
+    EndpointDoc { method: "GET", path: "/api/v1/logs/stream", description: "Stream server logs as Server-Sent Events (?level=, API key if configured)" },
+];
+
 /// Application state containing the plugin manager
 pub struct AppState {
     pub plugin_manager: Arc<Mutex<PluginManager>>,
+    pub idempotency: Arc<IdempotencyCache>,
+    pub gauges: Arc<ApiGauges>,
+    /// Limit on in-flight executions, beyond which execute requests get `429`
+    pub max_pending_executions: Option<usize>,
+    /// Admits executions to the plugin manager in priority order
+    pub execution_queue: Arc<ExecutionQueue>,
+}
+
+/// Live concurrency gauges reported by `/metrics`
+#[derive(Default)]
+pub struct ApiGauges {
+    /// Execute requests currently being handled, including those waiting for the manager
+    pub in_flight_executions: Arc<AtomicUsize>,
+    /// HTTP connections currently open
+    pub open_connections: Arc<AtomicUsize>,
+}
+
+impl ApiGauges {
+    /// Gauges in the Prometheus text format
+    fn to_prometheus(&self) -> String {
+        format!(
+            "# HELP dyn_plug_in_flight_executions Plugin executions currently in progress\n\
+             # TYPE dyn_plug_in_flight_executions gauge\n\
+             dyn_plug_in_flight_executions {}\n\
+             # HELP dyn_plug_open_connections HTTP connections currently open\n\
+             # TYPE dyn_plug_open_connections gauge\n\
+             dyn_plug_open_connections {}\n",
+            self.in_flight_executions.load(Ordering::SeqCst),
+            self.open_connections.load(Ordering::SeqCst),
+        )
+    }
+}
+
+/// Holds a gauge incremented for as long as the guard lives
+pub struct GaugeGuard(Arc<AtomicUsize>);
+
+impl GaugeGuard {
+    pub fn new(gauge: &Arc<AtomicUsize>) -> Self {
+        gauge.fetch_add(1, Ordering::SeqCst);
+        Self(gauge.clone())
+    }
+    
+    /// Increment the gauge unless it has already reached `limit`
+    pub fn try_new(gauge: &Arc<AtomicUsize>, limit: Option<usize>) -> Option<Self> {
+        let Some(limit) = limit else {
+            return Some(Self::new(gauge));
+        };
+        gauge
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| (current < limit).then_some(current + 1))
+            .ok()
+            .map(|_| Self(gauge.clone()))
+    }
+}
+
+impl Drop for GaugeGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Fields of an access log record
+#[derive(Serialize)]
+struct AccessLogRecord<'a> {
+    request_id: &'a str,
+    method: &'a str,
+    path: &'a str,
+    plugin: Option<&'a str>,
+    status: u16,
+    duration_ms: u64,
+}
+
+impl AccessLogRecord<'_> {
+    fn format(&self, format: AccessLogFormat) -> String {
+        match format {
+            AccessLogFormat::Json => serde_json::to_string(self).unwrap_or_default(),
+            _ => format!(
+                "request_id={} method={} path={} plugin={} status={} duration_ms={}",
+                self.request_id, self.method, self.path, self.plugin.unwrap_or("-"), self.status, self.duration_ms
+            ),
+        }
+    }
+}
+
+/// Request id assigned by `access_log`, stored in the request extensions for handlers
+#[derive(Clone)]
+pub struct RequestId(pub String);
+
+/// Use the client's request id when it sent a usable one, otherwise generate one
+fn request_id(req: &ServiceRequest) -> String {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    
+    match req.headers().get(REQUEST_ID).and_then(|v| v.to_str().ok()) {
+        Some(id) if is_usable_request_id(id) => id.to_string(),
+        _ => format!("{:x}-{:x}", chrono::Utc::now().timestamp_millis(), NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+    }
+}
+
+/// Whether a client-sent request id is short and plain enough to log and echo as is
+fn is_usable_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+}
+
+/// Middleware that tags each request with a request id and emits an access log record
+///
+/// The format is read from the app's `AccessLogFormat` data, defaulting to text.
+pub async fn access_log(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse, actix_web::Error> {
+    let format = req.app_data::<AccessLogFormat>().copied().unwrap_or_default();
+    let request_id = request_id(&req);
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+    let method = req.method().to_string();
+    let path = req.path().to_string();
+    let start_time = Instant::now();
+    
+    let result = next.call(req).await;
+    let (status, plugin) = match &result {
+        Ok(res) => (res.status(), res.request().match_info().get("name").map(str::to_string)),
+        Err(e) => (e.as_response_error().status_code(), None),
+    };
+    
+    if format != AccessLogFormat::Off {
+        let record = AccessLogRecord {
+            request_id: &request_id,
+            method: &method,
+            path: &path,
+            plugin: plugin.as_deref(),
+            status: status.as_u16(),
+            duration_ms: start_time.elapsed().as_millis() as u64,
+        };
+        info!(target: ACCESS_LOG_TARGET, "{}", record.format(format));
+    }
+    
+    let mut res = result?.map_into_boxed_body();
+    if let Ok(value) = actix_web::http::header::HeaderValue::from_str(&request_id) {
+        res.headers_mut().insert(actix_web::http::header::HeaderName::from_static("x-request-id"), value);
+    }
+    Ok(res)
 }
 
 /// GET /plugins - List all plugins with their status
-pub async fn list_plugins(data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+pub async fn list_plugins(
+    req: HttpRequest,
+    query: web::Query<ListPluginsQuery>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
     let start_time = Instant::now();
     info!("API: Listing all plugins");
     
@@ -75,7 +535,7 @@ pub async fn list_plugins(data: web::Data<AppState>) -> ActixResult<HttpResponse
         Err(e) => {
             error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
             return Ok(HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error("Internal server error".to_string())));
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
         }
     };
     
@@ -89,90 +549,502 @@ pub async fn list_plugins(data: web::Data<AppState>) -> ActixResult<HttpResponse
             enabled: p.enabled && p.config_enabled,
             loaded: p.enabled,
         })
+        .filter(|p| query.matches(p))
         .collect();
     
     let duration = start_time.elapsed();
     info!("API: Found {} plugins in {}ms (category: list_success)", 
           plugin_infos.len(), duration.as_millis());
     
-    Ok(HttpResponse::Ok().json(ApiResponse::success(plugin_infos)))
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(plugin_infos)))
 }
 
 /// POST /plugins/{name}/execute - Execute a plugin
+///
+/// With `?parse_output=true` and an `Accept` header admitting JSON, output that
+/// parses as JSON is embedded as a structured value instead of a string.
+/// With `?raw=true` the response body is just the output, or the error message
+/// for non-2xx responses, as `text/plain`. Only JSON responses are subject to
+/// `server.max_response_output_bytes`, which marks cut output with `truncated: true`.
+/// With `?version=<semver requirement>` the loaded plugin version must satisfy the
+/// requirement, or the request fails with `409 Conflict`.
+/// With `?multi=true` the response carries the plugin's named outputs as an
+/// `outputs` object instead of a single `output`; `raw` and `parse_output` are ignored.
+///
+/// With an `Idempotency-Key` header, the response for that key is replayed with
+/// `X-Idempotent-Replay: true` until it expires; reusing the key for a different
+/// request, or while the first request is still executing, is rejected with
+/// `409 Conflict`. Server errors and `408 Request Timeout` responses are not cached.
+///
+/// An `X-Deadline` header, as RFC 3339 or milliseconds from now, bounds the execution
+/// by the time left; a deadline that has already passed fails with `408 Request Timeout`,
+/// as does an execution that runs past the deadline or the plugin's timeout.
+/// An `X-Priority` header of `low`, `normal` or `high` orders waiting executions.
+/// In read-only mode, requests carrying `settings` overrides are refused with `403`.
 pub async fn execute_plugin(
+    req: HttpRequest,
     path: web::Path<String>,
+    query: web::Query<ExecuteQuery>,
     payload: web::Json<ExecuteRequest>,
     data: web::Data<AppState>,
 ) -> ActixResult<HttpResponse> {
-    let start_time = Instant::now();
     let plugin_name = path.into_inner();
-    let input = &payload.input;
+    let Some(_in_flight) = GaugeGuard::try_new(&data.gauges.in_flight_executions, data.max_pending_executions) else {
+        warn!("API: Rejected execution of plugin '{}', too many pending executions (category: overloaded)", plugin_name);
+        let mut response = execute_error(
+            &req,
+            actix_web::http::StatusCode::TOO_MANY_REQUESTS,
+            "Too many pending executions, retry later".to_string(),
+            query.raw,
+        );
+        response.headers_mut().insert(
+            actix_web::http::header::RETRY_AFTER,
+            actix_web::http::header::HeaderValue::from_static(RETRY_AFTER_SECS),
+        );
+        return Ok(response);
+    };
+    let key = match req.headers().get(IDEMPOTENCY_KEY).map(|v| v.to_str()) {
+        None => return run_execute(&req, plugin_name, &query, &payload, &data).await,
+        Some(Ok(key)) => key.to_string(),
+        Some(Err(_)) => {
+            return Ok(HttpResponse::BadRequest()
+                .api_json(&req, ApiResponse::<()>::error("Invalid Idempotency-Key header".to_string())));
+        }
+    };
+    // Everything that shapes the response; the deadline and priority only decide whether it runs
+    let fingerprint = serde_json::json!({
+        "plugin": plugin_name,
+        "query": &*query,
+        "accepts_json": accepts_json(&req),
+        "body": &*payload,
+    })
+    .to_string();
+    
+    let reservation = match data.idempotency.reserve(&key, &fingerprint) {
+        Lookup::Reserved(reservation) => reservation,
+        Lookup::Replay(cached) => {
+            info!("API: Replaying response for idempotency key '{}' (category: idempotent_replay)", key);
+            return Ok(HttpResponse::build(cached.status)
+                .content_type(execute_content_type(query.raw))
+                .insert_header(("X-Idempotent-Replay", "true"))
+                .body(cached.body));
+        }
+        Lookup::Conflict => {
+            warn!("API: Idempotency key '{}' reused with a different request (category: idempotency_conflict)", key);
+            return Ok(HttpResponse::Conflict().api_json(&req, ApiResponse::<()>::error(format!(
+                "Idempotency key '{}' was already used with a different request", key
+            ))));
+        }
+        Lookup::InProgress => {
+            warn!("API: Idempotency key '{}' is held by a request still executing (category: idempotency_conflict)", key);
+            return Ok(HttpResponse::Conflict().api_json(&req, ApiResponse::<()>::error(format!(
+                "A request with idempotency key '{}' is still in progress", key
+            ))));
+        }
+    };
+    
+    let response = run_execute(&req, plugin_name, &query, &payload, &data).await?;
+    let status = response.status();
+    // Timeouts depend on the deadline and load, so a retry with the same key runs again
+    if status.is_server_error() || status == actix_web::http::StatusCode::REQUEST_TIMEOUT {
+        return Ok(response);
+    }
+    
+    let body = actix_web::body::to_bytes(response.into_body())
+        .await
+        .map_err(|e| actix_web::error::ErrorInternalServerError(e.to_string()))?;
+    reservation.store(CachedResponse { status, body: body.clone() });
+    
+    Ok(HttpResponse::build(status)
+        .content_type(execute_content_type(query.raw))
+        .body(body))
+}
+
+/// Time left until a deadline given as RFC 3339 or as milliseconds from now, such as `500ms`
+///
+/// Returns `Ok(None)` when the deadline has already passed.
+fn time_until_deadline(value: &str) -> Result<Option<Duration>, String> {
+    let value = value.trim();
+    let relative = value.strip_suffix("ms").unwrap_or(value);
+    if let Ok(ms) = relative.parse::<u64>() {
+        return Ok((ms > 0).then(|| Duration::from_millis(ms)));
+    }
+    
+    let deadline = chrono::DateTime::parse_from_rfc3339(value)
+        .map_err(|e| format!("'{}' is neither RFC 3339 nor milliseconds: {}", value, e))?;
+    Ok((deadline.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok().filter(|remaining| !remaining.is_zero()))
+}
+
+/// Execute a plugin and build the API response
+async fn run_execute(
+    req: &HttpRequest,
+    plugin_name: String,
+    query: &ExecuteQuery,
+    request: &ExecuteRequest,
+    data: &AppState,
+) -> ActixResult<HttpResponse> {
+    let start_time = Instant::now();
+    let input = request.input.as_str();
+    
+    let priority = req
+        .headers()
+        .get(PRIORITY_HEADER)
+        .map(|v| v.to_str().map_err(|e| e.to_string()).and_then(str::parse));
+    let priority = match priority {
+        None => ExecutionPriority::Normal,
+        Some(Ok(priority)) => priority,
+        Some(Err(e)) => {
+            return Ok(execute_error(
+                req,
+                actix_web::http::StatusCode::BAD_REQUEST,
+                format!("Invalid {} header: {}", PRIORITY_HEADER, e),
+                query.raw,
+            ));
+        }
+    };
+    
+    let deadline = req
+        .headers()
+        .get(DEADLINE_HEADER)
+        .map(|v| v.to_str().map_err(|e| e.to_string()).and_then(time_until_deadline));
+    let deadline = match deadline {
+        None => None,
+        Some(Ok(Some(remaining))) => Some(remaining),
+        Some(Ok(None)) => {
+            warn!("API: Deadline for plugin '{}' already passed, not executing (category: deadline_exceeded)", plugin_name);
+            return Ok(execute_error(
+                req,
+                actix_web::http::StatusCode::REQUEST_TIMEOUT,
+                "Deadline has already passed".to_string(),
+                query.raw,
+            ));
+        }
+        Some(Err(e)) => {
+            return Ok(execute_error(
+                req,
+                actix_web::http::StatusCode::BAD_REQUEST,
+                format!("Invalid {} header: {}", DEADLINE_HEADER, e),
+                query.raw,
+            ));
+        }
+    };
     
     info!("API: Executing plugin '{}' with input length: {}", plugin_name, input.len());
     debug!("API: Plugin '{}' input content: '{}'", plugin_name, 
            if input.len() > 100 { 
-               format!("{}...", &input[..100]) 
+               format!("{}...", crate::prefix_at_char_boundary(input, 100)) 
            } else { 
                input.to_string() 
            });
     
+    // Time spent waiting for a slot and the manager lock is reported as queue time
+    let mut options = ExecutionOptions::default()
+        .with_priority(priority)
+        .with_enqueued_at(start_time)
+        .with_settings_override(request.settings.clone());
+    if let Some(RequestId(request_id)) = req.extensions().get::<RequestId>() {
+        options = options.with_request_id(request_id.clone());
+    }
+    
+    // Higher-priority requests take the manager first when several are waiting; the
+    // wait happens on a blocking thread so other requests keep being served meanwhile
+    let queue = data.execution_queue.clone();
+    let _slot = match web::block(move || queue.acquire(options.priority)).await {
+        Ok(slot) => slot,
+        Err(e) => {
+            error!("API: Failed to wait for an execution slot: {} (category: queue_error)", e);
+            return Ok(execute_error(
+                req,
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error".to_string(),
+                query.raw,
+            ));
+        }
+    };
     let manager = match data.plugin_manager.lock() {
         Ok(manager) => manager,
         Err(e) => {
             error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
-            return Ok(HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error("Internal server error".to_string())));
+            return Ok(execute_error(
+                req,
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal server error".to_string(),
+                query.raw,
+            ));
         }
     };
     
-    match manager.execute_plugin(&plugin_name, input) {
+    // Overriding settings reconfigures the plugin for the request, so read-only mode refuses it
+    if !request.settings.is_empty() && manager.config().server.read_only {
+        warn!("API: Rejected settings override for plugin '{}' in read-only mode (category: forbidden)", plugin_name);
+        return Ok(execute_error(
+            req,
+            actix_web::http::StatusCode::FORBIDDEN,
+            "Server is read-only".to_string(),
+            query.raw,
+        ));
+    }
+    
+    if let Some(version_req) = query.version.as_deref() {
+        if let Err(e) = manager.check_plugin_version(&plugin_name, version_req) {
+            warn!("API: Version check for plugin '{}' failed: {} (category: {})", plugin_name, e, e.category());
+            let status_code = match &e {
+                PluginError::NotFound { .. } => actix_web::http::StatusCode::NOT_FOUND,
+                PluginError::VersionMismatch { .. } => actix_web::http::StatusCode::CONFLICT,
+                _ => actix_web::http::StatusCode::BAD_REQUEST,
+            };
+            return Ok(execute_error(req, status_code, e.user_friendly_message(), query.raw));
+        }
+    }
+    
+    options.timeout = manager.execution_options(&plugin_name).timeout;
+    if let Some(remaining) = deadline {
+        // A deadline can only shorten the plugin's own timeout
+        let remaining = remaining.saturating_sub(start_time.elapsed());
+        let timeout = options.timeout.map_or(remaining, |timeout| timeout.min(remaining));
+        options = options.with_timeout(timeout);
+    }
+    if query.multi {
+        return Ok(run_execute_multi(req, &manager, plugin_name, input, options, start_time));
+    }
+    match manager.execute_plugin_with_options(&plugin_name, input, options) {
         Ok(result) => {
             let api_duration = start_time.elapsed();
             if result.success {
                 info!("API: Plugin '{}' executed successfully in {}ms (API overhead: {}ms, category: execute_success)", 
                       plugin_name, result.duration_ms, api_duration.as_millis().saturating_sub(result.duration_ms as u128));
-                let execution_result = ExecutionResult {
+                // A rejection is a handled result rather than an error, so it keeps a success body
+                let http_status = match result.status {
+                    ExecStatus::Rejected => actix_web::http::StatusCode::UNPROCESSABLE_ENTITY,
+                    ExecStatus::Ok | ExecStatus::Warning => actix_web::http::StatusCode::OK,
+                };
+                if query.raw {
+                    return Ok(HttpResponse::build(http_status)
+                        .content_type(execute_content_type(true))
+                        .body(result.output));
+                }
+                let output_empty = result.output.is_empty();
+                let (output, truncated) = truncate_output(result.output, manager.config().server.max_response_output_bytes);
+                if truncated {
+                    debug!("API: Truncated output of plugin '{}' to {} bytes", plugin_name, output.len());
+                }
+                let execution_result = ExecuteResponse {
                     plugin: plugin_name,
-                    output: result.output,
+                    // Truncated output is no longer valid JSON, so it stays a string
+                    output: response_output(output, !truncated && query.parse_output && accepts_json(req)),
                     duration_ms: result.duration_ms,
+                    queue_ms: result.queue_ms,
+                    exec_ms: result.exec_ms,
+                    truncated,
+                    output_empty,
+                    status: result.status,
                 };
-                Ok(HttpResponse::Ok().json(ApiResponse::success(execution_result)))
+                Ok(HttpResponse::build(http_status).api_json(req, ApiResponse::success(execution_result)))
             } else {
                 warn!("API: Plugin '{}' execution failed in {}ms: {} (category: execute_failed)", 
                       plugin_name, result.duration_ms, result.output);
-                Ok(HttpResponse::BadRequest()
-                    .json(ApiResponse::<()>::error(format!("Plugin execution failed: {}", result.output))))
+                // Timeouts are reported as 408 so they are never cached for idempotent retries
+                let status_code = if result.error_category.as_deref() == Some("timeout_error") {
+                    actix_web::http::StatusCode::REQUEST_TIMEOUT
+                } else {
+                    actix_web::http::StatusCode::BAD_REQUEST
+                };
+                Ok(execute_error(
+                    req,
+                    status_code,
+                    format!("Plugin execution failed: {}", result.output),
+                    query.raw,
+                ))
             }
         }
         Err(PluginError::NotFound { .. }) => {
             warn!("API: Plugin '{}' not found (category: not_found)", plugin_name);
-            Ok(HttpResponse::NotFound()
-                .json(ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
+            Ok(execute_error(
+                req,
+                actix_web::http::StatusCode::NOT_FOUND,
+                format!("Plugin '{}' not found", plugin_name),
+                query.raw,
+            ))
         }
         Err(PluginError::PluginDisabled { .. }) => {
             warn!("API: Plugin '{}' is disabled (category: plugin_disabled)", plugin_name);
-            Ok(HttpResponse::BadRequest()
-                .json(ApiResponse::<()>::error(format!("Plugin '{}' is disabled", plugin_name))))
+            Ok(execute_error(
+                req,
+                actix_web::http::StatusCode::BAD_REQUEST,
+                format!("Plugin '{}' is disabled", plugin_name),
+                query.raw,
+            ))
         }
         Err(e) => {
             error!("API: Failed to execute plugin '{}': {} (category: {})", plugin_name, e, e.category());
             
             let status_code = match &e {
                 PluginError::NotFound { .. } => actix_web::http::StatusCode::NOT_FOUND,
-                PluginError::PluginDisabled { .. } => actix_web::http::StatusCode::BAD_REQUEST,
+                PluginError::PluginDisabled { .. } | PluginError::InvalidInput { .. } => actix_web::http::StatusCode::BAD_REQUEST,
                 PluginError::TimeoutError { .. } => actix_web::http::StatusCode::REQUEST_TIMEOUT,
                 PluginError::ResourceExhausted { .. } => actix_web::http::StatusCode::SERVICE_UNAVAILABLE,
                 _ => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
             };
             
-            Ok(HttpResponse::build(status_code)
-                .json(ApiResponse::<()>::error(e.user_friendly_message())))
+            Ok(execute_error(req, status_code, e.user_friendly_message(), query.raw))
+        }
+    }
+}
+
+/// Execute a plugin through `execute_multi` and build the `?multi=true` response
+fn run_execute_multi(
+    req: &HttpRequest,
+    manager: &PluginManager,
+    plugin_name: String,
+    input: &str,
+    options: ExecutionOptions,
+    start_time: Instant,
+) -> HttpResponse {
+    match manager.execute_plugin_multi_with_options(&plugin_name, input, options) {
+        Ok(outputs) => {
+            info!("API: Plugin '{}' returned {} named outputs in {}ms (category: execute_success)",
+                  plugin_name, outputs.len(), start_time.elapsed().as_millis());
+            HttpResponse::Ok().api_json(req, ApiResponse::success(MultiExecuteResponse {
+                plugin: plugin_name,
+                outputs: outputs.into_iter().collect(),
+                duration_ms: start_time.elapsed().as_millis() as u64,
+            }))
+        }
+        Err(e) => {
+            warn!("API: Plugin '{}' multi-output execution failed: {} (category: {})", plugin_name, e, e.category());
+            let response = ApiResponse::<()>::error(e.user_friendly_message());
+            match &e {
+                PluginError::NotFound { .. } => HttpResponse::NotFound().api_json(req, response),
+                PluginError::TimeoutError { .. } => HttpResponse::RequestTimeout().api_json(req, response),
+                PluginError::ResourceExhausted { .. } => HttpResponse::ServiceUnavailable().api_json(req, response),
+                _ => HttpResponse::BadRequest().api_json(req, response),
+            }
+        }
+    }
+}
+
+/// POST /plugins/{name}/execute_batch - Execute a plugin over several inputs
+///
+/// Plugins that override `batch_execute` process the whole batch in one call.
+pub async fn execute_plugin_batch(
+    req: HttpRequest,
+    path: web::Path<String>,
+    payload: web::Json<BatchExecuteRequest>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let plugin_name = path.into_inner();
+    let _in_flight = GaugeGuard::new(&data.gauges.in_flight_executions);
+    let start_time = Instant::now();
+    info!("API: Executing plugin '{}' over a batch of {} inputs", plugin_name, payload.inputs.len());
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    let (inputs, positions) = if payload.deduplicate {
+        distinct_inputs(&payload.inputs)
+    } else {
+        (payload.inputs.clone(), (0..payload.inputs.len()).collect())
+    };
+    if inputs.len() < payload.inputs.len() {
+        debug!("API: Deduplicated batch for plugin '{}' from {} to {} inputs", plugin_name, payload.inputs.len(), inputs.len());
+    }
+    
+    match manager.execute_plugin_batch(&plugin_name, &inputs) {
+        Ok(results) => {
+            let results: Vec<BatchItemResult> = positions
+                .into_iter()
+                .map(|index| results[index].clone())
+                .map(|result| BatchItemResult {
+                    success: result.success,
+                    output: result.output,
+                    error_category: result.error_category,
+                })
+                .collect();
+            info!("API: Plugin '{}' executed a batch of {} inputs in {}ms (category: execute_success)",
+                  plugin_name, results.len(), start_time.elapsed().as_millis());
+            Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(BatchExecuteResponse {
+                plugin: plugin_name,
+                results,
+                duration_ms: start_time.elapsed().as_millis() as u64,
+            })))
+        }
+        Err(e) => {
+            warn!("API: Batch execution of plugin '{}' failed: {} (category: {})", plugin_name, e, e.category());
+            let response = ApiResponse::<()>::error(e.user_friendly_message());
+            Ok(match &e {
+                PluginError::NotFound { .. } => HttpResponse::NotFound().api_json(&req, response),
+                PluginError::PluginDisabled { .. } => HttpResponse::BadRequest().api_json(&req, response),
+                PluginError::ResourceExhausted { .. } => HttpResponse::ServiceUnavailable().api_json(&req, response),
+                _ => HttpResponse::InternalServerError().api_json(&req, response),
+            })
+        }
+    }
+}
+
+/// POST /plugins/diff - Run two plugins on the same input and compare their outputs
+///
+/// Responds `200` whether or not the outputs match; `matches` carries the result.
+pub async fn diff_plugins(
+    req: HttpRequest,
+    payload: web::Json<DiffRequest>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let _in_flight = GaugeGuard::new(&data.gauges.in_flight_executions);
+    info!("API: Comparing plugins '{}' and '{}'", payload.left, payload.right);
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    match crate::diff_exec::diff_plugins(&manager, &payload.left, &payload.right, &payload.input) {
+        Ok(diff) => {
+            info!("API: Plugins '{}' and '{}' {} (category: plugin_diff)",
+                  payload.left, payload.right, if diff.matches { "match" } else { "differ" });
+            Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(diff)))
+        }
+        Err(e) => {
+            warn!("API: Comparing plugins '{}' and '{}' failed: {} (category: {})", payload.left, payload.right, e, e.category());
+            let response = ApiResponse::<()>::error(e.user_friendly_message());
+            Ok(match &e {
+                PluginError::NotFound { .. } => HttpResponse::NotFound().api_json(&req, response),
+                PluginError::PluginDisabled { .. } => HttpResponse::BadRequest().api_json(&req, response),
+                PluginError::ResourceExhausted { .. } => HttpResponse::ServiceUnavailable().api_json(&req, response),
+                _ => HttpResponse::InternalServerError().api_json(&req, response),
+            })
         }
     }
 }
 
+/// Distinct inputs in order of first appearance, and for each input the index of its distinct value
+fn distinct_inputs(inputs: &[String]) -> (Vec<String>, Vec<usize>) {
+    let mut distinct = Vec::new();
+    let mut indices = std::collections::HashMap::new();
+    let positions = inputs
+        .iter()
+        .map(|input| {
+            *indices.entry(input.as_str()).or_insert_with(|| {
+                distinct.push(input.clone());
+                distinct.len() - 1
+            })
+        })
+        .collect();
+    (distinct, positions)
+}
+
 /// PUT /plugins/{name}/enable - Enable a plugin
 pub async fn enable_plugin(
+    req: HttpRequest,
     path: web::Path<String>,
     data: web::Data<AppState>,
 ) -> ActixResult<HttpResponse> {
@@ -186,22 +1058,26 @@ pub async fn enable_plugin(
         Err(e) => {
             error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
             return Ok(HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error("Internal server error".to_string())));
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
         }
     };
     
+    if let Some(response) = reject_if_read_only(&req, &manager) {
+        return Ok(response);
+    }
+    
     match manager.enable_plugin(&plugin_name) {
         Ok(()) => {
             let duration = start_time.elapsed();
             info!("API: Plugin '{}' enabled successfully in {}ms (category: enable_success)", 
                   plugin_name, duration.as_millis());
             Ok(HttpResponse::Ok()
-                .json(ApiResponse::success(format!("Plugin '{}' enabled successfully", plugin_name))))
+                .api_json(&req, ApiResponse::success(format!("Plugin '{}' enabled successfully", plugin_name))))
         }
         Err(PluginError::NotFound { .. }) => {
             warn!("API: Plugin '{}' not found (category: not_found)", plugin_name);
             Ok(HttpResponse::NotFound()
-                .json(ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
+                .api_json(&req, ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
         }
         Err(e) => {
             error!("API: Failed to enable plugin '{}': {} (category: {})", plugin_name, e, e.category());
@@ -213,13 +1089,14 @@ pub async fn enable_plugin(
             };
             
             Ok(HttpResponse::build(status_code)
-                .json(ApiResponse::<()>::error(e.user_friendly_message())))
+                .api_json(&req, ApiResponse::<()>::error(e.user_friendly_message())))
         }
     }
 }
 
 /// PUT /plugins/{name}/disable - Disable a plugin
 pub async fn disable_plugin(
+    req: HttpRequest,
     path: web::Path<String>,
     data: web::Data<AppState>,
 ) -> ActixResult<HttpResponse> {
@@ -233,22 +1110,26 @@ pub async fn disable_plugin(
         Err(e) => {
             error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
             return Ok(HttpResponse::InternalServerError()
-                .json(ApiResponse::<()>::error("Internal server error".to_string())));
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
         }
     };
     
+    if let Some(response) = reject_if_read_only(&req, &manager) {
+        return Ok(response);
+    }
+    
     match manager.disable_plugin(&plugin_name) {
         Ok(()) => {
             let duration = start_time.elapsed();
             info!("API: Plugin '{}' disabled successfully in {}ms (category: disable_success)", 
                   plugin_name, duration.as_millis());
             Ok(HttpResponse::Ok()
-                .json(ApiResponse::success(format!("Plugin '{}' disabled successfully", plugin_name))))
+                .api_json(&req, ApiResponse::success(format!("Plugin '{}' disabled successfully", plugin_name))))
         }
         Err(PluginError::NotFound { .. }) => {
             warn!("API: Plugin '{}' not found (category: not_found)", plugin_name);
             Ok(HttpResponse::NotFound()
-                .json(ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
+                .api_json(&req, ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
         }
         Err(e) => {
             error!("API: Failed to disable plugin '{}': {} (category: {})", plugin_name, e, e.category());
@@ -260,165 +1141,2439 @@ pub async fn disable_plugin(
             };
             
             Ok(HttpResponse::build(status_code)
-                .json(ApiResponse::<()>::error(e.user_friendly_message())))
+                .api_json(&req, ApiResponse::<()>::error(e.user_friendly_message())))
         }
     }
 }
 
-/// GET /health - Health check endpoint
-pub async fn health_check() -> ActixResult<HttpResponse> {
-    debug!("API: Health check requested (category: health_check)");
+/// POST /plugins/{name}/toggle - Flip a plugin's enabled state and return its new status
+pub async fn toggle_plugin(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let start_time = Instant::now();
+    let plugin_name = path.into_inner();
     
-    #[derive(Serialize)]
+    info!("API: Toggling plugin '{}'", plugin_name);
+    
+    // Hold the manager lock across the read-modify-write so concurrent toggles serialize
+    let mut manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    if let Some(response) = reject_if_read_only(&req, &manager) {
+        return Ok(response);
+    }
+    
+    match manager.toggle_plugin(&plugin_name) {
+        Ok(status) => {
+            let duration = start_time.elapsed();
+            info!("API: Plugin '{}' toggled to enabled={} in {}ms (category: toggle_success)", 
+                  plugin_name, status.enabled && status.config_enabled, duration.as_millis());
+            Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(status)))
+        }
+        Err(PluginError::NotFound { .. }) => {
+            warn!("API: Plugin '{}' not found (category: not_found)", plugin_name);
+            Ok(HttpResponse::NotFound()
+                .api_json(&req, ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
+        }
+        Err(e) => {
+            error!("API: Failed to toggle plugin '{}': {} (category: {})", plugin_name, e, e.category());
+            
+            let status_code = match &e {
+                PluginError::ConfigError { .. } => actix_web::http::StatusCode::BAD_REQUEST,
+                _ => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            
+            Ok(HttpResponse::build(status_code)
+                .api_json(&req, ApiResponse::<()>::error(e.user_friendly_message())))
+        }
+    }
+}
+
+/// POST /plugins/{name}/reload-settings - Re-read a plugin's settings from the config file
+///
+/// The plugin is notified through `on_config_changed` when its settings changed.
+/// Nothing else is reloaded. Responds with the settings now in effect.
+pub async fn reload_plugin_settings(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let plugin_name = path.into_inner();
+    info!("API: Reloading settings of plugin '{}'", plugin_name);
+    
+    let mut manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    if let Some(response) = reject_if_read_only(&req, &manager) {
+        return Ok(response);
+    }
+    
+    match manager.reload_plugin_settings(&plugin_name) {
+        Ok(settings) => {
+            info!("API: Settings of plugin '{}' reloaded (category: settings_reload)", plugin_name);
+            Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(settings)))
+        }
+        Err(PluginError::NotFound { .. }) => {
+            warn!("API: Plugin '{}' not found (category: not_found)", plugin_name);
+            Ok(HttpResponse::NotFound()
+                .api_json(&req, ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
+        }
+        Err(e) => {
+            error!("API: Failed to reload settings of plugin '{}': {} (category: {})", plugin_name, e, e.category());
+            Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error(e.user_friendly_message())))
+        }
+    }
+}
+
+/// GET /plugins/{name}/examples - List example inputs for a plugin
+pub async fn plugin_examples(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let plugin_name = path.into_inner();
+    info!("API: Listing examples for plugin '{}'", plugin_name);
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    match manager.get_plugin_examples(&plugin_name) {
+        Some(examples) => {
+            let examples: Vec<PluginExample> = examples
+                .into_iter()
+                .map(|(description, input)| PluginExample { description, input })
+                .collect();
+            debug!("API: Plugin '{}' has {} examples", plugin_name, examples.len());
+            Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(examples)))
+        }
+        None => {
+            warn!("API: Plugin '{}' not found (category: not_found)", plugin_name);
+            Ok(HttpResponse::NotFound()
+                .api_json(&req, ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
+        }
+    }
+}
+
+/// /plugins/{name}/custom/{path} - Call a route the plugin declares in `routes`
+///
+/// Any method is accepted; the plugin's route for the method and path receives the
+/// request body, or the query string when the body is empty. Unknown routes get `404`.
+/// Methods other than GET and HEAD are refused in read-only mode. Routes run in the
+/// host process while holding the plugin manager, without middleware, timeouts or
+/// the circuit breaker.
+pub async fn plugin_custom_route(
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    body: web::Bytes,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let (plugin_name, route_path) = path.into_inner();
+    let method = req.method().as_str();
+    info!("API: Calling custom route {} /{} of plugin '{}'", method, route_path, plugin_name);
+    
+    let input = if body.is_empty() {
+        req.query_string().to_string()
+    } else {
+        match String::from_utf8(body.to_vec()) {
+            Ok(input) => input,
+            Err(_) => {
+                return Ok(HttpResponse::BadRequest()
+                    .api_json(&req, ApiResponse::<()>::error("Request body must be UTF-8".to_string())));
+            }
+        }
+    };
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    if req.method() != actix_web::http::Method::GET && req.method() != actix_web::http::Method::HEAD {
+        if let Some(response) = reject_if_read_only(&req, &manager) {
+            return Ok(response);
+        }
+    }
+    
+    match manager.execute_plugin_route(&plugin_name, method, &route_path, &input) {
+        Ok(Some(output)) => Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(output))),
+        Ok(None) => {
+            warn!("API: Plugin '{}' has no route {} /{} (category: not_found)", plugin_name, method, route_path);
+            Ok(HttpResponse::NotFound().api_json(&req, ApiResponse::<()>::error(format!(
+                "Plugin '{}' has no route {} /{}", plugin_name, method, route_path
+            ))))
+        }
+        Err(e) => {
+            warn!("API: Custom route of plugin '{}' failed: {} (category: {})", plugin_name, e, e.category());
+            let response = ApiResponse::<()>::error(e.user_friendly_message());
+            Ok(match &e {
+                PluginError::NotFound { .. } => HttpResponse::NotFound().api_json(&req, response),
+                PluginError::ExecutionFailed { .. } | PluginError::PluginDisabled { .. } => HttpResponse::BadRequest().api_json(&req, response),
+                _ => HttpResponse::InternalServerError().api_json(&req, response),
+            })
+        }
+    }
+}
+
+/// GET /plugins/{name}/stats - Execution counts and input/output sizes for a plugin
+pub async fn plugin_stats(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let plugin_name = path.into_inner();
+    debug!("API: Stats requested for plugin '{}' (category: metrics)", plugin_name);
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    if !manager.has_plugin(&plugin_name) {
+        warn!("API: Plugin '{}' not found (category: not_found)", plugin_name);
+        return Ok(HttpResponse::NotFound()
+            .api_json(&req, ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))));
+    }
+    
+    let metrics = manager.metrics().snapshot().plugins.remove(&plugin_name).unwrap_or_default();
+    let stats = PluginStats {
+        plugin: plugin_name,
+        executions: metrics.executions,
+        failures: metrics.failures,
+        avg_input_bytes: metrics.avg_input_bytes(),
+        max_input_bytes: metrics.max_input_bytes,
+        avg_output_bytes: metrics.avg_output_bytes(),
+        max_output_bytes: metrics.max_output_bytes,
+    };
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(stats)))
+}
+
+/// GET /info - Server version, build details, and configuration summary
+pub async fn server_info(req: HttpRequest, data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    debug!("API: Server info requested (category: info)");
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    let config = manager.config();
+    let info = ServerInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        target: env!("BUILD_TARGET"),
+        git_hash: env!("GIT_HASH"),
+        plugin_count: manager.plugin_count(),
+        config: ConfigSummary {
+            plugins_dir: config.plugins_dir.to_string_lossy().to_string(),
+            log_level: config.log_level.clone(),
+        },
+    };
+    
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(info)))
+}
+
+/// GET /plugins/{name}/schema - Input and output JSON Schemas for a plugin
+pub async fn plugin_schema(
+    req: HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let plugin_name = path.into_inner();
+    info!("API: Fetching schemas for plugin '{}'", plugin_name);
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    let schemas = manager.get_plugin_schema(&plugin_name).and_then(|input| {
+        Ok(PluginSchemas { input, output: manager.get_plugin_output_schema(&plugin_name)? })
+    });
+    match schemas {
+        Ok(schemas) => Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(schemas))),
+        Err(e) => {
+            warn!("API: Plugin '{}' not found (category: {})", plugin_name, e.category());
+            Ok(HttpResponse::NotFound()
+                .api_json(&req, ApiResponse::<()>::error(format!("Plugin '{}' not found", plugin_name))))
+        }
+    }
+}
+
+/// GET /operations - Operations declared by each plugin, keyed by plugin name
+pub async fn list_operations(req: HttpRequest, data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    debug!("API: Listing plugin operations");
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(manager.list_operations())))
+}
+
+/// GET /executions/recent - List recent executions, optionally after a timestamp
+pub async fn recent_executions(
+    req: HttpRequest,
+    query: web::Query<RecentExecutionsQuery>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    info!("API: Listing recent executions (since: {:?}, limit: {:?})", query.since, query.limit);
+    
+    let since = match query.since.as_deref().map(chrono::DateTime::parse_from_rfc3339).transpose() {
+        Ok(since) => since.map(|since| since.with_timezone(&chrono::Utc)),
+        Err(e) => {
+            warn!("API: Invalid 'since' timestamp {:?}: {} (category: invalid_request)", query.since, e);
+            return Ok(HttpResponse::BadRequest()
+                .api_json(&req, ApiResponse::<()>::error(format!("Invalid 'since' timestamp, expected RFC 3339: {}", e))));
+        }
+    };
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    let executions = manager.recent_executions(since, query.limit);
+    debug!("API: Returning {} recent executions", executions.len());
+    
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(executions)))
+}
+
+/// GET /logs/stream - Stream server log records as Server-Sent Events
+///
+/// Log records include execution inputs, so the stream requires the API key when one is set.
+pub async fn stream_logs(
+    req: HttpRequest,
+    query: web::Query<LogStreamQuery>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    {
+        let manager = match data.plugin_manager.lock() {
+            Ok(manager) => manager,
+            Err(e) => {
+                error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+                return Ok(HttpResponse::InternalServerError()
+                    .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+            }
+        };
+        if let Some(response) = require_api_key(&req, &manager) {
+            return Ok(response);
+        }
+    }
+    
+    let min_level = match query.level.as_deref().map(str::parse::<log::Level>).transpose() {
+        Ok(level) => level.unwrap_or(log::Level::Trace),
+        Err(_) => {
+            warn!("API: Invalid log level {:?} (category: invalid_request)", query.level);
+            return Ok(HttpResponse::BadRequest()
+                .api_json(&req, ApiResponse::<()>::error(format!(
+                    "Invalid level '{}', expected one of: error, warn, info, debug, trace",
+                    query.level.as_deref().unwrap_or_default()
+                ))));
+        }
+    };
+    info!("API: Log stream subscriber connected (level: {}) (category: log_stream)", min_level);
+    
+    let events = futures_util::stream::unfold(crate::log_stream::subscribe(), move |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(event) if event.level <= min_level => {
+                    let json = serde_json::to_string(&event).unwrap_or_default();
+                    let frame = web::Bytes::from(format!("data: {}\n\n", json));
+                    return Some((Ok::<_, actix_web::Error>(frame), rx));
+                }
+                Ok(_) => continue,
+                // A slow subscriber misses records rather than stalling logging
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(events))
+}
+
+/// GET / - Describe the available endpoints
+pub async fn list_endpoints(req: HttpRequest) -> ActixResult<HttpResponse> {
+    debug!("API: Endpoint listing requested (category: endpoints)");
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(ENDPOINTS)))
+}
+
+/// Request header carrying the API key for administrative endpoints
+pub const API_KEY_HEADER: &str = "X-API-Key";
+
+/// Reject the request unless it carries the configured API key
+///
+/// Accepts the key in `X-API-Key` or as an `Authorization: Bearer` token.
+/// Returns `None` when no key is configured or the key matches.
+fn require_api_key(req: &HttpRequest, manager: &PluginManager) -> Option<HttpResponse> {
+    let expected = manager.config().server.api_key.as_deref()?;
+    let headers = req.headers();
+    let provided = headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(actix_web::http::header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        });
+    
+    if provided == Some(expected) {
+        return None;
+    }
+    
+    warn!("API: Rejected request to {} with missing or invalid API key (category: unauthorized)", req.path());
+    Some(HttpResponse::Unauthorized()
+        .api_json(req, ApiResponse::<()>::error("Missing or invalid API key".to_string())))
+}
+
+/// Refuse a mutation endpoint with `403 Forbidden` when the server runs read-only
+fn reject_if_read_only(req: &HttpRequest, manager: &PluginManager) -> Option<HttpResponse> {
+    if !manager.config().server.read_only {
+        return None;
+    }
+    
+    warn!("API: Rejected {} {} in read-only mode (category: forbidden)", req.method(), req.path());
+    Some(HttpResponse::Forbidden()
+        .api_json(req, ApiResponse::<()>::error("Server is read-only".to_string())))
+}
+
+/// GET /metrics - Execution metrics in the Prometheus text format
+///
+/// Gauges are read when the request arrives, before waiting for the plugin manager.
+pub async fn metrics_text(req: HttpRequest, data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    debug!("API: Metrics requested (category: metrics)");
+    let gauges = data.gauges.to_prometheus();
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    Ok(HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(manager.metrics().snapshot().to_prometheus() + &gauges))
+}
+
+/// GET /metrics/json - Execution metrics as structured JSON
+pub async fn metrics_json(req: HttpRequest, data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    debug!("API: JSON metrics requested (category: metrics)");
+    
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(manager.metrics().snapshot())))
+}
+
+/// Placeholder returned in place of secrets such as the API key
+pub const REDACTED: &str = "<redacted>";
+
+/// GET /config - The file configuration as JSON, with secrets redacted
+///
+/// Environment overrides are left out, so the body can be edited and sent back
+/// to `PUT /config` without persisting them.
+pub async fn get_config(req: HttpRequest, data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    if let Some(response) = require_api_key(&req, &manager) {
+        return Ok(response);
+    }
+    
+    debug!("API: Configuration requested (category: config)");
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(redacted_config(&manager.file_config()))))
+}
+
+/// PUT /config - Validate, persist and apply a new configuration
+///
+/// An `api_key` of `<redacted>`, as returned by `GET /config`, keeps the current key.
+/// Plugin enabled states and settings are re-synced; the server's own bind address
+/// and worker settings take effect on the next start.
+pub async fn put_config(
+    req: HttpRequest,
+    payload: web::Json<serde_json::Value>,
+    data: web::Data<AppState>,
+) -> ActixResult<HttpResponse> {
+    let mut manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    if let Some(response) = require_api_key(&req, &manager) {
+        return Ok(response);
+    }
+    if let Some(response) = reject_if_read_only(&req, &manager) {
+        return Ok(response);
+    }
+    
+    let mut config: Config = match serde_json::from_value(payload.into_inner()) {
+        Ok(config) => config,
+        Err(e) => {
+            warn!("API: Rejected configuration that does not parse: {} (category: config_error)", e);
+            return Ok(HttpResponse::BadRequest()
+                .api_json(&req, ApiResponse::<()>::error(format!("Invalid configuration: {}", e))));
+        }
+    };
+    if config.server.api_key.as_deref() == Some(REDACTED) {
+        config.server.api_key = manager.config().server.api_key.clone();
+    }
+    
+    match manager.replace_config(config) {
+        Ok(()) => {
+            info!("API: Configuration replaced (category: config_replaced)");
+            Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(redacted_config(&manager.file_config()))))
+        }
+        Err(e) => {
+            warn!("API: Rejected configuration: {} (category: {})", e, e.category());
+            Ok(HttpResponse::BadRequest().api_json(&req, ApiResponse::<()>::error(e.to_string())))
+        }
+    }
+}
+
+/// A copy of `config` with the API key replaced by `REDACTED`
+pub fn redacted_config(config: &Config) -> Config {
+    let mut config = config.clone();
+    if config.server.api_key.is_some() {
+        config.server.api_key = Some(REDACTED.to_string());
+    }
+    config
+}
+
+/// POST /metrics/reset - Zero the execution metrics, returning the values they held
+pub async fn reset_metrics(req: HttpRequest, data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    let manager = match data.plugin_manager.lock() {
+        Ok(manager) => manager,
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    if let Some(response) = require_api_key(&req, &manager) {
+        return Ok(response);
+    }
+    if let Some(response) = reject_if_read_only(&req, &manager) {
+        return Ok(response);
+    }
+    
+    let previous = manager.metrics().reset();
+    info!("API: Metrics reset after {} executions (category: metrics_reset)", previous.executions_total);
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(previous)))
+}
+
+/// GET /health - Health check endpoint
+///
+/// When `server.fail_health_on_load_errors` is set, any plugin load failure from
+/// the last scan reports the service as degraded with `503 Service Unavailable`.
+pub async fn health_check(req: HttpRequest, data: web::Data<AppState>) -> ActixResult<HttpResponse> {
+    debug!("API: Health check requested (category: health_check)");
+    
+    #[derive(Serialize)]
     struct HealthStatus {
         status: String,
         timestamp: String,
         version: String,
         uptime_ms: u64,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        load_failures: Vec<PluginLoadFailure>,
+    }
+    
+    // Simple uptime tracking (could be enhanced with actual process start time)
+    static START_TIME: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    let start_time = START_TIME.get_or_init(Instant::now);
+    let uptime = start_time.elapsed();
+    
+    let (load_failures, fail_on_load_errors) = match data.plugin_manager.lock() {
+        Ok(manager) => (manager.load_failures(), manager.config().server.fail_health_on_load_errors),
+        Err(e) => {
+            error!("API: Failed to acquire plugin manager lock: {} (category: lock_error)", e);
+            return Ok(HttpResponse::InternalServerError()
+                .api_json(&req, ApiResponse::<()>::error("Internal server error".to_string())));
+        }
+    };
+    
+    let degraded = fail_on_load_errors && !load_failures.is_empty();
+    
+    let health = HealthStatus {
+        status: if degraded { "degraded" } else { "healthy" }.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        uptime_ms: uptime.as_millis() as u64,
+        load_failures,
+    };
+    
+    if degraded {
+        warn!("API: Reporting degraded health, {} plugins failed to load (category: health_degraded)", 
+              health.load_failures.len());
+        return Ok(HttpResponse::ServiceUnavailable().api_json(&req, ApiResponse::success(health)));
+    }
+    
+    Ok(HttpResponse::Ok().api_json(&req, ApiResponse::success(health)))
+}
+
+
+
+/// Start the HTTP API server with graceful shutdown support
+pub async fn start_server(
+    plugin_manager: PluginManager,
+    host: &str,
+    port: u16,
+    mut shutdown_signal: tokio::sync::mpsc::Receiver<()>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!("Starting HTTP API server with graceful shutdown on {}:{}", host, port);
+    
+    let idempotency_ttl = Duration::from_secs(plugin_manager.config().server.idempotency_ttl_secs);
+    let strict_content_type = plugin_manager.config().server.strict_content_type;
+    let worker_threads = plugin_manager.config().server.worker_threads;
+    let max_pending_executions = plugin_manager.config().server.max_pending_executions;
+    let graceful_shutdown = Duration::from_secs(plugin_manager.config().server.graceful_shutdown_secs);
+    let access_log_format = plugin_manager.config().server.access_log_format;
+    let response_style = plugin_manager.config().server.response_style;
+    let plugin_manager = Arc::new(Mutex::new(plugin_manager));
+    let idempotency = Arc::new(IdempotencyCache::new(idempotency_ttl));
+    let gauges = Arc::new(ApiGauges::default());
+    let open_connections = gauges.open_connections.clone();
+    // The manager runs one execution at a time, so the queue has a single slot
+    let execution_queue = Arc::new(ExecutionQueue::new(1));
+    
+    // Create the HTTP server
+    let mut server = HttpServer::new(move || {
+        let app_state = AppState {
+            plugin_manager: plugin_manager.clone(),
+            idempotency: idempotency.clone(),
+            gauges: gauges.clone(),
+            max_pending_executions,
+            execution_queue: execution_queue.clone(),
+        };
+        
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .app_data(json_config(strict_content_type))
+            .app_data(access_log_format)
+            .app_data(response_style)
+            .wrap(from_fn(access_log))
+            .wrap(actix_web::middleware::DefaultHeaders::new()
+                .add(("X-Service", "DynPlug Plugin System")))
+            .service(
+                web::scope("/api/v1")
+                    .route("/plugins", web::get().to(list_plugins))
+                    .route("/operations", web::get().to(list_operations))
+                    .route("/plugins/{name}/execute", web::post().to(execute_plugin))
+                    .route("/plugins/{name}/execute_batch", web::post().to(execute_plugin_batch))
+                    .route("/plugins/diff", web::post().to(diff_plugins))
+                    .route("/plugins/{name}/enable", web::put().to(enable_plugin))
+                    .route("/plugins/{name}/disable", web::put().to(disable_plugin))
+                    .route("/plugins/{name}/toggle", web::post().to(toggle_plugin))
+                    .route("/plugins/{name}/reload-settings", web::post().to(reload_plugin_settings))
+                    .route("/plugins/{name}/examples", web::get().to(plugin_examples))
+                    .route("/plugins/{name}/schema", web::get().to(plugin_schema))
+                    .route("/plugins/{name}/stats", web::get().to(plugin_stats))
+                    .route("/plugins/{name}/custom/{path:.*}", web::route().to(plugin_custom_route))
+                    .route("/executions/recent", web::get().to(recent_executions))
+                    .route("/health", web::get().to(health_check))
+                    .route("/info", web::get().to(server_info))
+                    .route("/config", web::get().to(get_config))
+                    .route("/config", web::put().to(put_config))
+                    .route("/logs/stream", web::get().to(stream_logs))
+            )
+            // Also expose health endpoint at root level
+            .route("/health", web::get().to(health_check))
+            .route("/metrics", web::get().to(metrics_text))
+            .route("/metrics/json", web::get().to(metrics_json))
+            .route("/metrics/reset", web::post().to(reset_metrics))
+            .route("/", web::get().to(list_endpoints))
+    })
+    // The guard lives in the connection's extensions, which are dropped when it closes
+    .on_connect(move |_, extensions| {
+        extensions.insert(GaugeGuard::new(&open_connections));
+    });
+    if let Some(workers) = worker_threads {
+        debug!("Using {} HTTP worker threads", workers);
+        server = server.workers(workers);
+    }
+    let server = server
+    .shutdown_timeout(graceful_shutdown.as_secs())
+    .bind(format!("{}:{}", host, port))
+    .map_err(|e| {
+        error!("Failed to bind server to {}:{}: {}", host, port, e);
+        e
+    })?;
+    
+    // Start the server and handle graceful shutdown
+    let server = server.run();
+    let server_handle = server.handle();
+    
+    tokio::select! {
+        result = server => {
+            match result {
+                Ok(()) => {
+                    info!("HTTP server completed successfully");
+                    Ok(())
+                }
+                Err(e) => {
+                    error!("HTTP server error: {}", e);
+                    Err(Box::new(e) as Box<dyn std::error::Error>)
+                }
+            }
+        }
+        _ = shutdown_signal.recv() => {
+            info!("Shutdown signal received, waiting up to {:?} for in-flight requests...", graceful_shutdown);
+            let started = Instant::now();
+            
+            if tokio::time::timeout(graceful_shutdown, server_handle.stop(true)).await.is_ok() {
+                info!("HTTP server shut down gracefully in {:?}", started.elapsed());
+            } else {
+                warn!("In-flight requests did not finish within {:?}, forcing HTTP server shutdown", graceful_shutdown);
+                server_handle.stop(false).await;
+                info!("HTTP server shutdown forced after {:?}", started.elapsed());
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, App};
+    use chrono::TimeZone;
+    use crate::test_plugins::{EmptyOutputPlugin, SleepPlugin};
+    use dyn_plug_core::{ExecutionRecord, Plugin, PluginManager, PluginStatus};
+    use tempfile::TempDir;
+    
+    struct EchoPlugin;
+    
+    impl Plugin for EchoPlugin {
+        fn name(&self) -> &str { "echo" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Echoes its input" }
+        fn examples(&self) -> Vec<(String, String)> {
+            vec![
+                ("Echo plain text".to_string(), "hello".to_string()),
+                ("Echo JSON".to_string(), r#"{"greeting":"hello"}"#.to_string()),
+            ]
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+    
+    /// Send a raw HTTP/1.1 request that closes the connection and return the full response
+    fn http_request(port: u16, request: String) -> String {
+        use std::io::{Read, Write};
+        let mut stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+    
+    /// Returns the setting named by its input as JSON
+    struct SettingPlugin;
+    
+    impl Plugin for SettingPlugin {
+        fn name(&self) -> &str { "setting" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Reads a setting from its execution context" }
+        fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Err("requires an execution context".into())
+        }
+        fn execute_with_context(&self, input: &str, ctx: &dyn_plug_core::ExecutionContext) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(ctx.setting(input).cloned().unwrap_or_default().to_string())
+        }
+    }
+    
+    /// Reads a setting from its context, recording each settings change it is notified of
+    struct SettingsWatcherPlugin {
+        notified: Arc<Mutex<Vec<std::collections::HashMap<String, serde_json::Value>>>>,
+    }
+    
+    impl Plugin for SettingsWatcherPlugin {
+        fn name(&self) -> &str { "watcher" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Records settings changes" }
+        fn on_config_changed(&self, settings: &std::collections::HashMap<String, serde_json::Value>) {
+            self.notified.lock().unwrap().push(settings.clone());
+        }
+        fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Err("requires an execution context".into())
+        }
+        fn execute_with_context(&self, input: &str, ctx: &dyn_plug_core::ExecutionContext) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(ctx.setting(input).cloned().unwrap_or_default().to_string())
+        }
+    }
+    
+    /// Uppercases inputs, recording whether its bulk path was used
+    struct BulkPlugin {
+        batched: Arc<std::sync::atomic::AtomicBool>,
+    }
+    
+    impl Plugin for BulkPlugin {
+        fn name(&self) -> &str { "bulk" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Processes batches in one call" }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_uppercase())
+        }
+        fn batch_execute(&self, inputs: &[String]) -> Result<Vec<Result<String, String>>, Box<dyn std::error::Error>> {
+            self.batched.store(true, Ordering::SeqCst);
+            Ok(inputs
+                .iter()
+                .map(|input| if input.is_empty() { Err("empty input".to_string()) } else { Ok(input.to_uppercase()) })
+                .collect())
+        }
+    }
+    
+    /// Uppercases its input, counting how many times it runs
+    struct CountingPlugin {
+        calls: Arc<AtomicUsize>,
+    }
+    
+    impl Plugin for CountingPlugin {
+        fn name(&self) -> &str { "counting" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Counts its executions" }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(input.to_uppercase())
+        }
+    }
+    
+    struct ValidatorPlugin;
+    
+    impl Plugin for ValidatorPlugin {
+        fn name(&self) -> &str { "validator" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Accepts only the input 'valid'" }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            self.execute_status(input).map(|(output, _)| output)
+        }
+        fn execute_status(&self, input: &str) -> Result<(String, ExecStatus), Box<dyn std::error::Error>> {
+            if input == "valid" {
+                Ok(("valid".to_string(), ExecStatus::Ok))
+            } else {
+                Ok((format!("'{}' is not valid", input), ExecStatus::Rejected))
+            }
+        }
+    }
+    
+    struct DigestPlugin;
+    
+    impl Plugin for DigestPlugin {
+        fn name(&self) -> &str { "digest" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Returns the length and reverse of its input" }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.len().to_string())
+        }
+        fn execute_multi(&self, input: &str) -> Result<std::collections::HashMap<String, String>, Box<dyn std::error::Error>> {
+            Ok(std::collections::HashMap::from([
+                ("length".to_string(), input.len().to_string()),
+                ("reversed".to_string(), input.chars().rev().collect()),
+            ]))
+        }
+    }
+    
+    struct GreeterPlugin;
+    
+    impl Plugin for GreeterPlugin {
+        fn name(&self) -> &str { "greeter" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Declares a custom greeting route" }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+        fn routes(&self) -> Vec<dyn_plug_core::RouteSpec> {
+            vec![dyn_plug_core::RouteSpec::new("GET", "greeting", |input: &str| Ok(format!("hello {}", input)))]
+        }
+    }
+    
+    struct SchemaPlugin;
+    
+    impl Plugin for SchemaPlugin {
+        fn name(&self) -> &str { "schema" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Publishes input and output schemas" }
+        fn input_schema(&self) -> Option<serde_json::Value> {
+            Some(serde_json::json!({"type": "object", "required": ["text"]}))
+        }
+        fn output_schema(&self) -> Option<serde_json::Value> {
+            Some(serde_json::json!({"type": "object", "required": ["result"]}))
+        }
+        fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(r#"{"result":"ok"}"#.to_string())
+        }
+    }
+    
+    fn create_test_manager() -> (PluginManager, TempDir) {
+        create_test_manager_with_server_config("")
+    }
+    
+    /// Create a test manager whose `server` section includes `extra_server_yaml` lines
+    fn create_test_manager_with_server_config(extra_server_yaml: &str) -> (PluginManager, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        std::fs::write(&config_path, format!(
+            "plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\n{}plugins: {{}}\n",
+            temp_dir.path().join("plugins").to_string_lossy(),
+            temp_dir.path().join("state").to_string_lossy(),
+            extra_server_yaml,
+        )).unwrap();
+        let mut manager = PluginManager::with_config_path(&config_path)
+            .expect("Failed to create plugin manager");
+        manager.register_plugin(Box::new(EchoPlugin)).unwrap();
+        (manager, temp_dir)
+    }
+    
+    fn create_test_app() -> App<
+        impl actix_web::dev::ServiceFactory<
+            actix_web::dev::ServiceRequest,
+            Config = (),
+            Response = actix_web::dev::ServiceResponse,
+            Error = actix_web::Error,
+            InitError = (),
+        >
+    > {
+        let manager = PluginManager::new().expect("Failed to create plugin manager");
+        create_test_app_with_manager(manager)
+    }
+    
+    fn create_test_app_with_manager(manager: PluginManager) -> App<
+        impl actix_web::dev::ServiceFactory<
+            actix_web::dev::ServiceRequest,
+            Config = (),
+            Response = actix_web::dev::ServiceResponse,
+            Error = actix_web::Error,
+            InitError = (),
+        >
+    > {
+        let app_state = AppState {
+            plugin_manager: Arc::new(Mutex::new(manager)),
+            idempotency: Arc::new(IdempotencyCache::new(Duration::from_secs(300))),
+            gauges: Arc::new(ApiGauges::default()),
+            max_pending_executions: None,
+            execution_queue: Arc::new(ExecutionQueue::new(1)),
+        };
+        create_test_app_with_state(app_state)
+    }
+    
+    fn create_test_app_with_state(app_state: AppState) -> App<
+        impl actix_web::dev::ServiceFactory<
+            actix_web::dev::ServiceRequest,
+            Config = (),
+            Response = actix_web::dev::ServiceResponse,
+            Error = actix_web::Error,
+            InitError = (),
+        >
+    > {
+        let server_config = app_state.plugin_manager.lock().unwrap().config().server.clone();
+        App::new()
+            .app_data(web::Data::new(app_state))
+            .app_data(json_config(server_config.strict_content_type))
+            .app_data(server_config.access_log_format)
+            .app_data(server_config.response_style)
+            .wrap(from_fn(access_log))
+            .service(
+                web::scope("/api/v1")
+                    .route("/plugins", web::get().to(list_plugins))
+                    .route("/operations", web::get().to(list_operations))
+                    .route("/plugins/{name}/execute", web::post().to(execute_plugin))
+                    .route("/plugins/{name}/execute_batch", web::post().to(execute_plugin_batch))
+                    .route("/plugins/diff", web::post().to(diff_plugins))
+                    .route("/plugins/{name}/enable", web::put().to(enable_plugin))
+                    .route("/plugins/{name}/disable", web::put().to(disable_plugin))
+                    .route("/plugins/{name}/toggle", web::post().to(toggle_plugin))
+                    .route("/plugins/{name}/reload-settings", web::post().to(reload_plugin_settings))
+                    .route("/plugins/{name}/examples", web::get().to(plugin_examples))
+                    .route("/plugins/{name}/schema", web::get().to(plugin_schema))
+                    .route("/plugins/{name}/stats", web::get().to(plugin_stats))
+                    .route("/plugins/{name}/custom/{path:.*}", web::route().to(plugin_custom_route))
+                    .route("/executions/recent", web::get().to(recent_executions))
+                    .route("/health", web::get().to(health_check))
+                    .route("/info", web::get().to(server_info))
+                    .route("/config", web::get().to(get_config))
+                    .route("/config", web::put().to(put_config))
+                    .route("/logs/stream", web::get().to(stream_logs))
+            )
+            .route("/health", web::get().to(health_check))
+            .route("/metrics", web::get().to(metrics_text))
+            .route("/metrics/json", web::get().to(metrics_json))
+            .route("/metrics/reset", web::post().to(reset_metrics))
+            .route("/", web::get().to(list_endpoints))
     }
     
-    // Simple uptime tracking (could be enhanced with actual process start time)
-    static START_TIME: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
-    let start_time = START_TIME.get_or_init(|| Instant::now());
-    let uptime = start_time.elapsed();
+    #[actix_web::test]
+    async fn test_health_endpoint() {
+        let app = test::init_service(create_test_app()).await;
+        let req = test::TestRequest::get().uri("/health").to_request();
+        let resp = test::call_service(&app, req).await;
+        
+        assert!(resp.status().is_success());
+    }
+    
+    #[actix_web::test]
+    async fn test_list_endpoints() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let req = test::TestRequest::get().uri("/").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        
+        let endpoints = body["data"].as_array().unwrap();
+        assert_eq!(endpoints.len(), ENDPOINTS.len());
+        assert!(endpoints.iter().any(|e| e["path"] == "/api/v1/plugins/{name}/execute" && e["method"] == "POST"));
+    }
+    
+    #[actix_web::test]
+    async fn test_list_plugins_endpoint() {
+        let app = test::init_service(create_test_app()).await;
+        let req = test::TestRequest::get().uri("/api/v1/plugins").to_request();
+        let resp = test::call_service(&app, req).await;
+        
+        assert!(resp.status().is_success());
+    }
+    
+    #[actix_web::test]
+    async fn test_list_plugins_filters() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(SchemaPlugin)).unwrap();
+        manager.disable_plugin("schema").unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let names = |body: serde_json::Value| -> Vec<String> {
+            body["data"].as_array().unwrap().iter()
+                .map(|p| p["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+        let list = |uri: &str| test::TestRequest::get().uri(uri).to_request();
+        
+        let body = test::call_and_read_body_json(&app, list("/api/v1/plugins?enabled=true")).await;
+        assert_eq!(names(body), ["echo"]);
+        
+        let body = test::call_and_read_body_json(&app, list("/api/v1/plugins?enabled=false&name_contains=SCH")).await;
+        assert_eq!(names(body), ["schema"]);
+        
+        let body = test::call_and_read_body_json(&app, list("/api/v1/plugins?enabled=true&loaded=true&name_contains=ech&unknown=1")).await;
+        assert_eq!(names(body), ["echo"]);
+        
+        let body = test::call_and_read_body_json(&app, list("/api/v1/plugins?enabled=true&name_contains=schema")).await;
+        assert!(names(body).is_empty());
+        
+        let resp = test::call_service(&app, list("/api/v1/plugins?enabled=maybe")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_nonexistent_plugin() {
+        let app = test::init_service(create_test_app()).await;
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/nonexistent/execute")
+            .set_json(&ExecuteRequest {
+                input: "test".to_string(),
+                ..Default::default()
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+    
+    #[actix_web::test]
+    async fn test_toggle_plugin_endpoint() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        #[derive(Deserialize)]
+        struct ToggleResponse {
+            success: bool,
+            data: PluginStatus,
+        }
+        
+        let req = test::TestRequest::post().uri("/api/v1/plugins/echo/toggle").to_request();
+        let body: ToggleResponse = test::call_and_read_body_json(&app, req).await;
+        assert!(body.success);
+        assert_eq!(body.data.name, "echo");
+        assert!(!body.data.enabled);
+        assert!(!body.data.config_enabled);
+        
+        let req = test::TestRequest::post().uri("/api/v1/plugins/echo/toggle").to_request();
+        let body: ToggleResponse = test::call_and_read_body_json(&app, req).await;
+        assert!(body.success);
+        assert!(body.data.enabled);
+        assert!(body.data.config_enabled);
+    }
+    
+    #[actix_web::test]
+    async fn test_toggle_nonexistent_plugin() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post().uri("/api/v1/plugins/nonexistent/toggle").to_request();
+        let resp = test::call_service(&app, req).await;
+        
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+    
+    #[actix_web::test]
+    async fn test_health_reports_load_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+        std::fs::write(plugins_dir.join("broken.so"), "not a library").unwrap();
+        
+        let config_path = temp_dir.path().join("config.yaml");
+        let config_content = |fail_health: bool| format!(r#"
+plugins_dir: "{}"
+log_level: "info"
+server:
+  host: "127.0.0.1"
+  port: 8080
+  enabled: true
+  fail_health_on_load_errors: {}
+plugins: {{}}
+"#, plugins_dir.to_string_lossy(), fail_health);
+        
+        // Default behavior stays healthy despite the failure
+        std::fs::write(&config_path, config_content(false)).unwrap();
+        let manager = PluginManager::with_config_path(&config_path).unwrap();
+        assert_eq!(manager.load_failures().len(), 1);
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let req = test::TestRequest::get().uri("/health").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        
+        // With the flag on, the load failure makes the service unhealthy
+        std::fs::write(&config_path, config_content(true)).unwrap();
+        let manager = PluginManager::with_config_path(&config_path).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let req = test::TestRequest::get().uri("/health").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::SERVICE_UNAVAILABLE);
+        
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["data"]["status"], "degraded");
+        assert_eq!(body["data"]["load_failures"].as_array().unwrap().len(), 1);
+    }
+    
+    #[actix_web::test]
+    async fn test_recent_executions_since_filter() {
+        let (manager, _temp_dir) = create_test_manager();
+        for (plugin, secs) in [("first", 1_700_000_000), ("second", 1_700_000_100), ("third", 1_700_000_200)] {
+            manager.history().record(ExecutionRecord {
+                plugin: plugin.to_string(),
+                success: true,
+                duration_ms: 1,
+                executed_at: chrono::Utc.timestamp_opt(secs, 0).unwrap(),
+                input_bytes: 0,
+                output_bytes: 0,
+            });
+        }
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        // The boundary is exclusive: an entry exactly at `since` is not returned
+        let since = chrono::Utc.timestamp_opt(1_700_000_100, 0).unwrap().to_rfc3339();
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/v1/executions/recent?since={}", since.replace('+', "%2B")))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        let executions = body["data"].as_array().unwrap();
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0]["plugin"], "third");
+        
+        let req = test::TestRequest::get().uri("/api/v1/executions/recent").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"].as_array().unwrap().len(), 3);
+        
+        let req = test::TestRequest::get().uri("/api/v1/executions/recent?since=yesterday").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_raw_output() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(SchemaPlugin)).unwrap();
+        manager.disable_plugin("schema").unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let execute = |uri: &str| {
+            test::TestRequest::post()
+                .uri(uri)
+                .set_json(&ExecuteRequest { input: "hello raw".to_string(), ..Default::default() })
+                .to_request()
+        };
+        
+        let resp = test::call_service(&app, execute("/api/v1/plugins/echo/execute?raw=true")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
+        assert_eq!(test::read_body(resp).await, "hello raw");
+        
+        let resp = test::call_service(&app, execute("/api/v1/plugins/missing/execute?raw=true")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/plain; charset=utf-8");
+        assert_eq!(test::read_body(resp).await, "Plugin 'missing' not found");
+        
+        let resp = test::call_service(&app, execute("/api/v1/plugins/schema/execute?raw=true")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        assert_eq!(test::read_body(resp).await, "Plugin 'schema' is disabled");
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_truncates_large_output() {
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  max_response_output_bytes: 16\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let large_input = "x".repeat(1000);
+        let execute = |uri: &str, input: &str| {
+            test::TestRequest::post()
+                .uri(uri)
+                .set_json(&ExecuteRequest { input: input.to_string(), ..Default::default() })
+                .to_request()
+        };
+        
+        let body: serde_json::Value = test::call_and_read_body_json(&app, execute("/api/v1/plugins/echo/execute", &large_input)).await;
+        assert_eq!(body["data"]["output"], "x".repeat(16));
+        assert_eq!(body["data"]["truncated"], true);
+        
+        // Output within the limit carries no marker
+        let body: serde_json::Value = test::call_and_read_body_json(&app, execute("/api/v1/plugins/echo/execute", "short")).await;
+        assert_eq!(body["data"]["output"], "short");
+        assert!(body["data"].get("truncated").is_none());
+        
+        // Raw responses are never truncated
+        let resp = test::call_service(&app, execute("/api/v1/plugins/echo/execute?raw=true", &large_input)).await;
+        assert_eq!(test::read_body(resp).await, large_input.as_str());
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_with_version_constraint() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let execute = |uri: &str| {
+            test::TestRequest::post()
+                .uri(uri)
+                .set_json(&ExecuteRequest { input: "pinned".to_string(), ..Default::default() })
+                .to_request()
+        };
+        
+        let body: serde_json::Value = test::call_and_read_body_json(&app, execute("/api/v1/plugins/echo/execute?version=%5E1.0")).await;
+        assert_eq!(body["success"], true);
+        assert_eq!(body["data"]["output"], "pinned");
+        
+        let resp = test::call_service(&app, execute("/api/v1/plugins/echo/execute?version=%5E0.1")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error"].as_str().unwrap().contains("does not satisfy"));
+        
+        let resp = test::call_service(&app, execute("/api/v1/plugins/echo/execute?version=banana")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_uses_plugin_timeout() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(SleepPlugin)).unwrap();
+        manager.get_plugin_config("sleep").timeout_ms = Some(20);
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/sleep/execute")
+            .set_json(&ExecuteRequest { input: "100".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::REQUEST_TIMEOUT);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error"].as_str().unwrap().contains("timed out"), "{}", body);
+        
+        // A later deadline does not extend the plugin's timeout
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/sleep/execute")
+            .insert_header((DEADLINE_HEADER, "5000ms"))
+            .set_json(&ExecuteRequest { input: "100".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::REQUEST_TIMEOUT);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error"].as_str().unwrap().contains("timed out"), "{}", body);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_with_settings_override() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(SettingPlugin)).unwrap();
+        manager.get_plugin_config("setting").overridable_settings = vec!["mode".to_string()];
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/setting/execute")
+            .set_json(serde_json::json!({"input": "mode", "settings": {"mode": "fast"}}))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], "\"fast\"");
+        
+        // Settings the plugin's configuration does not list stay out of the caller's reach
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/setting/execute")
+            .set_json(serde_json::json!({"input": "mode", "settings": {"mode": "fast", "secret": "x"}}))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error"].as_str().unwrap().contains("secret"), "{}", body);
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/setting/execute")
+            .set_json(&ExecuteRequest { input: "mode".to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], "null");
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_parse_output() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let json_input = r#"{"operation":"uppercase","output":"HELLO"}"#;
+        
+        // Without the flag the output stays a string
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: json_input.to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], json_input);
+        
+        // With the flag and a JSON Accept header the output is embedded as an object
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute?parse_output=true")
+            .insert_header(("Accept", "application/json"))
+            .set_json(&ExecuteRequest { input: json_input.to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"]["output"], "HELLO");
+        
+        // Output that is not JSON falls back to the string
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute?parse_output=true")
+            .insert_header(("Accept", "application/json"))
+            .set_json(&ExecuteRequest { input: "plain text".to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], "plain text");
+        
+        // An Accept header that excludes JSON keeps the string form
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute?parse_output=true")
+            .insert_header(("Accept", "text/plain"))
+            .set_json(&ExecuteRequest { input: json_input.to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], json_input);
+    }
+    
+    #[actix_web::test]
+    async fn test_plugin_examples() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/echo/examples").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["success"], true);
+        assert_eq!(body["data"].as_array().unwrap().len(), 2);
+        assert_eq!(body["data"][0]["input"], "hello");
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/missing/examples").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+    
+    #[actix_web::test]
+    async fn test_server_info() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::get().uri("/api/v1/info").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["success"], true);
+        assert_eq!(body["data"]["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(body["data"]["plugin_count"], 1);
+        assert!(!body["data"]["git_hash"].as_str().unwrap().is_empty());
+        assert_eq!(body["data"]["config"]["log_level"], "info");
+    }
+    
+    /// Install the broadcasting logger once so tests can subscribe to log records
+    fn install_log_stream() {
+        static INSTALL_LOGGER: std::sync::Once = std::sync::Once::new();
+        INSTALL_LOGGER.call_once(|| {
+            let logger = env_logger::Builder::new().filter_level(log::LevelFilter::Info).build();
+            crate::log_stream::install(logger).unwrap();
+        });
+    }
+    
+    #[actix_web::test]
+    async fn test_stream_logs() {
+        install_log_stream();
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::get().uri("/api/v1/logs/stream?level=bogus").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        
+        let req = test::TestRequest::get().uri("/api/v1/logs/stream?level=info").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/event-stream");
+        let mut body = std::pin::pin!(resp.into_body());
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: "streamed".to_string(), ..Default::default() })
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+        
+        // Other tests log concurrently, so read frames until the execution's record arrives
+        let found = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let frame = std::future::poll_fn(|cx| actix_web::body::MessageBody::poll_next(body.as_mut(), cx))
+                    .await
+                    .expect("log stream ended")
+                    .unwrap();
+                let frame = String::from_utf8(frame.to_vec()).unwrap();
+                assert!(frame.starts_with("data: ") && frame.ends_with("\n\n"));
+                let event: serde_json::Value = serde_json::from_str(frame["data: ".len()..].trim_end()).unwrap();
+                let message = event["message"].as_str().unwrap();
+                if message.contains("Plugin 'echo' executed successfully") {
+                    assert_eq!(event["level"], "INFO");
+                    break;
+                }
+            }
+        })
+        .await;
+        assert!(found.is_ok(), "execution log record was not streamed");
+    }
+    
+    #[actix_web::test]
+    async fn test_access_log_records_request_fields() {
+        install_log_stream();
+        let mut logs = crate::log_stream::subscribe();
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  access_log_format: json\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .insert_header((REQUEST_ID, "access-log-test"))
+            .set_json(&ExecuteRequest { input: "logged".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.headers().get(REQUEST_ID).unwrap(), "access-log-test");
+        
+        // Other tests log concurrently, so skip records until this request's arrives
+        let record = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let event = logs.recv().await.unwrap();
+                if event.target == ACCESS_LOG_TARGET && event.message.contains("access-log-test") {
+                    break serde_json::from_str::<serde_json::Value>(&event.message).unwrap();
+                }
+            }
+        })
+        .await
+        .expect("access log record was not emitted");
+        assert_eq!(record["method"], "POST");
+        assert_eq!(record["path"], "/api/v1/plugins/echo/execute");
+        assert_eq!(record["plugin"], "echo");
+        assert_eq!(record["status"], 200);
+        assert!(record["duration_ms"].is_u64());
+        
+        // Requests without an id get a generated one
+        let req = test::TestRequest::get().uri("/health").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(!resp.headers().get(REQUEST_ID).unwrap().is_empty());
+        
+        // So do requests whose id could forge fields in a text access log
+        for bad_id in ["id status=500", "a\"b", &"x".repeat(MAX_REQUEST_ID_LEN + 1)] {
+            let req = test::TestRequest::get().uri("/health").insert_header((REQUEST_ID, bad_id)).to_request();
+            let resp = test::call_service(&app, req).await;
+            let id = resp.headers().get(REQUEST_ID).unwrap().to_str().unwrap();
+            assert!(id != bad_id && is_usable_request_id(id), "{}", id);
+        }
+    }
+    
+    #[actix_web::test]
+    async fn test_plugin_stats_record_payload_sizes() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        for input in ["abcd", "abcdefghijkl"] {
+            let req = test::TestRequest::post()
+                .uri("/api/v1/plugins/echo/execute")
+                .set_json(&ExecuteRequest { input: input.to_string(), ..Default::default() })
+                .to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/echo/stats").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["executions"], 2);
+        assert_eq!(body["data"]["avg_input_bytes"], 8.0);
+        assert_eq!(body["data"]["max_input_bytes"], 12);
+        assert_eq!(body["data"]["avg_output_bytes"], 8.0);
+        assert_eq!(body["data"]["max_output_bytes"], 12);
+        
+        let req = test::TestRequest::get().uri("/api/v1/executions/recent?limit=1").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"][0]["input_bytes"], 12);
+        assert_eq!(body["data"][0]["output_bytes"], 12);
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/missing/stats").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_batch_uses_plugin_batch_path() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        let batched = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        manager.register_plugin(Box::new(BulkPlugin { batched: batched.clone() })).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/bulk/execute_batch")
+            .set_json(&BatchExecuteRequest { inputs: vec!["a".to_string(), String::new(), "c".to_string()], ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert!(batched.load(Ordering::SeqCst));
+        let results = body["data"]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["output"], "A");
+        assert_eq!(results[1]["success"], false);
+        assert_eq!(results[2]["output"], "C");
+        
+        // Plugins without an override go through the default per-input path
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute_batch")
+            .set_json(&BatchExecuteRequest { inputs: vec!["x".to_string(), "y".to_string()], ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["results"][1]["output"], "y");
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/bulk/stats").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["executions"], 3);
+        assert_eq!(body["data"]["failures"], 1);
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/missing/execute_batch")
+            .set_json(&BatchExecuteRequest { inputs: vec!["x".to_string()], ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_batch_deduplicates_inputs() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        let calls = Arc::new(AtomicUsize::new(0));
+        manager.register_plugin(Box::new(CountingPlugin { calls: calls.clone() })).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let inputs: Vec<String> = ["a", "b", "a", "c", "b", "a"].iter().map(|input| input.to_string()).collect();
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/counting/execute_batch")
+            .set_json(&BatchExecuteRequest { inputs: inputs.clone(), deduplicate: true })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let outputs: Vec<&str> = body["data"]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| result["output"].as_str().unwrap())
+            .collect();
+        assert_eq!(outputs, ["A", "B", "A", "C", "B", "A"]);
+        
+        // Without the option every input runs
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/counting/execute_batch")
+            .set_json(&BatchExecuteRequest { inputs, deduplicate: false })
+            .to_request();
+        let _: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 9);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_marks_empty_output() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(EmptyOutputPlugin)).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/empty/execute")
+            .set_json(&ExecuteRequest { input: "ignored".to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["success"], true);
+        assert_eq!(body["data"]["output"], "");
+        assert_eq!(body["data"]["output_empty"], true);
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: "hello".to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body["data"].get("output_empty").is_none());
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_rejected_status_returns_422() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(ValidatorPlugin)).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/validator/execute")
+            .set_json(&ExecuteRequest { input: "bogus".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNPROCESSABLE_ENTITY);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["success"], true);
+        assert_eq!(body["data"]["status"], "rejected");
+        assert_eq!(body["data"]["output"], "'bogus' is not valid");
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/validator/execute")
+            .set_json(&ExecuteRequest { input: "valid".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["data"].get("status").is_none());
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_multi_returns_named_outputs() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(DigestPlugin)).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let multi_request = |name: &str| test::TestRequest::post()
+            .uri(&format!("/api/v1/plugins/{}/execute?multi=true", name))
+            .set_json(&ExecuteRequest { input: "abc".to_string(), ..Default::default() })
+            .to_request();
+        
+        let body: serde_json::Value = test::call_and_read_body_json(&app, multi_request("digest")).await;
+        assert_eq!(body["data"]["plugin"], "digest");
+        assert_eq!(body["data"]["outputs"], serde_json::json!({"length": "3", "reversed": "cba"}));
+        assert!(body["data"].get("output").is_none());
+        
+        // Plugins without named outputs return their output under "result"
+        let body: serde_json::Value = test::call_and_read_body_json(&app, multi_request("echo")).await;
+        assert_eq!(body["data"]["outputs"], serde_json::json!({"result": "abc"}));
+        
+        let resp = test::call_service(&app, multi_request("missing")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+        
+        // Named-output runs are recorded like single executions
+        let req = test::TestRequest::get().uri("/api/v1/plugins/digest/stats").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["executions"], 1);
+    }
+    
+    #[actix_web::test]
+    async fn test_plugin_custom_route() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(GreeterPlugin)).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/greeter/custom/greeting?world").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"], "hello world");
+        
+        let req = test::TestRequest::post().uri("/api/v1/plugins/greeter/custom/greeting").set_payload("there").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+        
+        for uri in ["/api/v1/plugins/greeter/custom/farewell", "/api/v1/plugins/echo/custom/greeting", "/api/v1/plugins/missing/custom/greeting"] {
+            let resp = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND, "{}", uri);
+        }
+        
+        // Only calls that reached a route are recorded
+        let req = test::TestRequest::get().uri("/api/v1/plugins/greeter/stats").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["executions"], 1);
+    }
     
-    let health = HealthStatus {
-        status: "healthy".to_string(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-        uptime_ms: uptime.as_millis() as u64,
-    };
+    #[actix_web::test]
+    async fn test_diff_plugins_reports_mismatch() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(EmptyOutputPlugin)).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let diff_request = |left: &str, right: &str| test::TestRequest::post()
+            .uri("/api/v1/plugins/diff")
+            .set_json(&DiffRequest { left: left.to_string(), right: right.to_string(), input: "hello".to_string() })
+            .to_request();
+        
+        let body: serde_json::Value = test::call_and_read_body_json(&app, diff_request("echo", "empty")).await;
+        assert_eq!(body["data"]["matches"], false);
+        assert_eq!(body["data"]["left"]["output"], "hello");
+        assert_eq!(body["data"]["right"]["output"], "");
+        assert!(body["data"]["diff"].as_str().unwrap().contains("-hello"));
+        
+        let body: serde_json::Value = test::call_and_read_body_json(&app, diff_request("echo", "echo")).await;
+        assert_eq!(body["data"]["matches"], true);
+        assert!(body["data"].get("diff").is_none());
+        
+        let resp = test::call_service(&app, diff_request("echo", "missing")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
     
-    Ok(HttpResponse::Ok().json(ApiResponse::success(health)))
-}
-
-
-
-/// Start the HTTP API server with graceful shutdown support
-pub async fn start_server(
-    plugin_manager: PluginManager,
-    host: &str,
-    port: u16,
-    mut shutdown_signal: tokio::sync::mpsc::Receiver<()>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    info!("Starting HTTP API server with graceful shutdown on {}:{}", host, port);
+    #[actix_web::test]
+    async fn test_plugin_schema() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(SchemaPlugin)).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/schema/schema").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["input"]["required"][0], "text");
+        assert_eq!(body["data"]["output"]["required"][0], "result");
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/echo/schema").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body["data"]["input"].is_null());
+        assert!(body["data"]["output"].is_null());
+        
+        let req = test::TestRequest::get().uri("/api/v1/plugins/missing/schema").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
     
-    let plugin_manager = Arc::new(Mutex::new(plugin_manager));
+    #[actix_web::test]
+    async fn test_execute_idempotency_key() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let execute = |key: &str, input: &str| {
+            test::TestRequest::post()
+                .uri("/api/v1/plugins/echo/execute")
+                .insert_header((IDEMPOTENCY_KEY, key.to_string()))
+                .set_json(&ExecuteRequest { input: input.to_string(), ..Default::default() })
+                .to_request()
+        };
+        
+        let resp = test::call_service(&app, execute("key-1", "hello")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert!(resp.headers().get("X-Idempotent-Replay").is_none());
+        let first = test::read_body(resp).await;
+        
+        // Repeating the request replays the cached response
+        let resp = test::call_service(&app, execute("key-1", "hello")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(resp.headers().get("X-Idempotent-Replay").unwrap(), "true");
+        assert_eq!(test::read_body(resp).await, first);
+        
+        // Reusing the key with a different body or query is a conflict
+        let resp = test::call_service(&app, execute("key-1", "goodbye")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute?parse_output=true")
+            .insert_header((IDEMPOTENCY_KEY, "key-1"))
+            .set_json(&ExecuteRequest { input: "hello".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+        
+        // Failed executions are replayed as well
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/missing/execute")
+            .insert_header((IDEMPOTENCY_KEY, "key-2"))
+            .set_json(&ExecuteRequest { input: "hello".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/missing/execute")
+            .insert_header((IDEMPOTENCY_KEY, "key-2"))
+            .set_json(&ExecuteRequest { input: "hello".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+        assert_eq!(resp.headers().get("X-Idempotent-Replay").unwrap(), "true");
+    }
     
-    // Create the HTTP server
-    let server = HttpServer::new(move || {
-        let app_state = AppState { plugin_manager: plugin_manager.clone() };
+    #[actix_web::test]
+    async fn test_execute_rejected_when_pending_limit_reached() {
+        let (manager, _temp_dir) = create_test_manager();
+        let gauges = Arc::new(ApiGauges::default());
+        let app_state = AppState {
+            plugin_manager: Arc::new(Mutex::new(manager)),
+            idempotency: Arc::new(IdempotencyCache::new(Duration::from_secs(300))),
+            gauges: gauges.clone(),
+            max_pending_executions: Some(1),
+            execution_queue: Arc::new(ExecutionQueue::new(1)),
+        };
+        let app = test::init_service(create_test_app_with_state(app_state)).await;
+        let execute = || test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
         
-        App::new()
-            .app_data(web::Data::new(app_state))
-            .wrap(Logger::default())
-            .wrap(actix_web::middleware::DefaultHeaders::new()
-                .add(("X-Service", "DynPlug Plugin System")))
-            .service(
-                web::scope("/api/v1")
-                    .route("/plugins", web::get().to(list_plugins))
-                    .route("/plugins/{name}/execute", web::post().to(execute_plugin))
-                    .route("/plugins/{name}/enable", web::put().to(enable_plugin))
-                    .route("/plugins/{name}/disable", web::put().to(disable_plugin))
-                    .route("/health", web::get().to(health_check))
-            )
-            // Also expose health endpoint at root level
-            .route("/health", web::get().to(health_check))
-    })
-    .bind(format!("{}:{}", host, port))
-    .map_err(|e| {
-        error!("Failed to bind server to {}:{}: {}", host, port, e);
-        e
-    })?;
+        // An execution already in progress fills the queue
+        let pending = GaugeGuard::new(&gauges.in_flight_executions);
+        let resp = test::call_service(&app, execute()).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(resp.headers().get(actix_web::http::header::RETRY_AFTER).unwrap(), RETRY_AFTER_SECS);
+        assert_eq!(gauges.in_flight_executions.load(Ordering::SeqCst), 1);
+        
+        drop(pending);
+        let resp = test::call_service(&app, execute()).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+    }
     
-    // Start the server and handle graceful shutdown
-    let server_handle = server.run();
+    #[actix_web::test]
+    async fn test_execute_priority_header() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let execute = |priority: &str| test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .insert_header((PRIORITY_HEADER, priority.to_string()))
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
+        
+        for priority in ["low", "normal", "High"] {
+            let resp = test::call_service(&app, execute(priority)).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::OK, "{}", priority);
+        }
+        let resp = test::call_service(&app, execute("urgent")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+    }
     
-    tokio::select! {
-        result = server_handle => {
-            match result {
-                Ok(()) => {
-                    info!("HTTP server completed successfully");
-                    Ok(())
-                }
-                Err(e) => {
-                    error!("HTTP server error: {}", e);
-                    Err(Box::new(e) as Box<dyn std::error::Error>)
+    #[actix_web::test]
+    async fn test_high_priority_execution_overtakes_queued_low() {
+        let (manager, _temp_dir) = create_test_manager();
+        let plugin_manager = Arc::new(Mutex::new(manager));
+        let execution_queue = Arc::new(ExecutionQueue::new(1));
+        let app_state = AppState {
+            plugin_manager: plugin_manager.clone(),
+            idempotency: Arc::new(IdempotencyCache::new(Duration::from_secs(300))),
+            gauges: Arc::new(ApiGauges::default()),
+            max_pending_executions: None,
+            execution_queue: execution_queue.clone(),
+        };
+        let app = std::rc::Rc::new(test::init_service(create_test_app_with_state(app_state)).await);
+        let execute = |priority: &str, input: &str| {
+            let app = app.clone();
+            let req = test::TestRequest::post()
+                .uri("/api/v1/plugins/echo/execute")
+                .insert_header((PRIORITY_HEADER, priority.to_string()))
+                .set_json(&ExecuteRequest { input: input.to_string(), ..Default::default() })
+                .to_request();
+            actix_web::rt::spawn(async move { test::call_service(&*app, req).await.status() })
+        };
+        let wait_for_waiters = |count: usize| {
+            let execution_queue = execution_queue.clone();
+            async move {
+                while execution_queue.waiting() < count {
+                    actix_web::rt::time::sleep(Duration::from_millis(1)).await;
                 }
             }
+        };
+        
+        // Both requests queue behind an execution holding the only slot
+        let blocker = execution_queue.acquire(ExecutionPriority::Normal);
+        let low = execute("low", "l");
+        wait_for_waiters(1).await;
+        let high = execute("high", "hhh");
+        wait_for_waiters(2).await;
+        drop(blocker);
+        assert_eq!(low.await.unwrap(), actix_web::http::StatusCode::OK);
+        assert_eq!(high.await.unwrap(), actix_web::http::StatusCode::OK);
+        
+        // The later high-priority request ran first
+        let input_bytes: Vec<u64> = plugin_manager.lock().unwrap()
+            .recent_executions(None, None)
+            .iter()
+            .map(|record| record.input_bytes)
+            .collect();
+        assert_eq!(input_bytes, [3, 1]);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_reports_queue_time() {
+        let (manager, _temp_dir) = create_test_manager();
+        let plugin_manager = Arc::new(Mutex::new(manager));
+        let app_state = AppState {
+            plugin_manager: plugin_manager.clone(),
+            idempotency: Arc::new(IdempotencyCache::new(Duration::from_secs(300))),
+            gauges: Arc::new(ApiGauges::default()),
+            max_pending_executions: None,
+            execution_queue: Arc::new(ExecutionQueue::new(1)),
+        };
+        let app = test::init_service(create_test_app_with_state(app_state)).await;
+        let execute = || test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
+        
+        let body: serde_json::Value = test::call_and_read_body_json(&app, execute()).await;
+        assert!(body["data"]["queue_ms"].as_u64().unwrap() < 50);
+        
+        // Another execution holding the manager delays this one
+        let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+        let holder = std::thread::spawn(move || {
+            let _manager = plugin_manager.lock().unwrap();
+            locked_tx.send(()).unwrap();
+            std::thread::sleep(Duration::from_millis(100));
+        });
+        locked_rx.recv().unwrap();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, execute()).await;
+        holder.join().unwrap();
+        assert!(body["data"]["queue_ms"].as_u64().unwrap() >= 50, "{}", body);
+        assert!(body["data"]["exec_ms"].as_u64().unwrap() < 50, "{}", body);
+    }
+    
+    #[actix_web::test]
+    async fn test_execute_deadline_header() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let execute = |deadline: String| test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .insert_header((DEADLINE_HEADER, deadline))
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
+        
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(30)).to_rfc3339();
+        for deadline in [future, "5000ms".to_string(), "5000".to_string()] {
+            let body: serde_json::Value = test::call_and_read_body_json(&app, execute(deadline.clone())).await;
+            assert_eq!(body["data"]["output"], "hi", "{}", deadline);
         }
-        _ = shutdown_signal.recv() => {
-            info!("Shutdown signal received, stopping HTTP server gracefully...");
-            
-            // Give the server a moment to finish current requests
-            tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
-            
-            info!("HTTP server shutdown completed");
-            Ok(())
+        
+        let past = (chrono::Utc::now() - chrono::Duration::seconds(1)).to_rfc3339();
+        for deadline in [past, "0ms".to_string()] {
+            let resp = test::call_service(&app, execute(deadline.clone())).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::REQUEST_TIMEOUT, "{}", deadline);
         }
+        
+        let resp = test::call_service(&app, execute("soon".to_string())).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        
+        // A timed-out request is not replayed, so its key can be retried with more time
+        let execute_with_key = |deadline: &str| test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .insert_header((DEADLINE_HEADER, deadline.to_string()))
+            .insert_header((IDEMPOTENCY_KEY, "retry-key"))
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, execute_with_key("0ms")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::REQUEST_TIMEOUT);
+        let resp = test::call_service(&app, execute_with_key("5000ms")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert!(resp.headers().get("X-Idempotent-Replay").is_none());
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use actix_web::{test, App};
-    use dyn_plug_core::PluginManager;
     
-    fn create_test_app() -> App<
-        impl actix_web::dev::ServiceFactory<
-            actix_web::dev::ServiceRequest,
-            Config = (),
-            Response = actix_web::dev::ServiceResponse,
-            Error = actix_web::Error,
-            InitError = (),
-        >
-    > {
-        let manager = PluginManager::new().expect("Failed to create plugin manager");
-        let app_state = AppState { plugin_manager: Arc::new(Mutex::new(manager)) };
+    #[actix_web::test]
+    async fn test_execute_deadline_timeout_is_not_replayed() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(SleepPlugin)).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let execute = |deadline: &str| test::TestRequest::post()
+            .uri("/api/v1/plugins/sleep/execute")
+            .insert_header((DEADLINE_HEADER, deadline.to_string()))
+            .insert_header((IDEMPOTENCY_KEY, "slow-key"))
+            .set_json(&ExecuteRequest { input: "100".to_string(), ..Default::default() })
+            .to_request();
         
-        App::new()
-            .app_data(web::Data::new(app_state))
-            .service(
-                web::scope("/api/v1")
-                    .route("/plugins", web::get().to(list_plugins))
-                    .route("/plugins/{name}/execute", web::post().to(execute_plugin))
-                    .route("/plugins/{name}/enable", web::put().to(enable_plugin))
-                    .route("/plugins/{name}/disable", web::put().to(disable_plugin))
-                    .route("/health", web::get().to(health_check))
-            )
-            .route("/health", web::get().to(health_check))
+        // The deadline runs out while the plugin is executing
+        let resp = test::call_service(&app, execute("20ms")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::REQUEST_TIMEOUT);
+        
+        let resp = test::call_service(&app, execute("5000ms")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert!(resp.headers().get("X-Idempotent-Replay").is_none());
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["data"]["output"], "100");
     }
     
     #[actix_web::test]
-    async fn test_health_endpoint() {
-        let app = test::init_service(create_test_app()).await;
-        let req = test::TestRequest::get().uri("/health").to_request();
-        let resp = test::call_service(&app, req).await;
+    async fn test_execute_idempotency_key_expires() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app_state = AppState {
+            plugin_manager: Arc::new(Mutex::new(manager)),
+            idempotency: Arc::new(IdempotencyCache::new(Duration::from_millis(50))),
+            gauges: Arc::new(ApiGauges::default()),
+            max_pending_executions: None,
+            execution_queue: Arc::new(ExecutionQueue::new(1)),
+        };
+        let app = test::init_service(create_test_app_with_state(app_state)).await;
+        let execute = |input: &str| {
+            test::TestRequest::post()
+                .uri("/api/v1/plugins/echo/execute")
+                .insert_header((IDEMPOTENCY_KEY, "key"))
+                .set_json(&ExecuteRequest { input: input.to_string(), ..Default::default() })
+                .to_request()
+        };
         
-        assert!(resp.status().is_success());
+        let resp = test::call_service(&app, execute("hello")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        
+        // After expiry the key is free, even for a different body
+        let resp = test::call_service(&app, execute("goodbye")).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert!(resp.headers().get("X-Idempotent-Replay").is_none());
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["data"]["output"], "goodbye");
     }
     
     #[actix_web::test]
-    async fn test_list_plugins_endpoint() {
-        let app = test::init_service(create_test_app()).await;
-        let req = test::TestRequest::get().uri("/api/v1/plugins").to_request();
+    async fn test_execute_content_type_validation() {
+        let wrong_content_type = || {
+            test::TestRequest::post()
+                .uri("/api/v1/plugins/echo/execute")
+                .insert_header(("Content-Type", "text/plain"))
+                .set_payload(r#"{"input": "hello"}"#)
+                .to_request()
+        };
+        
+        // Lenient by default: any body that parses as JSON is accepted
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let resp = test::call_service(&app, wrong_content_type()).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        
+        // Strict mode rejects non-JSON content types
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  strict_content_type: true\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let resp = test::call_service(&app, wrong_content_type()).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["success"], false);
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: "hello".to_string(), ..Default::default() })
+            .to_request();
         let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+    }
+    
+    #[actix_web::test]
+    async fn test_metrics_snapshot_and_reset() {
+        let (manager, _temp_dir) = create_test_manager();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
         
-        assert!(resp.status().is_success());
+        for input in ["one", "two"] {
+            let req = test::TestRequest::post()
+                .uri("/api/v1/plugins/echo/execute")
+                .set_json(&ExecuteRequest { input: input.to_string(), ..Default::default() })
+                .to_request();
+            test::call_service(&app, req).await;
+        }
+        
+        let req = test::TestRequest::get().uri("/metrics/json").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["executions_total"], 2);
+        assert_eq!(body["data"]["plugins"]["echo"]["executions"], 2);
+        
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        let text = test::call_and_read_body(&app, req).await;
+        assert!(String::from_utf8_lossy(&text).contains("dyn_plug_plugin_executions_total{plugin=\"echo\"} 2"));
+        
+        let req = test::TestRequest::post().uri("/metrics/reset").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["executions_total"], 2);
+        
+        let req = test::TestRequest::get().uri("/metrics/json").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["executions_total"], 0);
+        assert_eq!(body["data"]["failures_total"], 0);
+        assert!(body["data"]["plugins"].as_object().unwrap().is_empty());
     }
     
     #[actix_web::test]
-    async fn test_execute_nonexistent_plugin() {
-        let app = test::init_service(create_test_app()).await;
+    async fn test_metrics_reset_requires_api_key() {
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  api_key: secret\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::post().uri("/metrics/reset").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        
         let req = test::TestRequest::post()
-            .uri("/api/v1/plugins/nonexistent/execute")
-            .set_json(&ExecuteRequest {
-                input: "test".to_string(),
-            })
+            .uri("/metrics/reset")
+            .insert_header((API_KEY_HEADER, "wrong"))
             .to_request();
         let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        
+        let req = test::TestRequest::post()
+            .uri("/metrics/reset")
+            .insert_header(("Authorization", "Bearer secret"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+    }
+    
+    #[actix_web::test]
+    async fn test_stream_logs_requires_api_key() {
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  api_key: secret\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::get().uri("/api/v1/logs/stream").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        
+        let req = test::TestRequest::get()
+            .uri("/api/v1/logs/stream")
+            .insert_header((API_KEY_HEADER, "secret"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/event-stream");
+    }
+    
+    #[actix_web::test]
+    async fn test_get_config_redacts_api_key() {
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  api_key: secret\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        let req = test::TestRequest::get().uri("/api/v1/config").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::UNAUTHORIZED);
+        
+        let req = test::TestRequest::get()
+            .uri("/api/v1/config")
+            .insert_header((API_KEY_HEADER, "secret"))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["server"]["api_key"], REDACTED);
+        assert_eq!(body["data"]["log_level"], "info");
+        assert!(!body.to_string().contains("secret"));
+    }
+    
+    #[actix_web::test]
+    async fn test_response_style_shapes_execute_response() {
+        let execute = |input: &str| test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: input.to_string(), ..Default::default() })
+            .to_request();
+        let keys = |body: &serde_json::Value| {
+            let mut keys: Vec<String> = body.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  response_style: wrapped\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let body: serde_json::Value = test::call_and_read_body_json(&app, execute("hi")).await;
+        assert_eq!(keys(&body), ["data", "success"]);
+        assert_eq!(body["data"]["output"], "hi");
         
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  response_style: bare\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let body: serde_json::Value = test::call_and_read_body_json(&app, execute("hi")).await;
+        assert!(body.get("success").is_none() && body.get("data").is_none());
+        assert_eq!(body["plugin"], "echo");
+        assert_eq!(body["output"], "hi");
+        
+        // Errors keep only their message
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/missing/execute")
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
         assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(keys(&body), ["error"]);
+        
+        // Every endpoint builds its body in the configured style
+        let req = test::TestRequest::get().uri("/api/v1/plugins/echo/stats").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["plugin"], "echo");
+    }
+    
+    #[actix_web::test]
+    async fn test_reload_plugin_settings_from_disk() {
+        let (mut manager, temp_dir) = create_test_manager();
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        manager.register_plugin(Box::new(SettingsWatcherPlugin { notified: notified.clone() })).unwrap();
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        // Edit the setting on disk, as an operator would
+        let config_path = temp_dir.path().join("config.yaml");
+        let mut config: Config = serde_yaml::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        config.plugins.entry("watcher".to_string()).or_default()
+            .settings.insert("greeting".to_string(), serde_json::json!("hello"));
+        std::fs::write(&config_path, serde_yaml::to_string(&config).unwrap()).unwrap();
+        
+        let req = test::TestRequest::post().uri("/api/v1/plugins/watcher/reload-settings").to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"], serde_json::json!({"greeting": "hello"}));
+        assert_eq!(notified.lock().unwrap().len(), 1);
+        assert_eq!(notified.lock().unwrap()[0]["greeting"], "hello");
+        
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/watcher/execute")
+            .set_json(&ExecuteRequest { input: "greeting".to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], "\"hello\"");
+        
+        // Unchanged settings do not notify the plugin again
+        let req = test::TestRequest::post().uri("/api/v1/plugins/watcher/reload-settings").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(notified.lock().unwrap().len(), 1);
+        
+        // A malformed file is an error, and neither it nor the settings in effect change
+        std::fs::write(&config_path, "plugins: [unterminated\n").unwrap();
+        let req = test::TestRequest::post().uri("/api/v1/plugins/watcher/reload-settings").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), actix_web::http::StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), "plugins: [unterminated\n");
+        assert_eq!(notified.lock().unwrap().len(), 1);
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/watcher/execute")
+            .set_json(&ExecuteRequest { input: "greeting".to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], "\"hello\"");
+        
+        let req = test::TestRequest::post().uri("/api/v1/plugins/missing/reload-settings").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), actix_web::http::StatusCode::NOT_FOUND);
+    }
+    
+    #[actix_web::test]
+    async fn test_read_only_forbids_mutation_endpoints() {
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  read_only: true\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        
+        for req in [
+            test::TestRequest::put().uri("/api/v1/plugins/echo/enable"),
+            test::TestRequest::put().uri("/api/v1/plugins/echo/disable"),
+            test::TestRequest::post().uri("/api/v1/plugins/echo/toggle"),
+            test::TestRequest::put().uri("/api/v1/config").set_json(serde_json::json!({})),
+            test::TestRequest::post().uri("/api/v1/plugins/echo/custom/greeting"),
+            test::TestRequest::put().uri("/api/v1/plugins/echo/custom/greeting"),
+            test::TestRequest::post().uri("/metrics/reset"),
+            test::TestRequest::post()
+                .uri("/api/v1/plugins/echo/execute")
+                .set_json(serde_json::json!({"input": "hi", "settings": {"mode": "fast"}})),
+        ] {
+            let resp = test::call_service(&app, req.to_request()).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::FORBIDDEN);
+        }
+        
+        // Reads and executions are unaffected, and the plugin is still enabled
+        for uri in ["/api/v1/plugins", "/api/v1/health"] {
+            let resp = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+            assert_eq!(resp.status(), actix_web::http::StatusCode::OK, "{}", uri);
+        }
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["data"]["output"], "hi");
+    }
+    
+    #[actix_web::test]
+    async fn test_put_config_round_trip_and_rejects_invalid() {
+        let (manager, temp_dir) = create_test_manager_with_server_config("  api_key: secret\n");
+        let app = test::init_service(create_test_app_with_manager(manager)).await;
+        let get_config = || test::TestRequest::get()
+            .uri("/api/v1/config")
+            .insert_header((API_KEY_HEADER, "secret"))
+            .to_request();
+        let put_config = |config: &serde_json::Value| test::TestRequest::put()
+            .uri("/api/v1/config")
+            .insert_header((API_KEY_HEADER, "secret"))
+            .set_json(config)
+            .to_request();
+        
+        let body: serde_json::Value = test::call_and_read_body_json(&app, get_config()).await;
+        let mut config = body["data"].clone();
+        config["log_level"] = serde_json::json!("debug");
+        config["plugins"]["echo"] = serde_json::json!({ "enabled": false, "settings": {} });
+        let resp = test::call_service(&app, put_config(&config)).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        
+        // The change is persisted, the redacted key kept, and plugin states re-synced
+        let body: serde_json::Value = test::call_and_read_body_json(&app, get_config()).await;
+        assert_eq!(body["data"]["log_level"], "debug");
+        let saved = std::fs::read_to_string(temp_dir.path().join("config.yaml")).unwrap();
+        assert!(saved.contains("log_level: debug"), "{}", saved);
+        assert!(saved.contains("api_key: secret"), "{}", saved);
+        let req = test::TestRequest::post()
+            .uri("/api/v1/plugins/echo/execute")
+            .set_json(&ExecuteRequest { input: "hi".to_string(), ..Default::default() })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        
+        let mut invalid = config.clone();
+        invalid["log_level"] = serde_json::json!("loud");
+        let resp = test::call_service(&app, put_config(&invalid)).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error"].as_str().unwrap().contains("Invalid log level 'loud'"), "{}", body);
+        
+        let mut malformed = config.clone();
+        malformed["server"]["port"] = serde_json::json!("eighty");
+        let resp = test::call_service(&app, put_config(&malformed)).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert!(body["error"].as_str().unwrap().starts_with("Invalid configuration:"), "{}", body);
+        
+        // Rejected configurations change nothing
+        let body: serde_json::Value = test::call_and_read_body_json(&app, get_config()).await;
+        assert_eq!(body["data"]["log_level"], "debug");
+        assert_eq!(body["data"]["server"]["port"], 8080);
     }
-}
\ No newline at end of file
+    
+    #[actix_web::test]
+    async fn test_metrics_report_in_flight_executions() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(SleepPlugin)).unwrap();
+        let gauges = Arc::new(ApiGauges::default());
+        let state = web::Data::new(AppState {
+            plugin_manager: Arc::new(Mutex::new(manager)),
+            idempotency: Arc::new(IdempotencyCache::new(Duration::from_secs(300))),
+            gauges: gauges.clone(),
+            max_pending_executions: None,
+            execution_queue: Arc::new(ExecutionQueue::new(1)),
+        });
+        let open_connections = gauges.open_connections.clone();
+        
+        // A real server, so the scrape is served while the execution is still running
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(state.clone())
+                .route("/api/v1/plugins/{name}/execute", web::post().to(execute_plugin))
+                .route("/metrics", web::get().to(metrics_text))
+        })
+        .on_connect(move |_, extensions| {
+            extensions.insert(GaugeGuard::new(&open_connections));
+        })
+        .workers(2)
+        .listen(listener)
+        .unwrap()
+        .run();
+        let server_handle = server.handle();
+        actix_web::rt::spawn(server);
+        
+        let body = r#"{"input":"500"}"#;
+        let slow = tokio::task::spawn_blocking(move || http_request(port, format!(
+            "POST /api/v1/plugins/sleep/execute HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(), body
+        )));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while gauges.in_flight_executions.load(Ordering::SeqCst) == 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("execution never started");
+        
+        let metrics = tokio::task::spawn_blocking(move || http_request(port,
+            "GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n".to_string()
+        )).await.unwrap();
+        assert!(metrics.contains("# TYPE dyn_plug_in_flight_executions gauge\n"));
+        assert!(metrics.contains("\ndyn_plug_in_flight_executions 1\n"));
+        let connections: usize = metrics
+            .lines()
+            .find_map(|line| line.strip_prefix("dyn_plug_open_connections "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(connections >= 2, "expected the execution and scrape connections, got {}", connections);
+        
+        assert!(slow.await.unwrap().starts_with("HTTP/1.1 200"));
+        assert_eq!(gauges.in_flight_executions.load(Ordering::SeqCst), 0);
+        server_handle.stop(true).await;
+    }
+    
+    #[actix_web::test]
+    async fn test_shutdown_without_in_flight_requests_is_prompt() {
+        let (manager, _temp_dir) = create_test_manager_with_server_config("  graceful_shutdown_secs: 10\n");
+        let (shutdown_tx, shutdown_rx) = tokio::sync::mpsc::channel(1);
+        let server = actix_web::rt::spawn(async move {
+            start_server(manager, "127.0.0.1", 0, shutdown_rx).await.map_err(|e| e.to_string())
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        
+        let started = Instant::now();
+        shutdown_tx.send(()).await.unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(5), server).await.expect("shutdown hung").unwrap();
+        assert!(result.is_ok(), "{:?}", result);
+        assert!(started.elapsed() < Duration::from_secs(2), "shutdown took {:?}", started.elapsed());
+    }
+}
@@ -0,0 +1,50 @@
+//! Running two plugins on the same input and comparing their outputs
+
+use dyn_plug_core::{ExecutionResult, PluginManager, PluginResult};
+use serde::Serialize;
+
+/// Outcome of one side of a comparison
+#[derive(Debug, Serialize)]
+pub struct DiffSide {
+    pub plugin: String,
+    pub success: bool,
+    /// Plugin output, or the error message when the execution failed
+    pub output: String,
+}
+
+impl From<ExecutionResult> for DiffSide {
+    fn from(result: ExecutionResult) -> Self {
+        Self { plugin: result.plugin, success: result.success, output: result.output }
+    }
+}
+
+/// Comparison of two plugins' results on the same input
+#[derive(Debug, Serialize)]
+pub struct PluginDiff {
+    pub left: DiffSide,
+    pub right: DiffSide,
+    /// Whether both succeeded or both failed, with identical output
+    pub matches: bool,
+    /// Unified diff from the left output to the right output, when they differ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// Execute `left` and then `right` on `input` and compare the results
+///
+/// Errors resolving either plugin, such as it being missing or disabled, are returned.
+pub fn diff_plugins(manager: &PluginManager, left: &str, right: &str, input: &str) -> PluginResult<PluginDiff> {
+    let left = DiffSide::from(manager.execute_plugin(left, input)?);
+    let right = DiffSide::from(manager.execute_plugin(right, input)?);
+
+    let matches = left.success == right.success && left.output == right.output;
+    let diff = (!matches).then(|| {
+        similar::TextDiff::from_lines(&left.output, &right.output)
+            .unified_diff()
+            .header(&left.plugin, &right.plugin)
+            .missing_newline_hint(false)
+            .to_string()
+    });
+
+    Ok(PluginDiff { left, right, matches, diff })
+}
@@ -0,0 +1,154 @@
+//! In-memory cache backing the `Idempotency-Key` header on plugin execution
+
+use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A response recorded for an idempotency key
+#[derive(Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
+struct Entry {
+    fingerprint: String,
+    /// `None` while the request holding the key is still executing
+    response: Option<CachedResponse>,
+    stored_at: Instant,
+}
+
+/// Result of reserving an idempotency key
+pub enum Lookup<'a> {
+    /// No live entry existed; the key is now held for this request
+    Reserved(Reservation<'a>),
+    /// The key was used for the same request; replay its response
+    Replay(CachedResponse),
+    /// The same request is still executing under this key
+    InProgress,
+    /// The key was used for a different request
+    Conflict,
+}
+
+/// A key held by a request that is executing, released on drop unless a response is stored
+pub struct Reservation<'a> {
+    cache: &'a IdempotencyCache,
+    key: String,
+    stored: bool,
+}
+
+impl Reservation<'_> {
+    /// Record the response produced for the key
+    pub fn store(mut self, response: CachedResponse) {
+        let mut entries = self.cache.entries.lock().unwrap();
+        if let Some(entry) = entries.get_mut(&self.key) {
+            entry.response = Some(response);
+            entry.stored_at = Instant::now();
+        }
+        self.stored = true;
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if !self.stored {
+            self.cache.entries.lock().unwrap().remove(&self.key);
+        }
+    }
+}
+
+/// Responses keyed by idempotency key, kept for a fixed time-to-live
+pub struct IdempotencyCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl IdempotencyCache {
+    /// Create a cache whose entries expire after `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Look up a key, holding it for the caller if it is free
+    ///
+    /// The stored request fingerprint is compared with `fingerprint`, so a key can
+    /// only be replayed for the request it was first used with.
+    pub fn reserve(&self, key: &str, fingerprint: &str) -> Lookup<'_> {
+        let mut entries = self.entries.lock().unwrap();
+        Self::evict_expired(&mut entries, self.ttl);
+
+        match entries.get(key) {
+            None => {
+                entries.insert(
+                    key.to_string(),
+                    Entry {
+                        fingerprint: fingerprint.to_string(),
+                        response: None,
+                        stored_at: Instant::now(),
+                    },
+                );
+                Lookup::Reserved(Reservation { cache: self, key: key.to_string(), stored: false })
+            }
+            Some(entry) if entry.fingerprint != fingerprint => Lookup::Conflict,
+            Some(Entry { response: Some(response), .. }) => Lookup::Replay(response.clone()),
+            Some(_) => Lookup::InProgress,
+        }
+    }
+
+    fn evict_expired(entries: &mut HashMap<String, Entry>, ttl: Duration) {
+        entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            status: StatusCode::OK,
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    fn store(cache: &IdempotencyCache, key: &str, fingerprint: &str, body: &'static str) {
+        match cache.reserve(key, fingerprint) {
+            Lookup::Reserved(reservation) => reservation.store(response(body)),
+            _ => panic!("key '{}' is not free", key),
+        }
+    }
+
+    #[test]
+    fn test_lookup_outcomes() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        store(&cache, "key", "a", "first");
+        assert!(matches!(cache.reserve("key", "a"), Lookup::Replay(r) if r.body == "first"));
+        assert!(matches!(cache.reserve("key", "b"), Lookup::Conflict));
+        assert!(matches!(cache.reserve("other", "a"), Lookup::Reserved(_)));
+    }
+
+    #[test]
+    fn test_reserved_key_is_in_progress_until_released() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60));
+        let reservation = cache.reserve("key", "a");
+        assert!(matches!(cache.reserve("key", "a"), Lookup::InProgress));
+        assert!(matches!(cache.reserve("key", "b"), Lookup::Conflict));
+
+        // Dropping a reservation without a response frees the key
+        drop(reservation);
+        assert!(matches!(cache.reserve("key", "b"), Lookup::Reserved(_)));
+    }
+
+    #[test]
+    fn test_entries_expire() {
+        let cache = IdempotencyCache::new(Duration::from_millis(20));
+        store(&cache, "key", "a", "first");
+        std::thread::sleep(Duration::from_millis(40));
+        assert!(matches!(cache.reserve("key", "a"), Lookup::Reserved(_)));
+    }
+}
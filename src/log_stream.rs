@@ -0,0 +1,74 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::{Serialize, Serializer};
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+
+/// Log records buffered per subscriber before slow subscribers start missing records
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// A log record as delivered to log stream subscribers
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEvent {
+    pub timestamp: String,
+    #[serde(serialize_with = "serialize_level")]
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+fn serialize_level<S: Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(level)
+}
+
+fn sender() -> &'static broadcast::Sender<LogEvent> {
+    static SENDER: OnceLock<broadcast::Sender<LogEvent>> = OnceLock::new();
+    SENDER.get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0)
+}
+
+/// Receive log records emitted from now on
+pub fn subscribe() -> broadcast::Receiver<LogEvent> {
+    sender().subscribe()
+}
+
+/// Logger that writes through `inner` and also forwards records to subscribers
+///
+/// Only records passing the inner logger's filter are forwarded.
+struct BroadcastLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for BroadcastLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.inner.log(record);
+
+        let sender = sender();
+        if sender.receiver_count() > 0 {
+            // Sending only fails when every subscriber has gone away
+            let _ = sender.send(LogEvent {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                level: record.level(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install `inner` as the global logger, forwarding its records to subscribers
+pub fn install(inner: env_logger::Logger) -> Result<(), log::SetLoggerError> {
+    let max_level: LevelFilter = inner.filter();
+    log::set_boxed_logger(Box::new(BroadcastLogger { inner }))?;
+    log::set_max_level(max_level);
+    Ok(())
+}
@@ -1,10 +1,18 @@
-use clap::{Parser, Subcommand};
-use dyn_plug_core::{PluginManager, PluginError};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use dyn_plug_core::{ExecutionOptions, ExecutionResult, MetricsSnapshot, PluginManager, PluginError, PluginStatus};
 use log::{debug, error, info, warn};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::process;
 
 mod api;
+mod diff_exec;
+mod idempotency;
+mod log_stream;
+#[cfg(test)]
+mod test_plugins;
 
 /// Initialize logging with configurable levels
 /// 
@@ -23,11 +31,13 @@ fn initialize_logging() {
         env::set_var("RUST_LOG", &log_level);
     }
     
-    // Initialize env_logger with timestamp and target information
-    env_logger::Builder::from_default_env()
+    // Initialize env_logger with timestamp and target information, also
+    // forwarding records to `/api/v1/logs/stream` subscribers
+    let logger = env_logger::Builder::from_default_env()
         .format_timestamp_secs()
         .format_target(true)
-        .init();
+        .build();
+    log_stream::install(logger).expect("logger already initialized");
     
     info!("Logging initialized with level: {}", log_level);
     debug!("Debug logging is enabled");
@@ -67,11 +77,88 @@ fn is_transient_error(error: &PluginError) -> bool {
     error.is_transient()
 }
 
+/// Exit code for failures without a more specific code
+const EXIT_FAILURE: i32 = 1;
+/// Exit code when the requested plugin does not exist
+const EXIT_NOT_FOUND: i32 = 2;
+/// Exit code when the requested plugin is disabled
+const EXIT_DISABLED: i32 = 3;
+/// Exit code when the plugin ran but returned an error
+const EXIT_EXECUTION_ERROR: i32 = 4;
+/// Exit code when the plugin timed out
+const EXIT_TIMEOUT: i32 = 5;
+
+/// A command failure carrying the process exit code and error category to report
+#[derive(Debug)]
+struct CliError {
+    code: i32,
+    category: String,
+    message: String,
+}
+
+impl CliError {
+    fn new<C: Into<String>, S: Into<String>>(code: i32, category: C, message: S) -> Self {
+        Self { code, category: category.into(), message: message.into() }
+    }
+
+    fn not_found<S: Into<String>>(message: S) -> Self {
+        Self::new(EXIT_NOT_FOUND, "not_found", message)
+    }
+
+    fn disabled<S: Into<String>>(message: S) -> Self {
+        Self::new(EXIT_DISABLED, "plugin_disabled", message)
+    }
+
+    /// A plugin that ran and failed, classified by the category of its error
+    fn execution_failed<S: Into<String>>(category: Option<&str>, message: S) -> Self {
+        let category = category.unwrap_or("execution_failed");
+        let code = match category {
+            "timeout_error" => EXIT_TIMEOUT,
+            _ => EXIT_EXECUTION_ERROR,
+        };
+        Self::new(code, category, message)
+    }
+
+    fn from_plugin_error(error: &PluginError) -> Self {
+        Self::new(exit_code_for(error), error.category(), error.user_friendly_message())
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Map a plugin error to the process exit code convention
+fn exit_code_for(error: &PluginError) -> i32 {
+    match error {
+        PluginError::NotFound { .. } => EXIT_NOT_FOUND,
+        PluginError::PluginDisabled { .. } => EXIT_DISABLED,
+        PluginError::TimeoutError { .. } => EXIT_TIMEOUT,
+        PluginError::ExecutionFailed { .. }
+        | PluginError::PluginFailure { .. }
+        | PluginError::InvalidInput { .. }
+        | PluginError::TemporaryFailure { .. }
+        | PluginError::ResourceExhausted { .. } => EXIT_EXECUTION_ERROR,
+        _ => EXIT_FAILURE,
+    }
+}
+
 #[derive(Parser)]
 #[command(name = "dyn-plug")]
 #[command(about = "A pluggable service system")]
 #[command(version = "0.1.0")]
+#[command(after_help = "Exit codes:\n  0  success\n  1  general failure\n  2  plugin not found\n  3  plugin disabled\n  4  plugin execution error\n  5  plugin timed out")]
 struct Cli {
+    /// Suppress human-facing output such as the server banner
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    /// Print errors to stderr as JSON objects with error, category, and code fields
+    #[arg(long, global = true)]
+    json_errors: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -79,16 +166,57 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// List all available plugins with their status
-    List,
+    List {
+        /// Field to order plugins by
+        #[arg(long, value_enum, default_value = "name")]
+        sort: ListSort,
+        /// Reverse the sort order
+        #[arg(long)]
+        reverse: bool,
+        /// Print only the number of enabled, disabled and failed plugins
+        #[arg(long)]
+        count: bool,
+        /// Output format for the counts
+        #[arg(long, value_enum, requires = "count")]
+        format: Option<OutputFormat>,
+    },
+    /// Show details and example inputs for a plugin
+    Info {
+        /// Name of the plugin to describe
+        name: String,
+    },
+    /// List the operations each plugin declares
+    Operations {
+        /// Print one operation per line with the plugin providing it
+        #[arg(long)]
+        flat: bool,
+    },
+    /// Print a plugin's input JSON Schema
+    Schema {
+        /// Name of the plugin
+        name: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "json")]
+        format: OutputFormat,
+        /// Write the schema to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+    },
     /// Enable a plugin
     Enable {
         /// Name of the plugin to enable
         name: String,
+        /// Show the resulting state without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Disable a plugin
     Disable {
         /// Name of the plugin to disable
         name: String,
+        /// Show the resulting state without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Execute a plugin with optional input
     Execute {
@@ -97,6 +225,58 @@ enum Commands {
         /// Input to pass to the plugin (optional)
         #[arg(short, long)]
         input: Option<String>,
+        /// Print how the execution will be dispatched before running it
+        #[arg(long)]
+        explain: bool,
+    },
+    /// Run two plugins on the same input and show whether their outputs match
+    DiffExec {
+        /// Plugin whose output is the baseline
+        left: String,
+        /// Plugin compared against the baseline
+        right: String,
+        /// Input to pass to both plugins (optional)
+        #[arg(short, long)]
+        input: Option<String>,
+    },
+    /// Execute a batch of plugin invocations read from a JSON file
+    Batch {
+        /// Path to a JSON array of {"plugin": "...", "input": "..."} items
+        file: std::path::PathBuf,
+        /// Number of items to execute concurrently (sequential when omitted)
+        #[arg(long)]
+        parallel: Option<usize>,
+    },
+    /// Benchmark every enabled plugin by running its first example input
+    BenchAll {
+        /// Number of times to run each plugin
+        #[arg(long, default_value = "10")]
+        repeat: usize,
+    },
+    /// Run every enabled plugin's example inputs and report which fail
+    Selftest,
+    /// Poll a running server's metrics and show a refreshing per-plugin table
+    TailMetrics {
+        /// Base URL of the server, e.g. http://127.0.0.1:8080
+        #[arg(long)]
+        url: String,
+        /// Time between polls, e.g. 500ms, 2s or 1m
+        #[arg(long, default_value = "2s", value_parser = parse_interval)]
+        interval: std::time::Duration,
+    },
+    /// Show recent plugin executions
+    History {
+        /// Only show executions strictly after this RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
+        /// Maximum number of most recent executions to show
+        #[arg(short, long)]
+        limit: Option<usize>,
+    },
+    /// Inspect the effective configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
     },
     /// Start the HTTP API server
     Serve {
@@ -106,45 +286,121 @@ enum Commands {
         /// Host to bind the server to
         #[arg(long, default_value = "127.0.0.1")]
         host: String,
+        /// Do not print the startup banner and endpoint list
+        #[arg(long)]
+        no_banner: bool,
+    },
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
     },
 }
 
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the fully-resolved configuration currently in use, with the API key redacted
+    Dump {
+        /// Output format
+        #[arg(long, value_enum, default_value = "yaml")]
+        format: OutputFormat,
+    },
+}
+
+/// Ordering for `list` output
+#[derive(Clone, Copy, ValueEnum)]
+enum ListSort {
+    Name,
+    Version,
+    Status,
+}
+
+/// Serialization format for structured CLI output
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Yaml,
+    Json,
+}
+
 fn main() {
     // Initialize logging with configurable levels
     initialize_logging();
+    dyn_plug_core::panic_hook::install();
     
     let cli = Cli::parse();
     
+    // Completions only need the command definition, not loaded plugins
+    if let Commands::Completions { shell } = cli.command {
+        write_completions(&mut std::io::stdout(), shell);
+        return;
+    }
+    
     // Initialize plugin manager with retry logic for transient failures
     let mut manager = match initialize_plugin_manager_with_retry() {
         Ok(manager) => manager,
         Err(e) => {
             error!("Failed to initialize plugin manager after retries: {}", e);
-            process::exit(1);
+            exit_with_error(&CliError::new(EXIT_FAILURE, "initialization_failed", e.to_string()), cli.json_errors);
         }
     };
     
     // Execute the requested command
     let result = match cli.command {
-        Commands::List => handle_list(&manager),
-        Commands::Enable { name } => handle_enable(&mut manager, &name),
-        Commands::Disable { name } => handle_disable(&mut manager, &name),
-        Commands::Execute { name, input } => handle_execute(&manager, &name, input.as_deref()),
-        Commands::Serve { port, host } => handle_serve(manager, &host, port),
+        Commands::List { count: true, format, .. } => handle_list_count(&manager, format),
+        Commands::List { sort, reverse, .. } => handle_list(&manager, sort, reverse),
+        Commands::Info { name } => handle_info(&manager, &name),
+        Commands::Operations { flat } => handle_operations(&manager, flat),
+        Commands::Schema { name, format, output } => handle_schema(&manager, &name, format, output.as_deref()),
+        Commands::Enable { name, dry_run } => handle_enable(&mut manager, &name, dry_run),
+        Commands::Disable { name, dry_run } => handle_disable(&mut manager, &name, dry_run),
+        Commands::Execute { name, input, explain } => handle_execute(&manager, &name, input.as_deref(), explain),
+        Commands::DiffExec { left, right, input } => handle_diff_exec(&manager, &left, &right, input.as_deref()),
+        Commands::Batch { file, parallel } => handle_batch(&manager, &file, parallel),
+        Commands::BenchAll { repeat } => handle_bench_all(&manager, repeat),
+        Commands::Selftest => handle_selftest(&manager),
+        Commands::TailMetrics { url, interval } => handle_tail_metrics(&url, interval),
+        Commands::History { since, limit } => handle_history(&manager, since.as_deref(), limit),
+        Commands::Config { action: ConfigCommands::Dump { format } } => handle_config_dump(&manager, format),
+        Commands::Serve { port, host, no_banner } => handle_serve(manager, &host, port, no_banner || cli.quiet),
+        Commands::Completions { .. } => unreachable!("completions are printed before plugins are loaded"),
     };
     
     // Handle command result
     if let Err(e) = result {
         error!("Command failed: {}", e);
-        process::exit(1);
+        let error = match e.downcast::<CliError>() {
+            Ok(error) => *error,
+            Err(e) => CliError::new(EXIT_FAILURE, "general", e.to_string()),
+        };
+        exit_with_error(&error, cli.json_errors);
     }
 }
 
-fn handle_list(manager: &PluginManager) -> Result<(), Box<dyn std::error::Error>> {
+/// Report a command failure and exit with its code
+///
+/// With `--json-errors` the failure is printed to stderr as a single JSON object.
+fn exit_with_error(error: &CliError, json_errors: bool) -> ! {
+    if json_errors {
+        eprintln!("{}", error_json(error));
+    }
+    process::exit(error.code);
+}
+
+fn error_json(error: &CliError) -> serde_json::Value {
+    serde_json::json!({
+        "error": error.message,
+        "category": error.category,
+        "code": error.code,
+    })
+}
+
+fn handle_list(manager: &PluginManager, sort: ListSort, reverse: bool) -> Result<(), Box<dyn std::error::Error>> {
     info!("CLI: Starting plugin list operation");
     debug!("CLI: Retrieving plugin information from manager");
     
-    let plugins = manager.list_plugins();
+    let mut plugins = manager.list_plugins();
+    sort_plugins(&mut plugins, sort, reverse);
     
     info!("CLI: Found {} plugins", plugins.len());
     
@@ -162,13 +418,12 @@ fn handle_list(manager: &PluginManager) -> Result<(), Box<dyn std::error::Error>
     let mut disabled_count = 0;
     
     for plugin in plugins {
-        let status = if plugin.enabled && plugin.config_enabled {
+        let status = plugin_status_label(&plugin);
+        if status == "enabled" {
             enabled_count += 1;
-            "enabled"
         } else {
             disabled_count += 1;
-            "disabled"
-        };
+        }
         
         debug!("CLI: Plugin {} - status: {}, loaded: {}", plugin.name, status, plugin.loaded);
         
@@ -185,7 +440,142 @@ fn handle_list(manager: &PluginManager) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
-fn handle_enable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+/// Plugin totals printed by `list --count`
+#[derive(Debug, PartialEq, Serialize)]
+struct PluginCounts {
+    enabled: usize,
+    disabled: usize,
+    /// Enabled plus disabled; libraries that failed to load are counted separately
+    total: usize,
+    failed: usize,
+}
+
+fn count_plugins(plugins: &[PluginStatus], failed: usize) -> PluginCounts {
+    let enabled = plugins.iter().filter(|p| plugin_status_label(p) == "enabled").count();
+    PluginCounts { enabled, disabled: plugins.len() - enabled, total: plugins.len(), failed }
+}
+
+fn write_counts<W: std::io::Write>(out: &mut W, counts: &PluginCounts, format: Option<OutputFormat>) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        None => writeln!(out, "enabled={} disabled={} total={} failed={}", counts.enabled, counts.disabled, counts.total, counts.failed)?,
+        Some(OutputFormat::Json) => writeln!(out, "{}", serde_json::to_string(counts)?)?,
+        Some(OutputFormat::Yaml) => write!(out, "{}", serde_yaml::to_string(counts)?)?,
+    }
+    Ok(())
+}
+
+fn handle_list_count(manager: &PluginManager, format: Option<OutputFormat>) -> Result<(), Box<dyn std::error::Error>> {
+    let counts = count_plugins(&manager.list_plugins(), manager.load_failures().len());
+    info!("CLI: Plugin counts - {} enabled, {} disabled, {} failed", counts.enabled, counts.disabled, counts.failed);
+    write_counts(&mut std::io::stdout().lock(), &counts, format)
+}
+
+fn handle_info(manager: &PluginManager, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Showing info for plugin: {}", name);
+    
+    let status = manager.get_plugin_status(name).ok_or_else(|| {
+        error!("CLI: Plugin '{}' not found in registry", name);
+        CliError::not_found(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name))
+    })?;
+    let examples = manager.get_plugin_examples(name).unwrap_or_default();
+    
+    println!("Name:        {}", status.name);
+    println!("Version:     {}", status.version);
+    println!("Description: {}", status.description);
+    println!("Status:      {}", if status.enabled && status.config_enabled { "enabled" } else { "disabled" });
+    println!("Path:        {}", status.path.display());
+    if let Some(changed) = &status.last_state_change {
+        println!("Toggled:     {}", changed);
+    }
+    
+    if examples.is_empty() {
+        println!("\nNo examples provided.");
+        return Ok(());
+    }
+    
+    println!("\nExamples:");
+    for (description, input) in examples {
+        println!("  {}", description);
+        println!("    dyn-plug execute {} --input '{}'", status.name, input);
+    }
+    
+    Ok(())
+}
+
+fn handle_operations(manager: &PluginManager, flat: bool) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Listing plugin operations");
+    write_operations(&mut std::io::stdout().lock(), &manager.list_operations(), flat)?;
+    Ok(())
+}
+
+/// Write each plugin's operations, or one `operation plugin` line per operation when `flat`
+fn write_operations<W: std::io::Write>(
+    out: &mut W,
+    operations: &BTreeMap<String, Vec<String>>,
+    flat: bool,
+) -> std::io::Result<()> {
+    if operations.is_empty() {
+        writeln!(out, "No plugins declare operations.")?;
+        return Ok(());
+    }
+    
+    if flat {
+        let mut rows: Vec<(&str, &str)> = operations
+            .iter()
+            .flat_map(|(plugin, ops)| ops.iter().map(move |op| (op.as_str(), plugin.as_str())))
+            .collect();
+        rows.sort();
+        writeln!(out, "{:<20} {:<20}", "Operation", "Plugin")?;
+        for (operation, plugin) in rows {
+            writeln!(out, "{:<20} {:<20}", operation, plugin)?;
+        }
+    } else {
+        for (plugin, ops) in operations {
+            writeln!(out, "{}: {}", plugin, ops.join(", "))?;
+        }
+    }
+    Ok(())
+}
+
+fn handle_schema(
+    manager: &PluginManager,
+    name: &str,
+    format: OutputFormat,
+    output: Option<&std::path::Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Showing input schema for plugin: {}", name);
+    
+    let schema = match manager.get_plugin_schema(name) {
+        Ok(Some(schema)) => schema,
+        Ok(None) => {
+            println!("Plugin '{}' does not publish an input schema.", name);
+            return Ok(());
+        }
+        Err(PluginError::NotFound { .. }) => {
+            error!("CLI: Plugin '{}' not found in registry", name);
+            return Err(CliError::not_found(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name)).into());
+        }
+        Err(e) => return Err(e.user_friendly_message().into()),
+    };
+    
+    let rendered = match format {
+        OutputFormat::Json => serde_json::to_string_pretty(&schema)?,
+        OutputFormat::Yaml => serde_yaml::to_string(&schema)?,
+    };
+    
+    match output {
+        Some(path) => {
+            std::fs::write(path, format!("{}\n", rendered.trim_end()))
+                .map_err(|e| format!("Failed to write schema to '{}': {}", path.display(), e))?;
+            println!("Schema for plugin '{}' written to {}", name, path.display());
+        }
+        None => println!("{}", rendered.trim_end()),
+    }
+    
+    Ok(())
+}
+
+fn handle_enable(manager: &mut PluginManager, name: &str, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
     info!("CLI: Starting enable operation for plugin: {}", name);
     debug!("CLI: Checking if plugin '{}' exists before enabling", name);
     
@@ -199,7 +589,7 @@ fn handle_enable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
         debug!("CLI: Available plugins: {:?}", available_plugins);
         
         error!("Plugin '{}' not found.", name);
-        return Err(format!(
+        return Err(CliError::not_found(format!(
             "Plugin '{}' not found. Available plugins: {}. Use 'list' command for details.", 
             name, 
             if available_plugins.is_empty() { 
@@ -207,7 +597,14 @@ fn handle_enable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
             } else { 
                 available_plugins.join(", ") 
             }
-        ).into());
+        )).into());
+    }
+    
+    if dry_run {
+        if let Some(status) = manager.get_plugin_status(name) {
+            write_state_preview(&mut std::io::stdout(), &status, true)?;
+        }
+        return Ok(());
     }
     
     match manager.enable_plugin(name) {
@@ -218,7 +615,7 @@ fn handle_enable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
         }
         Err(PluginError::NotFound { .. }) => {
             error!("CLI: Plugin '{}' not found during enable operation", name);
-            Err(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name).into())
+            Err(CliError::not_found(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name)).into())
         }
         Err(e) => {
             error!("CLI: Failed to enable plugin '{}': {} (category: {})", name, e, e.category());
@@ -227,7 +624,7 @@ fn handle_enable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
     }
 }
 
-fn handle_disable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn handle_disable(manager: &mut PluginManager, name: &str, dry_run: bool) -> Result<(), Box<dyn std::error::Error>> {
     info!("CLI: Starting disable operation for plugin: {}", name);
     debug!("CLI: Checking if plugin '{}' exists before disabling", name);
     
@@ -241,7 +638,7 @@ fn handle_disable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
         debug!("CLI: Available plugins: {:?}", available_plugins);
         
         error!("Plugin '{}' not found.", name);
-        return Err(format!(
+        return Err(CliError::not_found(format!(
             "Plugin '{}' not found. Available plugins: {}. Use 'list' command for details.", 
             name, 
             if available_plugins.is_empty() { 
@@ -249,7 +646,14 @@ fn handle_disable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
             } else { 
                 available_plugins.join(", ") 
             }
-        ).into());
+        )).into());
+    }
+    
+    if dry_run {
+        if let Some(status) = manager.get_plugin_status(name) {
+            write_state_preview(&mut std::io::stdout(), &status, false)?;
+        }
+        return Ok(());
     }
     
     match manager.disable_plugin(name) {
@@ -260,7 +664,7 @@ fn handle_disable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
         }
         Err(PluginError::NotFound { .. }) => {
             error!("CLI: Plugin '{}' not found during disable operation", name);
-            Err(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name).into())
+            Err(CliError::not_found(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name)).into())
         }
         Err(e) => {
             error!("CLI: Failed to disable plugin '{}': {} (category: {})", name, e, e.category());
@@ -269,16 +673,31 @@ fn handle_disable(manager: &mut PluginManager, name: &str) -> Result<(), Box<dyn
     }
 }
 
+/// Print the state a plugin would move to, without changing it
+fn write_state_preview<W: std::io::Write>(out: &mut W, status: &PluginStatus, enable: bool) -> std::io::Result<()> {
+    let label = |enabled: bool| if enabled { "enabled" } else { "disabled" };
+    let current = status.enabled && status.config_enabled;
+    if current == enable {
+        writeln!(out, "{}: {} (no change)", status.name, label(current))
+    } else {
+        writeln!(out, "{}: {} -> {}", status.name, label(current), label(enable))
+    }
+}
+
 fn handle_execute(
     manager: &PluginManager,
     name: &str,
     input: Option<&str>,
+    explain: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let input_str = input.unwrap_or("");
+    if explain {
+        write_explain(&mut std::io::stdout(), manager, name, input_str, &manager.execution_options(name))?;
+    }
     info!("CLI: Starting execution of plugin '{}' with input length: {}", name, input_str.len());
     debug!("CLI: Plugin '{}' input content: '{}'", name, 
            if input_str.len() > 100 { 
-               format!("{}...", &input_str[..100]) 
+               format!("{}...", prefix_at_char_boundary(input_str, 100)) 
            } else { 
                input_str.to_string() 
            });
@@ -290,10 +709,10 @@ fn handle_execute(
         
         if !status.enabled || !status.config_enabled {
             warn!("CLI: Attempted to execute disabled plugin '{}'", name);
-            return Err(format!(
+            return Err(CliError::disabled(format!(
                 "Plugin '{}' is disabled. Use 'enable {}' to enable it first.", 
                 name, name
-            ).into());
+            )).into());
         }
     } else {
         warn!("CLI: Plugin '{}' not found in registry", name);
@@ -304,7 +723,7 @@ fn handle_execute(
             .collect();
         debug!("CLI: Available enabled plugins: {:?}", available_plugins);
         
-        return Err(format!(
+        return Err(CliError::not_found(format!(
             "Plugin '{}' not found. Available enabled plugins: {}. Use 'list' command for details.", 
             name, 
             if available_plugins.is_empty() { 
@@ -312,7 +731,7 @@ fn handle_execute(
             } else { 
                 available_plugins.join(", ") 
             }
-        ).into());
+        )).into());
     }
     
     match manager.execute_plugin(name, input_str) {
@@ -322,40 +741,569 @@ fn handle_execute(
                       name, result.duration_ms, result.output.len());
                 debug!("CLI: Plugin '{}' output: {}", name, 
                        if result.output.len() > 200 { 
-                           format!("{}...", &result.output[..200]) 
+                           format!("{}...", prefix_at_char_boundary(&result.output, 200)) 
                        } else { 
                            result.output.clone() 
                        });
                 
-                println!("Plugin '{}' executed successfully:", name);
-                println!("Output: {}", result.output);
-                println!("Duration: {}ms", result.duration_ms);
+                write_execution_result(&mut std::io::stdout(), &result)?;
             } else {
                 error!("CLI: Plugin '{}' execution failed after {}ms: {}", 
                        name, result.duration_ms, result.output);
-                return Err(format!("Plugin execution failed: {}", result.output).into());
+                return Err(CliError::execution_failed(
+                    result.error_category.as_deref(),
+                    format!("Plugin execution failed: {}", result.output),
+                ).into());
             }
             Ok(())
         }
         Err(PluginError::NotFound { .. }) => {
             error!("CLI: Plugin '{}' not found during execution", name);
-            Err(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name).into())
+            Err(CliError::not_found(format!("Plugin '{}' not found. Use 'list' command to see available plugins.", name)).into())
         }
         Err(PluginError::PluginDisabled { .. }) => {
             error!("CLI: Plugin '{}' is disabled during execution", name);
-            Err(format!("Plugin '{}' is disabled. Use 'enable {}' to enable it first.", name, name).into())
+            Err(CliError::disabled(format!("Plugin '{}' is disabled. Use 'enable {}' to enable it first.", name, name)).into())
         }
         Err(e) => {
             error!("CLI: Failed to execute plugin '{}': {} (category: {})", name, e, e.category());
-            Err(e.user_friendly_message().into())
+            Err(CliError::from_plugin_error(&e).into())
+        }
+    }
+}
+
+/// Print which plugin an execution resolves to, its enabled state, the execution
+/// options and the operation parsed from the input
+fn write_explain<W: std::io::Write>(
+    out: &mut W,
+    manager: &PluginManager,
+    name: &str,
+    input: &str,
+    options: &ExecutionOptions,
+) -> std::io::Result<()> {
+    writeln!(out, "Explain:")?;
+    match manager.get_plugin_status(name) {
+        Some(status) => {
+            writeln!(out, "  Plugin: {} v{} ({})", status.name, status.version, status.path.display())?;
+            writeln!(out, "  Enabled: {} (config: {})", status.enabled, status.config_enabled)?;
+        }
+        None => writeln!(out, "  Plugin: {} (not found)", name)?,
+    }
+    let isolation = manager.config().plugins.get(name).map(|p| p.isolation).unwrap_or_default();
+    writeln!(out, "  Isolation: {:?}", isolation)?;
+    match options.timeout {
+        Some(timeout) => writeln!(out, "  Timeout: {}ms", timeout.as_millis())?,
+        None => writeln!(out, "  Timeout: none")?,
+    }
+    writeln!(out, "  Retries: {} (delay {}ms)", options.max_retries, options.retry_delay.as_millis())?;
+    match dyn_plug_core::plugin_prelude::parse_operation(input) {
+        Ok((operation, _)) => writeln!(out, "  Operation: {}", operation)?,
+        Err(_) => writeln!(out, "  Operation: (none)")?,
+    }
+    writeln!(out)
+}
+
+/// Print a successful execution, marking empty output so it does not look truncated
+fn write_execution_result<W: std::io::Write>(out: &mut W, result: &ExecutionResult) -> std::io::Result<()> {
+    writeln!(out, "Plugin '{}' executed successfully:", result.plugin)?;
+    if result.output.is_empty() {
+        writeln!(out, "Output: (empty)")?;
+    } else {
+        writeln!(out, "Output: {}", result.output)?;
+    }
+    writeln!(out, "Duration: {}ms", result.duration_ms)
+}
+
+fn handle_diff_exec(
+    manager: &PluginManager,
+    left: &str,
+    right: &str,
+    input: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Comparing plugins '{}' and '{}'", left, right);
+    let diff = diff_exec::diff_plugins(manager, left, right, input.unwrap_or(""))
+        .map_err(|e| CliError::from_plugin_error(&e))?;
+    write_plugin_diff(&mut std::io::stdout(), &diff)?;
+
+    if !diff.matches {
+        return Err(CliError::new(
+            EXIT_FAILURE,
+            "output_mismatch",
+            format!("Outputs of '{}' and '{}' differ", left, right),
+        ).into());
+    }
+    Ok(())
+}
+
+/// Print whether two plugins' outputs match, with a unified diff when they do not
+fn write_plugin_diff<W: std::io::Write>(out: &mut W, diff: &diff_exec::PluginDiff) -> std::io::Result<()> {
+    if diff.matches {
+        return writeln!(out, "Outputs of '{}' and '{}' match", diff.left.plugin, diff.right.plugin);
+    }
+
+    writeln!(out, "Outputs of '{}' and '{}' differ:", diff.left.plugin, diff.right.plugin)?;
+    for side in [&diff.left, &diff.right] {
+        if !side.success {
+            writeln!(out, "'{}' failed: {}", side.plugin, side.output)?;
+        }
+    }
+    write!(out, "{}", diff.diff.as_deref().unwrap_or_default())
+}
+
+/// A single plugin invocation in a batch file
+#[derive(Debug, Deserialize)]
+struct BatchItem {
+    plugin: String,
+    #[serde(default)]
+    input: String,
+}
+
+/// Outcome of a single batch item
+#[derive(Debug, Serialize)]
+struct BatchItemResult {
+    plugin: String,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    duration_ms: u64,
+    queue_ms: u64,
+    exec_ms: u64,
+}
+
+fn handle_batch(
+    manager: &PluginManager,
+    file: &std::path::Path,
+    parallel: Option<usize>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Starting batch execution from {}", file.display());
+    
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read batch file '{}': {}", file.display(), e))?;
+    let items: Vec<BatchItem> = serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid batch file '{}': {}", file.display(), e))?;
+    
+    let results = run_batch(manager, &items, parallel)?;
+    let failed = results.iter().filter(|r| !r.success).count();
+    info!("CLI: Batch completed - {} succeeded, {} failed", results.len() - failed, failed);
+    
+    println!("{}", serde_json::to_string_pretty(&results)?);
+    Ok(())
+}
+
+/// Execute batch items, preserving input order in the results
+///
+/// With `parallel` set, items run on a dedicated thread pool of that size and
+/// report the time they spent queued for a free worker.
+fn run_batch(
+    manager: &PluginManager,
+    items: &[BatchItem],
+    parallel: Option<usize>,
+) -> Result<Vec<BatchItemResult>, Box<dyn std::error::Error>> {
+    let execute = |item: &BatchItem, options: ExecutionOptions| match manager.execute_plugin_with_options(&item.plugin, &item.input, options) {
+        Ok(result) if result.success => BatchItemResult {
+            plugin: item.plugin.clone(),
+            success: true,
+            output: Some(result.output),
+            error: None,
+            duration_ms: result.duration_ms,
+            queue_ms: result.queue_ms,
+            exec_ms: result.exec_ms,
+        },
+        Ok(result) => BatchItemResult {
+            plugin: item.plugin.clone(),
+            success: false,
+            output: None,
+            error: Some(result.output),
+            duration_ms: result.duration_ms,
+            queue_ms: result.queue_ms,
+            exec_ms: result.exec_ms,
+        },
+        Err(e) => {
+            warn!("CLI: Batch item for plugin '{}' failed: {} (category: {})", item.plugin, e, e.category());
+            BatchItemResult {
+                plugin: item.plugin.clone(),
+                success: false,
+                output: None,
+                error: Some(e.user_friendly_message()),
+                duration_ms: 0,
+                queue_ms: 0,
+                exec_ms: 0,
+            }
+        }
+    };
+    
+    match parallel {
+        None => Ok(items.iter().map(|item| execute(item, manager.execution_options(&item.plugin))).collect()),
+        Some(0) => Err("--parallel must be at least 1".into()),
+        Some(threads) => {
+            debug!("CLI: Executing {} batch items on {} threads", items.len(), threads);
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()?;
+            // Every item is queued up front; a worker picks it up when one is free
+            let enqueued_at = std::time::Instant::now();
+            Ok(pool.install(|| {
+                items
+                    .par_iter()
+                    .map(|item| execute(item, manager.execution_options(&item.plugin).with_enqueued_at(enqueued_at)))
+                    .collect()
+            }))
         }
     }
 }
 
+/// Latency summary for one plugin in `bench-all`
+struct BenchResult {
+    plugin: String,
+    runs: usize,
+    failures: usize,
+    min_ms: f64,
+    mean_ms: f64,
+    max_ms: f64,
+}
+
+/// Results of `bench-all`, plus the enabled plugins skipped for lack of examples
+struct BenchReport {
+    results: Vec<BenchResult>,
+    skipped: Vec<String>,
+}
+
+fn handle_bench_all(manager: &PluginManager, repeat: usize) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Benchmarking enabled plugins with {} runs each", repeat);
+    
+    if repeat == 0 {
+        return Err("--repeat must be at least 1".into());
+    }
+    
+    let report = run_bench_all(manager, repeat);
+    write_bench_report(&mut std::io::stdout().lock(), &report)?;
+    Ok(())
+}
+
+/// Run each enabled plugin's first example `repeat` times, fastest plugins first
+fn run_bench_all(manager: &PluginManager, repeat: usize) -> BenchReport {
+    let mut plugins = manager.list_plugins();
+    sort_plugins(&mut plugins, ListSort::Name, false);
+    
+    let mut results = Vec::new();
+    let mut skipped = Vec::new();
+    for plugin in plugins.iter().filter(|p| plugin_status_label(p) == "enabled") {
+        let input = match manager.get_plugin_examples(&plugin.name).and_then(|e| e.into_iter().next()) {
+            Some((_, input)) => input,
+            None => {
+                debug!("CLI: Skipping plugin '{}' in benchmark, it has no examples", plugin.name);
+                skipped.push(plugin.name.clone());
+                continue;
+            }
+        };
+        
+        let mut timings = Vec::with_capacity(repeat);
+        let mut failures = 0;
+        for _ in 0..repeat {
+            let start = std::time::Instant::now();
+            let succeeded = matches!(manager.execute_plugin(&plugin.name, &input), Ok(result) if result.success);
+            timings.push(start.elapsed().as_secs_f64() * 1000.0);
+            if !succeeded {
+                failures += 1;
+            }
+        }
+        
+        results.push(BenchResult {
+            plugin: plugin.name.clone(),
+            runs: repeat,
+            failures,
+            min_ms: timings.iter().cloned().fold(f64::INFINITY, f64::min),
+            mean_ms: timings.iter().sum::<f64>() / repeat as f64,
+            max_ms: timings.iter().cloned().fold(0.0, f64::max),
+        });
+    }
+    
+    results.sort_by(|a, b| a.mean_ms.total_cmp(&b.mean_ms));
+    BenchReport { results, skipped }
+}
+
+/// Write the `bench-all` latency table
+fn write_bench_report<W: std::io::Write>(out: &mut W, report: &BenchReport) -> std::io::Result<()> {
+    if report.results.is_empty() {
+        writeln!(out, "No enabled plugins with example inputs to benchmark.")?;
+    } else {
+        writeln!(out, "{:<20} {:>6} {:>6} {:>10} {:>10} {:>10}", "Plugin", "Runs", "Failed", "Min ms", "Mean ms", "Max ms")?;
+        writeln!(out, "{}", "-".repeat(67))?;
+        for result in &report.results {
+            writeln!(
+                out,
+                "{:<20} {:>6} {:>6} {:>10.3} {:>10.3} {:>10.3}",
+                truncate_string(&result.plugin, 20),
+                result.runs,
+                result.failures,
+                result.min_ms,
+                result.mean_ms,
+                result.max_ms
+            )?;
+        }
+    }
+    for plugin in &report.skipped {
+        writeln!(out, "Skipped '{}': no example inputs", plugin)?;
+    }
+    Ok(())
+}
+
+/// Outcome of running one plugin example during `selftest`
+struct SelftestCase {
+    plugin: String,
+    description: String,
+    /// Why the example failed, `None` when it succeeded
+    error: Option<String>,
+}
+
+fn handle_selftest(manager: &PluginManager) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Running plugin self-test");
+    
+    let cases = run_selftest(manager);
+    write_selftest_report(&mut std::io::stdout().lock(), &cases)?;
+    
+    let failed = cases.iter().filter(|case| case.error.is_some()).count();
+    if failed > 0 {
+        return Err(Box::new(CliError::new(
+            EXIT_EXECUTION_ERROR,
+            "selftest_failed",
+            format!("{} of {} plugin examples failed", failed, cases.len()),
+        )));
+    }
+    Ok(())
+}
+
+/// Execute every example of every enabled plugin, in plugin name order
+fn run_selftest(manager: &PluginManager) -> Vec<SelftestCase> {
+    let mut plugins = manager.list_plugins();
+    sort_plugins(&mut plugins, ListSort::Name, false);
+    
+    let mut cases = Vec::new();
+    for plugin in plugins.iter().filter(|p| plugin_status_label(p) == "enabled") {
+        for (description, input) in manager.get_plugin_examples(&plugin.name).unwrap_or_default() {
+            let error = match manager.execute_plugin(&plugin.name, &input) {
+                Ok(result) if result.success => None,
+                Ok(result) => Some(result.output),
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(error) = &error {
+                warn!("CLI: Self-test example '{}' of plugin '{}' failed: {}", description, plugin.name, error);
+            }
+            cases.push(SelftestCase { plugin: plugin.name.clone(), description, error });
+        }
+    }
+    cases
+}
+
+/// Write one PASS/FAIL line per example, followed by a summary
+fn write_selftest_report<W: std::io::Write>(out: &mut W, cases: &[SelftestCase]) -> std::io::Result<()> {
+    if cases.is_empty() {
+        writeln!(out, "No enabled plugins with example inputs to test.")?;
+        return Ok(());
+    }
+    
+    for case in cases {
+        match &case.error {
+            None => writeln!(out, "PASS  {:<20} {}", truncate_string(&case.plugin, 20), case.description)?,
+            Some(error) => writeln!(out, "FAIL  {:<20} {}: {}", truncate_string(&case.plugin, 20), case.description, error)?,
+        }
+    }
+    let failed = cases.iter().filter(|case| case.error.is_some()).count();
+    writeln!(out, "{} passed, {} failed", cases.len() - failed, failed)?;
+    Ok(())
+}
+
+/// Parse a polling interval such as `500ms`, `2s` or `1m`; bare numbers are seconds
+fn parse_interval(value: &str) -> Result<std::time::Duration, String> {
+    let value = value.trim();
+    let (number, unit_ms) = if let Some(number) = value.strip_suffix("ms") {
+        (number, 1)
+    } else if let Some(number) = value.strip_suffix('s') {
+        (number, 1_000)
+    } else if let Some(number) = value.strip_suffix('m') {
+        (number, 60_000)
+    } else {
+        (value, 1_000)
+    };
+    
+    let millis = number
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|&number| number > 0)
+        .and_then(|number| number.checked_mul(unit_ms));
+    match millis {
+        Some(millis) => Ok(std::time::Duration::from_millis(millis)),
+        None => Err(format!("invalid interval '{}', expected e.g. 500ms, 2s or 1m", value)),
+    }
+}
+
+fn handle_tail_metrics(url: &str, interval: std::time::Duration) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    
+    info!("CLI: Tailing metrics from {} every {:?}", url, interval);
+    
+    let running = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+    let handler_running = running.clone();
+    ctrlc::set_handler(move || handler_running.store(false, std::sync::atomic::Ordering::SeqCst))?;
+    
+    let client = reqwest::blocking::Client::builder().timeout(interval.max(std::time::Duration::from_secs(5))).build()?;
+    while running.load(std::sync::atomic::Ordering::SeqCst) {
+        let mut frame = Vec::new();
+        if let Err(e) = poll_metrics(&client, url, &mut frame) {
+            warn!("CLI: Failed to fetch metrics from {}: {}", url, e);
+            frame.clear();
+            writeln!(frame, "Failed to fetch metrics from {}: {}", url, e)?;
+        }
+        
+        // Clear the terminal and redraw from the top-left corner
+        let mut stdout = std::io::stdout().lock();
+        write!(stdout, "\x1B[2J\x1B[H")?;
+        stdout.write_all(&frame)?;
+        writeln!(stdout, "\nRefreshing every {:?}, press Ctrl+C to exit", interval)?;
+        stdout.flush()?;
+        drop(stdout);
+        
+        let deadline = std::time::Instant::now() + interval;
+        while running.load(std::sync::atomic::Ordering::SeqCst) && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+    }
+    
+    info!("CLI: Stopped tailing metrics");
+    Ok(())
+}
+
+/// A `/metrics/json` body in either the `wrapped` or the `bare` response style
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum MetricsBody {
+    Wrapped(api::ApiResponse<MetricsSnapshot>),
+    Bare(MetricsSnapshot),
+}
+
+/// Fetch `/metrics/json` from the server at `url` once and render it as a table
+fn poll_metrics<W: std::io::Write>(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    out: &mut W,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = format!("{}/metrics/json", url.trim_end_matches('/'));
+    debug!("CLI: Polling {}", endpoint);
+    
+    let snapshot = match client.get(&endpoint).send()?.error_for_status()?.json()? {
+        MetricsBody::Wrapped(response) => response
+            .data
+            .ok_or_else(|| response.error.unwrap_or_else(|| "response contained no metrics".to_string()))?,
+        MetricsBody::Bare(snapshot) => snapshot,
+    };
+    write_metrics_table(out, &snapshot)?;
+    Ok(())
+}
+
+/// Write per-plugin execution counts and latencies
+fn write_metrics_table<W: std::io::Write>(out: &mut W, snapshot: &MetricsSnapshot) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "Executions: {}  Failures: {}  Since: {}",
+        snapshot.executions_total,
+        snapshot.failures_total,
+        snapshot.since.to_rfc3339()
+    )?;
+    writeln!(out)?;
+    if snapshot.plugins.is_empty() {
+        writeln!(out, "No plugin executions recorded yet.")?;
+        return Ok(());
+    }
+    
+    writeln!(out, "{:<20} {:>10} {:>8} {:>10} {:>10}", "Plugin", "Executions", "Failed", "Avg ms", "Max ms")?;
+    writeln!(out, "{}", "-".repeat(62))?;
+    for (plugin, metrics) in &snapshot.plugins {
+        let avg_ms = if metrics.executions == 0 {
+            0.0
+        } else {
+            metrics.total_duration_ms as f64 / metrics.executions as f64
+        };
+        writeln!(
+            out,
+            "{:<20} {:>10} {:>8} {:>10.1} {:>10}",
+            truncate_string(plugin, 20),
+            metrics.executions,
+            metrics.failures,
+            avg_ms,
+            metrics.max_duration_ms
+        )?;
+    }
+    Ok(())
+}
+
+fn handle_history(
+    manager: &PluginManager,
+    since: Option<&str>,
+    limit: Option<usize>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    info!("CLI: Starting history operation (since: {:?}, limit: {:?})", since, limit);
+    
+    let since = match since {
+        Some(since) => {
+            let parsed = chrono::DateTime::parse_from_rfc3339(since).map_err(|e| {
+                error!("CLI: Invalid --since timestamp '{}': {}", since, e);
+                format!("Invalid --since timestamp '{}', expected RFC 3339 (e.g. 2024-01-01T00:00:00Z): {}", since, e)
+            })?;
+            Some(parsed.with_timezone(&chrono::Utc))
+        }
+        None => None,
+    };
+    
+    let executions = manager.recent_executions(since, limit);
+    info!("CLI: Found {} executions", executions.len());
+    
+    if executions.is_empty() {
+        println!("No executions found.");
+        return Ok(());
+    }
+    
+    println!("{:<32} {:<20} {:<10} {:>12}", "Executed At", "Plugin", "Status", "Duration");
+    println!("{}", "-".repeat(77));
+    
+    for execution in executions {
+        println!(
+            "{:<32} {:<20} {:<10} {:>10}ms",
+            execution.executed_at.to_rfc3339(),
+            truncate_string(&execution.plugin, 20),
+            if execution.success { "success" } else { "failed" },
+            execution.duration_ms
+        );
+    }
+    
+    Ok(())
+}
+
+fn handle_config_dump(manager: &PluginManager, format: OutputFormat) -> Result<(), Box<dyn std::error::Error>> {
+    debug!("CLI: Dumping effective configuration");
+    let config = api::redacted_config(manager.config());
+    let output = match format {
+        OutputFormat::Yaml => serde_yaml::to_string(&config)?,
+        OutputFormat::Json => serde_json::to_string_pretty(&config)?,
+    };
+    println!("{}", output.trim_end());
+    Ok(())
+}
+
+/// Write the completion script for `shell` covering every subcommand and option
+fn write_completions<W: std::io::Write>(out: &mut W, shell: clap_complete::Shell) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
 fn handle_serve(
     manager: PluginManager,
     host: &str,
     port: u16,
+    no_banner: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     info!("CLI: Starting HTTP API server on {}:{}", host, port);
     debug!("CLI: Server configuration - host: {}, port: {}", host, port);
@@ -369,7 +1317,7 @@ fn handle_serve(
     }
     
     // Create a new Tokio runtime for the server
-    let rt = match tokio::runtime::Runtime::new() {
+    let rt = match build_server_runtime(manager.config().server.worker_threads) {
         Ok(rt) => {
             debug!("CLI: Tokio runtime created successfully");
             rt
@@ -395,14 +1343,9 @@ fn handle_serve(
         };
         
         info!("CLI: HTTP API server configuration complete, starting server");
-        println!("HTTP API server starting on {}:{}", host_owned, port);
-        println!("Available endpoints:");
-        println!("  GET    /health                     - Health check");
-        println!("  GET    /api/v1/plugins             - List all plugins");
-        println!("  POST   /api/v1/plugins/{{name}}/execute - Execute plugin");
-        println!("  PUT    /api/v1/plugins/{{name}}/enable  - Enable plugin");
-        println!("  PUT    /api/v1/plugins/{{name}}/disable - Disable plugin");
-        println!("Press Ctrl+C to stop the server");
+        if let Err(e) = write_banner(&mut std::io::stdout(), &host_owned, port, no_banner) {
+            warn!("CLI: Failed to print startup banner: {}", e);
+        }
         
         // Start the server with graceful shutdown handling and retry logic
         let server_result = run_server_with_shutdown_and_retry(manager, &host_owned, port, shutdown_signal).await;
@@ -413,7 +1356,9 @@ fn handle_serve(
         
         match server_result {
             Ok(()) => {
-                println!("Server shutdown completed successfully");
+                if !no_banner {
+                    println!("Server shutdown completed successfully");
+                }
                 info!("CLI: Server shutdown completed successfully");
             }
             Err(e) => {
@@ -428,6 +1373,41 @@ fn handle_serve(
     Ok(())
 }
 
+/// Build the server runtime, falling back to a single-threaded runtime when the
+/// multi-threaded one cannot be created (e.g. thread limits in constrained environments)
+fn build_server_runtime(worker_threads: Option<usize>) -> std::io::Result<tokio::runtime::Runtime> {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all();
+    if let Some(worker_threads) = worker_threads {
+        debug!("CLI: Using {} runtime worker threads", worker_threads);
+        builder.worker_threads(worker_threads);
+    }
+    
+    match builder.build() {
+        Ok(rt) => Ok(rt),
+        Err(e) => {
+            warn!("CLI: Failed to create multi-threaded runtime ({}), falling back to a single-threaded runtime", e);
+            tokio::runtime::Builder::new_current_thread().enable_all().build()
+        }
+    }
+}
+
+/// Write the human-facing startup banner and endpoint list, unless suppressed
+fn write_banner<W: std::io::Write>(out: &mut W, host: &str, port: u16, no_banner: bool) -> std::io::Result<()> {
+    if no_banner {
+        debug!("CLI: Startup banner suppressed");
+        return Ok(());
+    }
+    
+    writeln!(out, "HTTP API server starting on {}:{}", host, port)?;
+    writeln!(out, "Available endpoints:")?;
+    for endpoint in api::ENDPOINTS {
+        writeln!(out, "  {:<6} {:<32} - {}", endpoint.method, endpoint.path, endpoint.description)?;
+    }
+    writeln!(out, "Press Ctrl+C to stop the server")?;
+    Ok(())
+}
+
 /// Run the server with graceful shutdown handling and retry logic
 async fn run_server_with_shutdown_and_retry(
     manager: PluginManager,
@@ -445,7 +1425,7 @@ async fn run_server_with_shutdown_and_retry(
             Ok(())
         }
         Err(e) => {
-            if is_recoverable_network_error(&e) {
+            if is_recoverable_network_error(e.as_ref()) {
                 warn!("CLI: Recoverable network error occurred: {}", e);
                 info!("CLI: Server stopped due to network error, but this is recoverable");
                 Ok(())
@@ -458,7 +1438,7 @@ async fn run_server_with_shutdown_and_retry(
 }
 
 /// Check if an error is a recoverable network error
-fn is_recoverable_network_error(error: &Box<dyn std::error::Error>) -> bool {
+fn is_recoverable_network_error(error: &dyn std::error::Error) -> bool {
     let error_str = error.to_string().to_lowercase();
     
     // Common recoverable network errors
@@ -527,18 +1507,66 @@ impl ShutdownManager {
     }
 }
 
+/// Status shown for a plugin in `list` output
+fn plugin_status_label(plugin: &PluginStatus) -> &'static str {
+    if plugin.enabled && plugin.config_enabled {
+        "enabled"
+    } else {
+        "disabled"
+    }
+}
+
+/// Order plugins for display, breaking ties by name so output is reproducible
+fn sort_plugins(plugins: &mut [PluginStatus], sort: ListSort, reverse: bool) {
+    plugins.sort_by(|a, b| {
+        let primary = match sort {
+            ListSort::Name => std::cmp::Ordering::Equal,
+            ListSort::Version => version_key(&a.version).cmp(&version_key(&b.version)),
+            ListSort::Status => plugin_status_label(a).cmp(plugin_status_label(b)),
+        };
+        primary.then_with(|| a.name.cmp(&b.name))
+    });
+    if reverse {
+        plugins.reverse();
+    }
+}
+
+/// Numeric components of a dotted version, so `1.10.0` sorts after `1.9.0`
+fn version_key(version: &str) -> Vec<u64> {
+    version
+        .split('.')
+        .map(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
 /// Truncate a string to a maximum length, adding "..." if truncated
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+        format!("{}...", prefix_at_char_boundary(s, max_len.saturating_sub(3)))
+    }
+}
+
+/// Longest prefix of `s` that fits in `max_len` bytes without splitting a character
+pub(crate) fn prefix_at_char_boundary(s: &str, max_len: usize) -> &str {
+    if s.len() <= max_len {
+        return s;
+    }
+    let mut end = max_len;
+    while !s.is_char_boundary(end) {
+        end -= 1;
     }
+    &s[..end]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_plugins::{EmptyOutputPlugin, SleepPlugin};
 
     #[test]
     fn test_truncate_string() {
@@ -546,9 +1574,50 @@ mod tests {
         assert_eq!(truncate_string("this is a very long string", 10), "this is...");
         assert_eq!(truncate_string("exactly10!", 10), "exactly10!");
         assert_eq!(truncate_string("", 5), "");
+        
+        // Multi-byte characters straddling the cut are dropped rather than split
+        assert_eq!(truncate_string("héllo wörld", 5), "h...");
+        assert_eq!(truncate_string("日本語のテキスト", 10), "日本...");
+        assert_eq!(prefix_at_char_boundary("aé", 2), "a");
+        assert_eq!(prefix_at_char_boundary("😀😀", 5), "😀");
+    }
+
+    #[test]
+    fn test_build_server_runtime_with_one_worker() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        std::fs::write(&config_path, format!(
+            "plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\n  worker_threads: 1\nplugins: {{}}\n",
+            temp_dir.path().join("plugins").to_string_lossy(),
+            temp_dir.path().join("state").to_string_lossy(),
+        )).unwrap();
+        let manager = PluginManager::with_config_path(&config_path).unwrap();
+        assert_eq!(manager.config().server.worker_threads, Some(1));
+        
+        let rt = build_server_runtime(manager.config().server.worker_threads).unwrap();
+        let result = rt.block_on(async move {
+            let (shutdown_tx, shutdown_rx) = tokio::sync::mpsc::channel(1);
+            shutdown_tx.send(()).await.unwrap();
+            api::start_server(manager, "127.0.0.1", 0, shutdown_rx).await.map_err(|e| e.to_string())
+        });
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn test_write_banner() {
+        let mut out = Vec::new();
+        write_banner(&mut out, "127.0.0.1", 8080, false).unwrap();
+        let banner = String::from_utf8(out).unwrap();
+        assert!(banner.contains("HTTP API server starting on 127.0.0.1:8080"));
+        assert!(banner.contains("/api/v1/plugins/{name}/execute"));
+        
+        let mut out = Vec::new();
+        write_banner(&mut out, "127.0.0.1", 8080, true).unwrap();
+        assert!(out.is_empty());
     }
 
     #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)]
     fn test_cli_parsing() {
         // Test that CLI can be parsed (basic smoke test)
         let cli = Cli::try_parse_from(&["dyn-plug", "list"]);
@@ -557,7 +1626,493 @@ mod tests {
         let cli = Cli::try_parse_from(&["dyn-plug", "enable", "test-plugin"]);
         assert!(cli.is_ok());
         
+        let cli = Cli::try_parse_from(["dyn-plug", "batch", "items.json", "--parallel", "4"]);
+        assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "schema", "test-plugin", "--format", "yaml", "-o", "schema.yaml"]);
+        assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "info", "test-plugin"]);
+        assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "list", "--sort", "version", "--reverse"]);
+        assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "list", "--count", "--format", "json"]);
+        assert!(cli.is_ok());
+        
+        // The format only applies to counts
+        let cli = Cli::try_parse_from(["dyn-plug", "list", "--format", "json"]);
+        assert!(cli.is_err());
+        
         let cli = Cli::try_parse_from(&["dyn-plug", "execute", "test-plugin", "--input", "test"]);
         assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "history", "--since", "2024-01-01T00:00:00Z", "--limit", "5"]);
+        assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "serve", "--no-banner"]);
+        assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "config", "dump", "--format", "json"]);
+        assert!(cli.is_ok());
+        
+        let cli = Cli::try_parse_from(["dyn-plug", "--quiet", "serve"]);
+        assert!(cli.is_ok_and(|cli| cli.quiet));
+    }
+    
+    struct UppercasePlugin;
+    
+    impl dyn_plug_core::Plugin for UppercasePlugin {
+        fn name(&self) -> &str { "upper" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Uppercases its input" }
+        fn examples(&self) -> Vec<(String, String)> {
+            vec![("Uppercase a word".to_string(), "hello".to_string())]
+        }
+        fn input_schema(&self) -> Option<serde_json::Value> {
+            Some(serde_json::json!({ "type": "string", "minLength": 1 }))
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            if input.is_empty() {
+                return Err("empty input".into());
+            }
+            Ok(input.to_uppercase())
+        }
     }
-}
\ No newline at end of file
+    
+    /// Plugin whose only example is input it rejects
+    struct BrokenExamplePlugin;
+    
+    impl dyn_plug_core::Plugin for BrokenExamplePlugin {
+        fn name(&self) -> &str { "broken" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Documents an example it cannot run" }
+        fn examples(&self) -> Vec<(String, String)> {
+            vec![("Outdated example".to_string(), "old format".to_string())]
+        }
+        fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Err("unsupported input format".into())
+        }
+    }
+    
+    /// Plugin whose operations come from its input schema
+    struct CalculatorPlugin;
+    
+    impl dyn_plug_core::Plugin for CalculatorPlugin {
+        fn name(&self) -> &str { "calc" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Adds and subtracts" }
+        fn input_schema(&self) -> Option<serde_json::Value> {
+            Some(serde_json::json!({ "properties": { "operation": { "enum": ["add", "subtract"] } } }))
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+    
+    /// Plugin declaring its operations directly
+    struct TextPlugin;
+    
+    impl dyn_plug_core::Plugin for TextPlugin {
+        fn name(&self) -> &str { "text" }
+        fn version(&self) -> &str { "1.0.0" }
+        fn description(&self) -> &str { "Reverses and counts text" }
+        fn capabilities(&self) -> Vec<String> {
+            vec!["reverse".to_string(), "count".to_string()]
+        }
+        fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+            Ok(input.to_string())
+        }
+    }
+    
+    fn create_test_manager() -> (PluginManager, tempfile::TempDir) {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        std::fs::write(&config_path, format!(
+            "plugins_dir: \"{}\"\nstate_dir: \"{}\"\nlog_level: info\nserver:\n  host: 127.0.0.1\n  port: 8080\n  enabled: true\nplugins: {{}}\n",
+            temp_dir.path().join("plugins").to_string_lossy(),
+            temp_dir.path().join("state").to_string_lossy(),
+        )).unwrap();
+        let mut manager = PluginManager::with_config_path(&config_path).unwrap();
+        manager.register_plugin(Box::new(UppercasePlugin)).unwrap();
+        manager.register_plugin(Box::new(SleepPlugin)).unwrap();
+        (manager, temp_dir)
+    }
+    
+    #[test]
+    fn test_run_batch_preserves_order() {
+        let (manager, _temp_dir) = create_test_manager();
+        
+        let items: Vec<BatchItem> = serde_json::from_str(r#"[
+            {"plugin": "upper", "input": "a"},
+            {"plugin": "missing", "input": "b"},
+            {"plugin": "upper", "input": ""},
+            {"plugin": "upper", "input": "d"}
+        ]"#).unwrap();
+        
+        for parallel in [None, Some(3)] {
+            let results = run_batch(&manager, &items, parallel).unwrap();
+            let outcomes: Vec<(&str, bool)> = results.iter().map(|r| (r.plugin.as_str(), r.success)).collect();
+            assert_eq!(outcomes, [("upper", true), ("missing", false), ("upper", false), ("upper", true)]);
+            assert_eq!(results[0].output.as_deref(), Some("A"));
+            assert_eq!(results[3].output.as_deref(), Some("D"));
+            assert!(results[1].error.as_ref().unwrap().contains("missing"));
+        }
+        
+        assert!(run_batch(&manager, &items, Some(0)).is_err());
+    }
+    
+    #[test]
+    fn test_write_completions_for_bash() {
+        let mut out = Vec::new();
+        write_completions(&mut out, clap_complete::Shell::Bash);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("_dyn-plug()"), "{}", out);
+        for subcommand in ["list", "execute", "diff-exec", "serve", "completions"] {
+            assert!(out.contains(subcommand), "missing {}", subcommand);
+        }
+    }
+
+    #[test]
+    fn test_write_explain() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        let mut out = Vec::new();
+        write_explain(&mut out, &manager, "upper", r#"{"operation": "shout"}"#, &ExecutionOptions::default()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Plugin: upper v1.0.0"), "{}", out);
+        assert!(out.contains("Enabled: true (config: true)"), "{}", out);
+        assert!(out.contains("Timeout: 30000ms"), "{}", out);
+        assert!(out.contains("Retries: 2 (delay 100ms)"), "{}", out);
+        assert!(out.contains("Operation: shout"), "{}", out);
+
+        let mut out = Vec::new();
+        write_explain(&mut out, &manager, "missing", "plain", &ExecutionOptions::no_timeout()).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Plugin: missing (not found)"), "{}", out);
+        assert!(out.contains("Timeout: none"), "{}", out);
+        assert!(out.contains("Operation: (none)"), "{}", out);
+    }
+    
+    #[test]
+    fn test_diff_exec_reports_mismatch() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(EmptyOutputPlugin)).unwrap();
+
+        let diff = diff_exec::diff_plugins(&manager, "upper", "empty", "hello").unwrap();
+        assert!(!diff.matches);
+        assert_eq!(diff.left.output, "HELLO");
+        assert_eq!(diff.right.output, "");
+        let mut out = Vec::new();
+        write_plugin_diff(&mut out, &diff).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("Outputs of 'upper' and 'empty' differ"), "{}", out);
+        assert!(out.contains("--- upper\n+++ empty\n"), "{}", out);
+        assert!(out.contains("-HELLO"), "{}", out);
+
+        // Both plugins echo numeric input unchanged
+        let diff = diff_exec::diff_plugins(&manager, "upper", "sleep", "5").unwrap();
+        assert!(diff.matches);
+        assert!(diff.diff.is_none());
+
+        assert!(diff_exec::diff_plugins(&manager, "upper", "missing", "hello").is_err());
+    }
+    
+    #[test]
+    fn test_dry_run_enable_changes_nothing() {
+        let (mut manager, temp_dir) = create_test_manager();
+        manager.disable_plugin("upper").unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        let before = std::fs::read_to_string(&config_path).unwrap();
+        
+        handle_enable(&mut manager, "upper", true).unwrap();
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), before);
+        let status = manager.get_plugin_status("upper").unwrap();
+        assert!(!status.enabled && !status.config_enabled);
+        
+        let mut out = Vec::new();
+        write_state_preview(&mut out, &status, true).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "upper: disabled -> enabled\n");
+        let mut out = Vec::new();
+        write_state_preview(&mut out, &status, false).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "upper: disabled (no change)\n");
+        
+        assert!(handle_disable(&mut manager, "missing", true).is_err());
+    }
+    
+    #[test]
+    fn test_handle_schema() {
+        let (manager, temp_dir) = create_test_manager();
+        let output = temp_dir.path().join("schema.json");
+        
+        handle_schema(&manager, "upper", OutputFormat::Json, Some(&output)).unwrap();
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+        assert_eq!(written, serde_json::json!({ "type": "string", "minLength": 1 }));
+        
+        assert!(handle_schema(&manager, "missing", OutputFormat::Json, None).is_err());
+    }
+    
+    #[test]
+    fn test_exit_code_for() {
+        assert_eq!(exit_code_for(&PluginError::NotFound { name: "x".to_string() }), EXIT_NOT_FOUND);
+        assert_eq!(exit_code_for(&PluginError::PluginDisabled { name: "x".to_string() }), EXIT_DISABLED);
+        assert_eq!(exit_code_for(&PluginError::TimeoutError { operation: "x".to_string() }), EXIT_TIMEOUT);
+        assert_eq!(exit_code_for(&PluginError::execution_failed("boom")), EXIT_EXECUTION_ERROR);
+        assert_eq!(exit_code_for(&PluginError::config_error("bad")), EXIT_FAILURE);
+    }
+    
+    #[test]
+    fn test_error_json() {
+        let error = CliError::from_plugin_error(&PluginError::TimeoutError { operation: "execute".to_string() });
+        let json = error_json(&error);
+        assert_eq!(json["category"], "timeout_error");
+        assert_eq!(json["code"], EXIT_TIMEOUT);
+        
+        let error = CliError::execution_failed(None, "boom");
+        assert_eq!(error_json(&error)["category"], "execution_failed");
+        assert_eq!(error.code, EXIT_EXECUTION_ERROR);
+    }
+    
+    #[test]
+    fn test_run_batch_reports_queue_time() {
+        let (manager, _temp_dir) = create_test_manager();
+        let items: Vec<BatchItem> = serde_json::from_str(r#"[
+            {"plugin": "sleep", "input": "60"},
+            {"plugin": "sleep", "input": "0"}
+        ]"#).unwrap();
+        
+        // A single worker is saturated by the first item, so the second waits
+        let results = run_batch(&manager, &items, Some(1)).unwrap();
+        assert!(results.iter().all(|r| r.success));
+        assert!(results[0].exec_ms >= 60);
+        assert!(results[1].queue_ms > 0, "second item should have been queued: {:?}", results[1]);
+        
+        // Without a pool nothing is queued
+        let results = run_batch(&manager, &items, None).unwrap();
+        assert!(results.iter().all(|r| r.queue_ms == 0));
+    }
+
+    fn config_only_status(name: &str, version: &str, enabled: bool) -> PluginStatus {
+        PluginStatus {
+            name: name.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            enabled,
+            loaded: false,
+            path: std::path::PathBuf::new(),
+            config_enabled: enabled,
+            last_state_change: None,
+            config_error: None,
+        }
+    }
+
+    fn names(plugins: &[PluginStatus]) -> Vec<&str> {
+        plugins.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_plugins() {
+        let mut plugins = vec![
+            config_only_status("gamma", "1.10.0", true),
+            config_only_status("alpha", "1.9.0", false),
+            config_only_status("beta", "2.0.0", true),
+        ];
+
+        sort_plugins(&mut plugins, ListSort::Name, false);
+        assert_eq!(names(&plugins), ["alpha", "beta", "gamma"]);
+
+        sort_plugins(&mut plugins, ListSort::Version, false);
+        assert_eq!(names(&plugins), ["alpha", "gamma", "beta"]);
+
+        sort_plugins(&mut plugins, ListSort::Status, false);
+        assert_eq!(names(&plugins), ["alpha", "beta", "gamma"]);
+
+        sort_plugins(&mut plugins, ListSort::Name, true);
+        assert_eq!(names(&plugins), ["gamma", "beta", "alpha"]);
+    }
+
+    #[test]
+    fn test_count_plugins() {
+        let plugins = vec![
+            config_only_status("alpha", "1.0.0", true),
+            config_only_status("beta", "1.0.0", false),
+            config_only_status("gamma", "1.0.0", true),
+            config_only_status("delta", "1.0.0", false),
+            config_only_status("epsilon", "1.0.0", false),
+        ];
+        let counts = count_plugins(&plugins, 2);
+        assert_eq!(counts, PluginCounts { enabled: 2, disabled: 3, total: 5, failed: 2 });
+        
+        let mut out = Vec::new();
+        write_counts(&mut out, &counts, None).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "enabled=2 disabled=3 total=5 failed=2\n");
+        
+        let mut out = Vec::new();
+        write_counts(&mut out, &counts, Some(OutputFormat::Json)).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json, serde_json::json!({"enabled": 2, "disabled": 3, "total": 5, "failed": 2}));
+    }
+
+    #[test]
+    fn test_bench_all_reports_each_plugin() {
+        let (manager, _temp_dir) = create_test_manager();
+        
+        let report = run_bench_all(&manager, 2);
+        let plugins: Vec<&str> = report.results.iter().map(|r| r.plugin.as_str()).collect();
+        assert_eq!(plugins, ["upper", "sleep"]);
+        assert!(report.results.iter().all(|r| r.runs == 2 && r.failures == 0));
+        assert!(report.results[1].min_ms >= 20.0);
+        
+        let mut out = Vec::new();
+        write_bench_report(&mut out, &report).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        let row = |name: &str| table.lines().find(|l| l.starts_with(name)).unwrap().to_string();
+        assert!(row("upper").contains("2"));
+        assert!(row("sleep").split_whitespace().nth(3).unwrap().parse::<f64>().unwrap() >= 20.0);
+    }
+
+    #[test]
+    fn test_selftest_fails_when_any_example_fails() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        assert!(handle_selftest(&manager).is_ok());
+        
+        manager.register_plugin(Box::new(BrokenExamplePlugin)).unwrap();
+        let cases = run_selftest(&manager);
+        let outcomes: Vec<(&str, bool)> = cases.iter().map(|c| (c.plugin.as_str(), c.error.is_none())).collect();
+        assert_eq!(outcomes, [("broken", false), ("sleep", true), ("upper", true)]);
+        
+        let mut out = Vec::new();
+        write_selftest_report(&mut out, &cases).unwrap();
+        let report = String::from_utf8(out).unwrap();
+        assert!(report.contains("FAIL  broken"));
+        assert!(report.ends_with("2 passed, 1 failed\n"));
+        
+        let error = handle_selftest(&manager).unwrap_err().downcast::<CliError>().unwrap();
+        assert_eq!(error.code, EXIT_EXECUTION_ERROR);
+        assert_eq!(error.category, "selftest_failed");
+    }
+
+    #[test]
+    fn test_operations_aggregated_across_plugins() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(CalculatorPlugin)).unwrap();
+        manager.register_plugin(Box::new(TextPlugin)).unwrap();
+        
+        // Plugins without declared operations are left out
+        let operations = manager.list_operations();
+        assert_eq!(operations, BTreeMap::from([
+            ("calc".to_string(), vec!["add".to_string(), "subtract".to_string()]),
+            ("text".to_string(), vec!["reverse".to_string(), "count".to_string()]),
+        ]));
+        
+        let mut out = Vec::new();
+        write_operations(&mut out, &operations, false).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "calc: add, subtract\ntext: reverse, count\n");
+        
+        let mut out = Vec::new();
+        write_operations(&mut out, &operations, true).unwrap();
+        let rows: Vec<Vec<String>> = String::from_utf8(out).unwrap()
+            .lines()
+            .skip(1)
+            .map(|line| line.split_whitespace().map(str::to_string).collect())
+            .collect();
+        assert_eq!(rows, [["add", "calc"], ["count", "text"], ["reverse", "text"], ["subtract", "calc"]]);
+    }
+
+    #[test]
+    fn test_empty_output_is_marked() {
+        let (mut manager, _temp_dir) = create_test_manager();
+        manager.register_plugin(Box::new(EmptyOutputPlugin)).unwrap();
+        
+        let result = manager.execute_plugin("empty", "ignored").unwrap();
+        assert!(result.success);
+        let mut out = Vec::new();
+        write_execution_result(&mut out, &result).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("Output: (empty)\n"));
+        
+        let result = manager.execute_plugin("upper", "abc").unwrap();
+        let mut out = Vec::new();
+        write_execution_result(&mut out, &result).unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("Output: ABC\n"));
+    }
+    
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("500ms").unwrap(), std::time::Duration::from_millis(500));
+        assert_eq!(parse_interval("2s").unwrap(), std::time::Duration::from_secs(2));
+        assert_eq!(parse_interval("1m").unwrap(), std::time::Duration::from_secs(60));
+        assert_eq!(parse_interval("3").unwrap(), std::time::Duration::from_secs(3));
+        assert!(parse_interval("0s").is_err());
+        assert!(parse_interval("soon").is_err());
+        assert!(parse_interval(&format!("{}m", u64::MAX)).is_err());
+    }
+
+    /// Metrics as served by `/metrics/json` in the `bare` response style
+    fn metrics_body() -> serde_json::Value {
+        serde_json::json!({
+            "since": "2024-01-01T00:00:00Z",
+            "executions_total": 5,
+            "failures_total": 1,
+            "plugins": {
+                "plugin_a": {"executions": 4, "failures": 0, "total_duration_ms": 10, "max_duration_ms": 6},
+                "plugin_b": {"executions": 1, "failures": 1, "total_duration_ms": 3, "max_duration_ms": 3}
+            }
+        })
+    }
+
+    /// Start a mock server answering a single request with `body`, returning its URL
+    /// and a handle yielding the request line
+    fn serve_once(body: String) -> (String, std::thread::JoinHandle<String>) {
+        use std::io::{Read, Write};
+        
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let read = stream.read(&mut request).unwrap();
+            let request_line = String::from_utf8_lossy(&request[..read]).lines().next().unwrap().to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request_line
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn test_poll_metrics_renders_plugins() {
+        let body = serde_json::json!({"success": true, "data": metrics_body()}).to_string();
+        let (url, server) = serve_once(body);
+        
+        let client = reqwest::blocking::Client::new();
+        let mut out = Vec::new();
+        poll_metrics(&client, &format!("{}/", url), &mut out).unwrap();
+        assert_eq!(server.join().unwrap(), "GET /metrics/json HTTP/1.1");
+        
+        let table = String::from_utf8(out).unwrap();
+        assert!(table.starts_with("Executions: 5  Failures: 1"));
+        let row = |name: &str| table.lines().find(|l| l.starts_with(name)).unwrap().split_whitespace().collect::<Vec<_>>();
+        assert_eq!(row("plugin_a"), ["plugin_a", "4", "0", "2.5", "6"]);
+        assert_eq!(row("plugin_b"), ["plugin_b", "1", "1", "3.0", "3"]);
+    }
+
+    #[test]
+    fn test_poll_metrics_accepts_bare_responses() {
+        let (url, server) = serve_once(metrics_body().to_string());
+        
+        let client = reqwest::blocking::Client::new();
+        let mut out = Vec::new();
+        poll_metrics(&client, &url, &mut out).unwrap();
+        server.join().unwrap();
+        
+        let table = String::from_utf8(out).unwrap();
+        assert!(table.starts_with("Executions: 5  Failures: 1"));
+        assert!(table.lines().any(|l| l.starts_with("plugin_a")));
+    }
+}
@@ -0,0 +1,31 @@
+//! Plugins shared by the CLI and API unit tests
+
+use dyn_plug_core::Plugin;
+
+/// Sleeps for the number of milliseconds given as input
+pub struct SleepPlugin;
+
+impl Plugin for SleepPlugin {
+    fn name(&self) -> &str { "sleep" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Sleeps for the given number of milliseconds" }
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![("Sleep briefly".to_string(), "20".to_string())]
+    }
+    fn execute(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        std::thread::sleep(std::time::Duration::from_millis(input.parse()?));
+        Ok(input.to_string())
+    }
+}
+
+/// Plugin that succeeds without producing any output
+pub struct EmptyOutputPlugin;
+
+impl Plugin for EmptyOutputPlugin {
+    fn name(&self) -> &str { "empty" }
+    fn version(&self) -> &str { "1.0.0" }
+    fn description(&self) -> &str { "Returns an empty string" }
+    fn execute(&self, _input: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(String::new())
+    }
+}
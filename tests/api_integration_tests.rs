@@ -21,6 +21,7 @@ plugins: {}
     Ok(())
 }
 
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn build_binary() -> Result<(), Box<dyn std::error::Error>> {
     let output = Command::new("cargo")
         .args(&["build", "--bin", "dyn-plug"])
@@ -34,6 +35,7 @@ fn build_binary() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn test_api_server_startup() -> Result<(), Box<dyn std::error::Error>> {
     let _ = env_logger::builder().is_test(true).try_init();
     
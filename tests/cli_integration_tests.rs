@@ -4,6 +4,7 @@ use tempfile::TempDir;
 use std::path::PathBuf;
 
 /// Helper function to build the CLI binary for testing
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn build_cli_binary() -> PathBuf {
     let output = Command::new("cargo")
         .args(&["build", "--bin", "dyn-plug"])
@@ -39,6 +40,22 @@ fn run_cli_command(args: &[&str], config_dir: Option<&std::path::Path>) -> std::
     cmd.output().expect("Failed to execute CLI command")
 }
 
+/// Build plugin_a and install it into `plugins_dir`
+fn install_plugin_a(plugins_dir: &std::path::Path) {
+    let output = Command::new("cargo")
+        .args(["build", "-p", "plugin_a"])
+        .output()
+        .expect("Failed to build plugin_a");
+    assert!(output.status.success(), "Failed to build plugin_a: {}", String::from_utf8_lossy(&output.stderr));
+    
+    let library_name = format!("{}plugin_a.{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_EXTENSION);
+    fs::create_dir_all(plugins_dir).unwrap();
+    fs::copy(
+        std::env::current_dir().unwrap().join("target").join("debug").join(&library_name),
+        plugins_dir.join(&library_name),
+    ).unwrap();
+}
+
 #[test]
 fn test_cli_list_command() {
     let _ = env_logger::builder().is_test(true).try_init();
@@ -326,4 +343,137 @@ plugins: {
     if !output.status.success() {
         assert!(stderr.contains("config") || stderr.contains("yaml") || stderr.contains("parse"));
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_cli_config_dump_reflects_env_override() {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("config.yaml");
+    install_plugin_a(&temp_dir.path().join("plugins"));
+    
+    let config_content = r#"
+plugins_dir: "plugins"
+log_level: "info"
+server:
+  host: "127.0.0.1"
+  port: 8080
+  enabled: true
+  api_key: "dump-secret"
+plugins:
+  plugin_a:
+    enabled: true
+    settings: {}
+"#;
+    fs::write(&config_path, config_content).unwrap();
+    
+    let binary_path = build_cli_binary();
+    let run = |args: &[&str]| {
+        Command::new(&binary_path)
+            .args(args)
+            .current_dir(temp_dir.path())
+            .env("DYN_PLUG_SERVER_PORT", "9191")
+            .output()
+            .expect("Failed to execute CLI command")
+    };
+    
+    let output = run(&["config", "dump", "--format", "json"]);
+    assert!(output.status.success(), "config dump failed: {}", String::from_utf8_lossy(&output.stderr));
+    let dumped: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(dumped["server"]["port"], 9191);
+    assert_eq!(dumped["server"]["api_key"], "<redacted>");
+    
+    // Saving the configuration must not persist the override
+    let output = run(&["disable", "plugin_a"]);
+    assert!(output.status.success(), "disable failed: {}", String::from_utf8_lossy(&output.stderr));
+    
+    let output = run(&["config", "dump"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("port: 9191"));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("dump-secret"));
+    
+    let saved: serde_yaml::Value = serde_yaml::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+    assert_eq!(saved["plugins"]["plugin_a"]["enabled"], serde_yaml::Value::Bool(false));
+    assert_eq!(saved["server"]["port"], serde_yaml::Value::from(8080));
+    assert_eq!(saved["server"]["api_key"], serde_yaml::Value::from("dump-secret"));
+}
+
+#[test]
+fn test_cli_exit_code_plugin_not_found() {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_content = r#"
+plugins_dir: "target/plugins"
+log_level: "info"
+server:
+  host: "127.0.0.1"
+  port: 8080
+  enabled: true
+plugins: {}
+"#;
+    fs::write(temp_dir.path().join("config.yaml"), config_content).unwrap();
+    
+    for args in [["execute", "nonexistent_plugin"], ["enable", "nonexistent_plugin"]] {
+        let output = run_cli_command(&args, Some(temp_dir.path()));
+        assert_eq!(output.status.code(), Some(2), "unexpected exit code for {:?}", args);
+    }
+}
+
+#[test]
+fn test_cli_exit_code_plugin_disabled() {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    // Install plugin_a into a private plugins directory
+    let temp_dir = TempDir::new().unwrap();
+    install_plugin_a(&temp_dir.path().join("plugins"));
+    
+    let config_content = r#"
+plugins_dir: "plugins"
+log_level: "info"
+server:
+  host: "127.0.0.1"
+  port: 8080
+  enabled: true
+plugins:
+  plugin_a:
+    enabled: false
+    settings: {}
+"#;
+    fs::write(temp_dir.path().join("config.yaml"), config_content).unwrap();
+    
+    let output = run_cli_command(
+        &["execute", "plugin_a", "--input", r#"{"operation":"uppercase","text":"hi"}"#],
+        Some(temp_dir.path()),
+    );
+    assert_eq!(output.status.code(), Some(3), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn test_cli_json_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+    
+    let temp_dir = TempDir::new().unwrap();
+    let config_content = r#"
+plugins_dir: "target/plugins"
+log_level: "info"
+server:
+  host: "127.0.0.1"
+  port: 8080
+  enabled: true
+plugins: {}
+"#;
+    fs::write(temp_dir.path().join("config.yaml"), config_content).unwrap();
+    
+    let output = run_cli_command(&["enable", "nonexistent", "--json-errors"], Some(temp_dir.path()));
+    assert_eq!(output.status.code(), Some(2));
+    
+    // Log lines may precede the error object on stderr; the error is the last line
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let last_line = stderr.lines().rev().find(|line| !line.trim().is_empty()).unwrap();
+    let error: serde_json::Value = serde_json::from_str(last_line).expect("stderr should end with a JSON error");
+    assert_eq!(error["category"], "not_found");
+    assert_eq!(error["code"], 2);
+    assert!(error["error"].as_str().unwrap().contains("nonexistent"));
+}
@@ -6,6 +6,7 @@ use std::time::Duration;
 use std::thread;
 
 /// Helper function to build all plugins
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn build_plugins() -> Result<(), Box<dyn std::error::Error>> {
     let output = Command::new("cargo")
         .args(&["build", "--release"])